@@ -5,7 +5,13 @@ use dropset_interface::{
         CancelOrderInstructionData,
         PostOrderInstructionData,
     },
-    state::sector::SectorIndex,
+    state::{
+        order::{
+            NO_CLIENT_ORDER_ID,
+            NO_EXPIRY,
+        },
+        sector::SectorIndex,
+    },
 };
 use itertools::Itertools;
 use price::{
@@ -56,22 +62,34 @@ pub fn get_non_redundant_order_flow(
 
     let cancels = unique_bid_cancels
         .iter()
-        .map(|c| CancelOrderInstructionData::new(c.encoded_price, true, maker_seat_index))
-        .chain(
-            unique_ask_cancels
-                .iter()
-                .map(|c| CancelOrderInstructionData::new(c.encoded_price, false, maker_seat_index)),
-        )
+        .map(|c| {
+            CancelOrderInstructionData::new(c.index, true, maker_seat_index, NO_CLIENT_ORDER_ID)
+        })
+        .chain(unique_ask_cancels.iter().map(|c| {
+            CancelOrderInstructionData::new(c.index, false, maker_seat_index, NO_CLIENT_ORDER_ID)
+        }))
         .collect_vec();
 
     let posts = unique_bid_posts
         .into_iter()
-        .map(|p| PostOrderInstructionData::new(p.clone(), true, maker_seat_index))
-        .chain(
-            unique_ask_posts
-                .into_iter()
-                .map(|p| PostOrderInstructionData::new(p.clone(), false, maker_seat_index)),
-        )
+        .map(|p| {
+            PostOrderInstructionData::new(
+                p.clone(),
+                true,
+                maker_seat_index,
+                NO_EXPIRY,
+                NO_CLIENT_ORDER_ID,
+            )
+        })
+        .chain(unique_ask_posts.into_iter().map(|p| {
+            PostOrderInstructionData::new(
+                p.clone(),
+                false,
+                maker_seat_index,
+                NO_EXPIRY,
+                NO_CLIENT_ORDER_ID,
+            )
+        }))
         .collect_vec();
 
     Ok((cancels, posts))
@@ -99,6 +117,7 @@ pub fn to_order_view_map(orders: Vec<OrderView>) -> HashMap<OrderAsKey, OrderVie
 
 #[cfg(test)]
 mod tests {
+    use dropset_interface::state::sector::NIL;
     use price::EncodedPrice;
     use rust_decimal::dec;
 
@@ -106,18 +125,20 @@ mod tests {
 
     const MAKER_SEAT_INDEX: SectorIndex = 0;
 
-    /// Helper to create an OrderView stub based on the input price and size.
-    fn to_order_view_stub(price: Decimal, size: u64) -> OrderView {
+    /// Helper to create an OrderView stub in the given sector based on the input price and size.
+    fn to_order_view_stub(sector_index: SectorIndex, price: Decimal, size: u64) -> OrderView {
         let args = to_order_info_args(price, size).unwrap();
         let info = to_order_info(args).unwrap();
         OrderView {
-            prev_index: 0,
-            index: 0,
-            next_index: 0,
+            prev_index: NIL,
+            index: sector_index,
+            next_index: NIL,
             encoded_price: info.encoded_price.as_u32(),
             user_seat: MAKER_SEAT_INDEX,
             base_remaining: info.base_atoms,
             quote_remaining: info.quote_atoms,
+            expiry: NO_EXPIRY,
+            client_order_id: NO_CLIENT_ORDER_ID,
         }
     }
 
@@ -135,16 +156,23 @@ mod tests {
         // The orders with price 3 are thus redundant.
         let size = 1;
 
-        let cancel_1 = to_order_view_stub(dec!(1.00), size);
-        let cancel_2 = to_order_view_stub(dec!(2.00), size);
-        let cancel_3 = to_order_view_stub(dec!(3.00), size);
+        let bid_cancels = [
+            to_order_view_stub(1, dec!(1.00), size),
+            to_order_view_stub(2, dec!(2.00), size),
+            to_order_view_stub(3, dec!(3.00), size),
+        ];
+        let ask_cancels = [
+            to_order_view_stub(4, dec!(1.00), size),
+            to_order_view_stub(5, dec!(2.00), size),
+            to_order_view_stub(6, dec!(3.00), size),
+        ];
         let post_3 = (dec!(3.00), size);
         let post_4 = (dec!(4.00), size);
         let post_5 = (dec!(5.00), size);
 
         let (cancels, posts) = get_non_redundant_order_flow(
-            vec![cancel_1.clone(), cancel_2.clone(), cancel_3.clone()],
-            vec![cancel_1, cancel_2, cancel_3],
+            bid_cancels.to_vec(),
+            ask_cancels.to_vec(),
             vec![post_3, post_4, post_5],
             vec![post_3, post_4, post_5],
             MAKER_SEAT_INDEX,
@@ -156,12 +184,20 @@ mod tests {
         // 2 unique bid posts + 2 unique ask posts = 4 (price 3 filtered out)
         assert_eq!(posts.len(), 4);
 
-        // Verify price 3 was filtered out.
+        // Ensure the cancels are exactly the bid and ask orders that aren't at price 3.
+        let cancelled = cancels
+            .iter()
+            .map(|c| (c.order_sector_index, c.is_bid))
+            .sorted()
+            .collect_vec();
+        assert_eq!(
+            cancelled,
+            vec![(1, true), (2, true), (4, false), (5, false)]
+        );
+
+        // Ensure the posts don't have any orders with price 3.
         let price_3_info = to_order_info(to_order_info_args(dec!(3.00), size).unwrap()).unwrap();
         let price_3_encoded = price_3_info.encoded_price.as_u32();
-
-        // Ensure that both cancels and posts don't have any orders with price 3.
-        assert!(!cancels.iter().any(|c| c.encoded_price == price_3_encoded));
         assert!(!posts
             .into_iter()
             .any(|p| post_data_to_encoded_price(p).as_u32() == price_3_encoded));
@@ -181,14 +217,16 @@ mod tests {
         // Orders are only redundant if both price AND size match.
         // cancel_1 and post_1 match in price and size → redundant
         // cancel_2 and post_2 have unique (price, size) tuples → not redundant
-        let cancel_1 = to_order_view_stub(dec!(1.00), 10000);
-        let cancel_2 = to_order_view_stub(dec!(1.00), 11111); // different size
+        let bid_cancel_1 = to_order_view_stub(1, dec!(1.00), 10000);
+        let bid_cancel_2 = to_order_view_stub(2, dec!(1.00), 11111); // different size
+        let ask_cancel_1 = to_order_view_stub(3, dec!(1.00), 10000);
+        let ask_cancel_2 = to_order_view_stub(4, dec!(1.00), 11111); // different size
         let post_1 = (dec!(1.00), 10000);
         let post_2 = (dec!(1.11), 10000); // different price
 
         let (cancels, posts) = get_non_redundant_order_flow(
-            vec![cancel_1.clone(), cancel_2.clone()],
-            vec![cancel_1.clone(), cancel_2.clone()],
+            vec![bid_cancel_1, bid_cancel_2.clone()],
+            vec![ask_cancel_1, ask_cancel_2.clone()],
             vec![post_1, post_2],
             vec![post_1, post_2],
             MAKER_SEAT_INDEX,
@@ -201,11 +239,17 @@ mod tests {
             cancels,
             vec![
                 CancelOrderInstructionData::new(
-                    cancel_2.clone().encoded_price,
+                    bid_cancel_2.index,
                     true,
-                    MAKER_SEAT_INDEX
+                    MAKER_SEAT_INDEX,
+                    NO_CLIENT_ORDER_ID
+                ),
+                CancelOrderInstructionData::new(
+                    ask_cancel_2.index,
+                    false,
+                    MAKER_SEAT_INDEX,
+                    NO_CLIENT_ORDER_ID
                 ),
-                CancelOrderInstructionData::new(cancel_2.encoded_price, false, MAKER_SEAT_INDEX),
             ]
         );
 
@@ -215,8 +259,20 @@ mod tests {
         assert_eq!(
             posts,
             vec![
-                PostOrderInstructionData::new(p2.clone(), true, MAKER_SEAT_INDEX),
-                PostOrderInstructionData::new(p2, false, MAKER_SEAT_INDEX),
+                PostOrderInstructionData::new(
+                    p2.clone(),
+                    true,
+                    MAKER_SEAT_INDEX,
+                    NO_EXPIRY,
+                    NO_CLIENT_ORDER_ID
+                ),
+                PostOrderInstructionData::new(
+                    p2,
+                    false,
+                    MAKER_SEAT_INDEX,
+                    NO_EXPIRY,
+                    NO_CLIENT_ORDER_ID
+                ),
             ]
         );
     }
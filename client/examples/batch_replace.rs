@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use client::{
+    e2e_helpers::{
+        test_accounts,
+        E2e,
+        Trader,
+    },
+    transactions::{
+        CustomRpcClient,
+        SendTransactionConfig,
+    },
+};
+use dropset_interface::{
+    instructions::{
+        BatchCancels,
+        BatchPosts,
+        BatchReplaceInstructionData,
+        CancelOrderArgs,
+        PostOrderArgs,
+    },
+    state::{
+        order::{
+            NO_CLIENT_ORDER_ID,
+            NO_EXPIRY,
+        },
+        sector::NIL,
+    },
+};
+use price::{
+    to_order_info,
+    OrderInfoArgs,
+};
+use solana_sdk::signer::Signer;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let rpc = CustomRpcClient::new(
+        None,
+        Some(SendTransactionConfig {
+            compute_budget: Some(2000000),
+            debug_logs: Some(true),
+            program_id_filter: HashSet::from([dropset_interface::program::ID]),
+        }),
+    );
+
+    let trader = test_accounts::acc_1111();
+    let e2e = E2e::new_traders_and_market(Some(rpc), [Trader::new(trader, 10000, 10000)]).await?;
+
+    e2e.market
+        .deposit_base(trader.pubkey(), 1000, NIL)
+        .send_single_signer(&e2e.rpc, trader)
+        .await?;
+
+    e2e.market
+        .deposit_quote(trader.pubkey(), 1000, NIL)
+        .send_single_signer(&e2e.rpc, trader)
+        .await?;
+
+    let user_seat = e2e
+        .fetch_seat(&trader.pubkey())
+        .await?
+        .expect("User should have been registered on deposit");
+
+    let bid_args = OrderInfoArgs::new_unscaled(10_000_000, 100);
+    let ask_args = OrderInfoArgs::new_unscaled(20_000_000, 100);
+
+    // Post a bid and an ask in a single batch with no cancels.
+    let post_res = e2e
+        .market
+        .batch_replace(
+            trader.pubkey(),
+            BatchReplaceInstructionData::new(
+                BatchCancels::default(),
+                BatchPosts::new(&[
+                    PostOrderArgs::new(bid_args.clone(), true, NO_EXPIRY, NO_CLIENT_ORDER_ID),
+                    PostOrderArgs::new(ask_args.clone(), false, NO_EXPIRY, NO_CLIENT_ORDER_ID),
+                ])
+                .expect("Should be a valid batch"),
+                user_seat.index,
+            ),
+        )
+        .send_single_signer(&e2e.rpc, trader)
+        .await?;
+
+    println!(
+        "Batch post transaction signature: {}",
+        post_res.parsed_transaction.signature
+    );
+
+    println!(
+        "Market after batch posting a bid and an ask:\n{:#?}",
+        e2e.view_market().await?
+    );
+
+    let bid_info = to_order_info(bid_args).expect("Should be a valid order");
+    let ask_info = to_order_info(ask_args).expect("Should be a valid order");
+
+    // Look up the posted orders' sector indices in the user's orders.
+    let market_view = e2e.view_market().await?;
+    let user_data = &market_view.users[&trader.pubkey()];
+    let bid_sector_index = user_data
+        .bids
+        .iter()
+        .find(|order| order.encoded_price == bid_info.encoded_price.as_u32())
+        .expect("User should have a bid at the bid's price")
+        .index;
+    let ask_sector_index = user_data
+        .asks
+        .iter()
+        .find(|order| order.encoded_price == ask_info.encoded_price.as_u32())
+        .expect("User should have an ask at the ask's price")
+        .index;
+
+    // Atomically replace both orders with orders at new prices.
+    let replace_res = e2e
+        .market
+        .batch_replace(
+            trader.pubkey(),
+            BatchReplaceInstructionData::new(
+                BatchCancels::new(&[
+                    CancelOrderArgs::new(bid_sector_index, true),
+                    CancelOrderArgs::new(ask_sector_index, false),
+                ])
+                .expect("Should be a valid batch"),
+                BatchPosts::new(&[
+                    PostOrderArgs::new(
+                        OrderInfoArgs::new_unscaled(11_000_000, 100),
+                        true,
+                        NO_EXPIRY,
+                        NO_CLIENT_ORDER_ID,
+                    ),
+                    PostOrderArgs::new(
+                        OrderInfoArgs::new_unscaled(19_000_000, 100),
+                        false,
+                        NO_EXPIRY,
+                        NO_CLIENT_ORDER_ID,
+                    ),
+                ])
+                .expect("Should be a valid batch"),
+                user_seat.index,
+            ),
+        )
+        .send_single_signer(&e2e.rpc, trader)
+        .await?;
+
+    println!(
+        "Batch replace transaction signature: {}",
+        replace_res.parsed_transaction.signature
+    );
+
+    let user_seat = e2e.fetch_seat(&trader.pubkey()).await?.unwrap();
+    println!("User seat after batch replace: {user_seat:#?}");
+
+    println!(
+        "Market after batch replace:\n{:#?}",
+        e2e.view_market().await?
+    );
+
+    Ok(())
+}
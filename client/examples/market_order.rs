@@ -11,12 +11,23 @@ use client::{
     transactions::ParsedTransactionWithEvents,
 };
 use dropset_interface::{
-    events::MarketOrderEventInstructionData,
+    events::{
+        FillEventInstructionData,
+        MarketOrderEventInstructionData,
+    },
     instructions::{
         MarketOrderInstructionData,
         PostOrderInstructionData,
+        SelfTradePrevention,
+        TimeInForce,
+    },
+    state::{
+        order::{
+            NO_CLIENT_ORDER_ID,
+            NO_EXPIRY,
+        },
+        sector::NIL,
     },
-    state::sector::NIL,
 };
 use itertools::Itertools;
 use price::{
@@ -24,6 +35,8 @@ use price::{
     to_order_info,
     OrderInfo,
     OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+    ENCODED_PRICE_ZERO,
 };
 use solana_address::Address;
 use solana_sdk::{
@@ -189,6 +202,13 @@ async fn run_partial_fill(
                 },
                 taker_is_market_buy,
                 denomination.is_base(),
+                match taker_is_market_buy {
+                    true => ENCODED_PRICE_INFINITY,
+                    false => ENCODED_PRICE_ZERO,
+                },
+                0,
+                TimeInForce::FillOrKill,
+                SelfTradePrevention::CancelResting,
             ),
         )
         .send_single_signer(&e2e.rpc, ctx.taker)
@@ -225,6 +245,8 @@ async fn post_maker_order(
                 ctx.order_info_args.clone(),
                 matches!(ctx.maker_side, BookSide::Bid),
                 maker_seat.index,
+                NO_EXPIRY,
+                NO_CLIENT_ORDER_ID,
             ),
         )
         .send_single_signer(&e2e.rpc, ctx.maker)
@@ -424,6 +446,43 @@ async fn main() -> anyhow::Result<()> {
         assert_eq!(event_1.quote_filled, event_2.quote_filled);
         assert_eq!(event_1.base_filled, taker_base_size);
         assert_eq!(event_1.quote_filled, taker_quote_size);
+
+        // Check that both orders ran as fill-or-kill and were filled entirely.
+        assert_eq!(event_1.time_in_force, TimeInForce::FillOrKill);
+        assert_eq!(event_2.time_in_force, TimeInForce::FillOrKill);
+        assert!(event_1.is_fully_filled);
+        assert!(event_2.is_fully_filled);
+
+        // Ensure that there's a single fill event in each transaction for the maker order. The
+        // first taker order partially fills the maker order and the second one closes it.
+        let get_fill_event = |txn: &ParsedTransactionWithEvents| -> FillEventInstructionData {
+            let mut fills: Vec<&FillEventInstructionData> = txn
+                .events
+                .iter()
+                .filter_map(|ev| match ev {
+                    DropsetEvent::Fill(f) => Some(f),
+                    _ => None,
+                })
+                .collect_vec();
+            assert_eq!(fills.len(), 1);
+            fills.pop().unwrap().clone()
+        };
+
+        let fill_event_1 = get_fill_event(&fill_1);
+        let fill_event_2 = get_fill_event(&fill_2);
+
+        assert_eq!(
+            fill_event_1.order_sector_index,
+            fill_event_2.order_sector_index
+        );
+        assert_eq!(
+            fill_event_1.encoded_price,
+            order_info.encoded_price.as_u32()
+        );
+        assert_eq!(fill_event_1.base_filled, taker_base_size);
+        assert_eq!(fill_event_2.quote_filled, taker_quote_size);
+        assert!(!fill_event_1.is_order_closed);
+        assert!(fill_event_2.is_order_closed);
     }
 
     Ok(())
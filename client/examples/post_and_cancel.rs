@@ -16,7 +16,13 @@ use dropset_interface::{
         CancelOrderInstructionData,
         PostOrderInstructionData,
     },
-    state::sector::NIL,
+    state::{
+        order::{
+            NO_CLIENT_ORDER_ID,
+            NO_EXPIRY,
+        },
+        sector::NIL,
+    },
 };
 use price::{
     to_order_info,
@@ -60,7 +66,13 @@ async fn main() -> anyhow::Result<()> {
         .market
         .post_order(
             trader.pubkey(),
-            PostOrderInstructionData::new(order_info_args, is_bid, user_seat.index),
+            PostOrderInstructionData::new(
+                order_info_args,
+                is_bid,
+                user_seat.index,
+                NO_EXPIRY,
+                NO_CLIENT_ORDER_ID,
+            ),
         )
         .send_single_signer(&e2e.rpc, trader)
         .await?;
@@ -78,14 +90,22 @@ async fn main() -> anyhow::Result<()> {
     let user_seat = e2e.fetch_seat(&trader.pubkey()).await?.unwrap();
     println!("User seat after posting ask: {user_seat:#?}");
 
+    let ask_sector_index = e2e.view_market().await?.users[&trader.pubkey()]
+        .asks
+        .iter()
+        .find(|order| order.encoded_price == order_info.encoded_price.as_u32())
+        .expect("User should have an ask at the ask's price")
+        .index;
+
     let cancel_ask_res = e2e
         .market
         .cancel_order(
             user_seat.user,
             CancelOrderInstructionData::new(
-                order_info.encoded_price.as_u32(),
+                ask_sector_index,
                 is_bid,
                 user_seat.index,
+                NO_CLIENT_ORDER_ID,
             ),
         )
         .send_single_signer(&e2e.rpc, trader)
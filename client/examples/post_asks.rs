@@ -13,7 +13,13 @@ use client::{
 };
 use dropset_interface::{
     instructions::PostOrderInstructionData,
-    state::sector::NIL,
+    state::{
+        order::{
+            NO_CLIENT_ORDER_ID,
+            NO_EXPIRY,
+        },
+        sector::NIL,
+    },
 };
 use itertools::Itertools;
 use price::OrderInfoArgs;
@@ -53,7 +59,13 @@ async fn main() -> anyhow::Result<()> {
         .market
         .post_order(
             trader.pubkey(),
-            PostOrderInstructionData::new(order_info_args.clone(), is_bid, user_seat.index),
+            PostOrderInstructionData::new(
+                order_info_args.clone(),
+                is_bid,
+                user_seat.index,
+                NO_EXPIRY,
+                NO_CLIENT_ORDER_ID,
+            ),
         )
         .send_single_signer(&e2e.rpc, trader)
         .await?;
@@ -88,6 +100,8 @@ async fn main() -> anyhow::Result<()> {
                         ),
                         is_bid,
                         user_seat.index,
+                        NO_EXPIRY,
+                        NO_CLIENT_ORDER_ID,
                     ),
                 )
                 .into()
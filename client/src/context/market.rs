@@ -3,21 +3,44 @@
 use dropset_interface::{
     instructions::{
         generated_client::*,
+        AddAllowlistedTraderInstructionData,
+        AmendOrderInstructionData,
+        BatchReplaceInstructionData,
+        CancelAllOrdersInstructionData,
         CancelOrderInstructionData,
+        CloseMarketInstructionData,
         CloseSeatInstructionData,
+        CompactMarketInstructionData,
         DepositInstructionData,
+        ExpandMarketInstructionData,
+        LimitOrderInstructionData,
         MarketOrderInstructionData,
         PostOrderInstructionData,
+        PruneExpiredOrdersInstructionData,
         RegisterMarketInstructionData,
+        RemoveAllowlistedTraderInstructionData,
+        RenounceMarketAuthorityInstructionData,
+        RevokeSeatDelegateInstructionData,
+        SeatMarketOrderInstructionData,
+        SetMarketOracleInstructionData,
+        SetSeatDelegateInstructionData,
+        SwapInstructionData,
+        SweepFeesInstructionData,
+        TransferMarketAuthorityInstructionData,
+        UpdateMarketParamsInstructionData,
+        UpdateMarketStatusInstructionData,
+        UpdateOraclePriceInstructionData,
         WithdrawInstructionData,
     },
     seeds::event_authority,
     state::{
+        market_status::MarketStatus,
         sector::NIL,
         SYSTEM_PROGRAM_ID,
     },
 };
 use solana_address::Address;
+use solana_instruction::Instruction;
 use transaction_parser::views::MarketSeatView;
 
 use crate::{
@@ -88,7 +111,11 @@ impl MarketContext {
         self.deposit_base(user, 1, NIL)
     }
 
-    pub fn register_market(&self, payer: Address, num_sectors: u16) -> SingleSignerInstruction {
+    pub fn register_market(
+        &self,
+        payer: Address,
+        data: RegisterMarketInstructionData,
+    ) -> SingleSignerInstruction {
         RegisterMarket {
             event_authority: event_authority::ID,
             user: payer,
@@ -103,7 +130,7 @@ impl MarketContext {
             system_program: SYSTEM_PROGRAM_ID,
             dropset_program: dropset::ID,
         }
-        .create_instruction(RegisterMarketInstructionData::new(num_sectors))
+        .create_instruction(data)
         .try_into()
         .expect("Should be a single signer instruction")
     }
@@ -204,6 +231,80 @@ impl MarketContext {
         .expect("Should be a single signer instruction")
     }
 
+    pub fn amend_order(
+        &self,
+        user: Address,
+        data: AmendOrderInstructionData,
+    ) -> SingleSignerInstruction {
+        AmendOrder {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn cancel_all_orders(
+        &self,
+        user: Address,
+        data: CancelAllOrdersInstructionData,
+    ) -> SingleSignerInstruction {
+        CancelAllOrders {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// The prune expired orders crank is permissionless, so the instruction has no signers.
+    pub fn prune_expired_orders(&self, data: PruneExpiredOrdersInstructionData) -> Instruction {
+        PruneExpiredOrders {
+            event_authority: event_authority::ID,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+    }
+
+    pub fn batch_replace(
+        &self,
+        user: Address,
+        data: BatchReplaceInstructionData,
+    ) -> SingleSignerInstruction {
+        BatchReplace {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn limit_order(
+        &self,
+        user: Address,
+        data: LimitOrderInstructionData,
+    ) -> SingleSignerInstruction {
+        LimitOrder {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
     pub fn market_order(
         &self,
         user: Address,
@@ -228,6 +329,287 @@ impl MarketContext {
         .expect("Should be a single signer instruction")
     }
 
+    /// A market order settled with the user's market seat balances instead of token transfers.
+    pub fn seat_market_order(
+        &self,
+        user: Address,
+        data: SeatMarketOrderInstructionData,
+    ) -> SingleSignerInstruction {
+        SeatMarketOrder {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Lets `delegate` post and cancel orders for the `user`'s seat, and place seat-funded taker
+    /// orders if `can_take` is set. Only the seat owner can set its delegate.
+    pub fn set_seat_delegate(
+        &self,
+        user: Address,
+        delegate: Address,
+        can_take: bool,
+        user_sector_index_hint: u32,
+    ) -> SingleSignerInstruction {
+        SetSeatDelegate {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(SetSeatDelegateInstructionData::new(
+            delegate,
+            can_take,
+            user_sector_index_hint,
+        ))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn revoke_seat_delegate(
+        &self,
+        user: Address,
+        user_sector_index_hint: u32,
+    ) -> SingleSignerInstruction {
+        RevokeSeatDelegate {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(RevokeSeatDelegateInstructionData::new(
+            user_sector_index_hint,
+        ))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// A swap against the order book without a seat. Quote it beforehand with
+    /// [`dropset_interface::matching::simulate_swap`].
+    pub fn swap(&self, user: Address, data: SwapInstructionData) -> SingleSignerInstruction {
+        Swap {
+            event_authority: event_authority::ID,
+            user,
+            market_account: self.market,
+            base_user_ata: self.get_base_ata(&user),
+            quote_user_ata: self.get_quote_ata(&user),
+            base_market_ata: self.base_market_ata,
+            quote_market_ata: self.quote_market_ata,
+            base_mint: self.base.mint_address,
+            quote_mint: self.quote.mint_address,
+            base_token_program: self.base.token_program,
+            quote_token_program: self.quote.token_program,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Sweeps the market's accrued protocol fees to the fee authority's quote token account.
+    pub fn sweep_fees(&self, fee_authority: Address) -> SingleSignerInstruction {
+        SweepFees {
+            event_authority: event_authority::ID,
+            fee_authority,
+            market_account: self.market,
+            fee_recipient_ata: self.get_quote_ata(&fee_authority),
+            quote_market_ata: self.quote_market_ata,
+            quote_mint: self.quote.mint_address,
+            quote_token_program: self.quote.token_program,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(SweepFeesInstructionData::new())
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn update_market_params(
+        &self,
+        authority: Address,
+        data: UpdateMarketParamsInstructionData,
+    ) -> SingleSignerInstruction {
+        UpdateMarketParams {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(data)
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn transfer_market_authority(
+        &self,
+        authority: Address,
+        new_authority: Address,
+    ) -> SingleSignerInstruction {
+        TransferMarketAuthority {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(TransferMarketAuthorityInstructionData::new(new_authority))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn renounce_market_authority(&self, authority: Address) -> SingleSignerInstruction {
+        RenounceMarketAuthority {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(RenounceMarketAuthorityInstructionData::new())
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Compacts the market account's sectors, visiting at most `max_sectors` live sectors, and
+    /// refunds the excess rent to the market authority once the compaction is complete.
+    pub fn compact_market(&self, authority: Address, max_sectors: u16) -> SingleSignerInstruction {
+        CompactMarket {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(CompactMarketInstructionData::new(max_sectors))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Allowlists a trader on a permissioned market, registering an empty seat for them if needed.
+    pub fn add_allowlisted_trader(
+        &self,
+        authority: Address,
+        trader: Address,
+    ) -> SingleSignerInstruction {
+        AddAllowlistedTrader {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(AddAllowlistedTraderInstructionData::new(trader))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn remove_allowlisted_trader(
+        &self,
+        authority: Address,
+        trader: Address,
+        sector_index_hint: u32,
+    ) -> SingleSignerInstruction {
+        RemoveAllowlistedTrader {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(RemoveAllowlistedTraderInstructionData::new(
+            trader,
+            sector_index_hint,
+        ))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Sets the oracle the market's price bands are centered on. Pass
+    /// [`dropset_interface::state::oracle::NO_ORACLE`] to remove the oracle.
+    pub fn set_market_oracle(
+        &self,
+        authority: Address,
+        oracle: Address,
+        band_bps: u16,
+        max_staleness_secs: u32,
+    ) -> SingleSignerInstruction {
+        SetMarketOracle {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(SetMarketOracleInstructionData::new(
+            oracle,
+            band_bps,
+            max_staleness_secs,
+        ))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// The update oracle price crank is permissionless, so the instruction has no signers.
+    pub fn update_oracle_price(&self, oracle: Address) -> Instruction {
+        UpdateOraclePrice {
+            event_authority: event_authority::ID,
+            market_account: self.market,
+            oracle,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(UpdateOraclePriceInstructionData::new())
+    }
+
+    /// Expanding a market is permissionless, so any payer can pre-allocate sectors for it.
+    pub fn expand_market(&self, payer: Address, num_sectors: u16) -> SingleSignerInstruction {
+        ExpandMarket {
+            event_authority: event_authority::ID,
+            payer,
+            market_account: self.market,
+            system_program: SYSTEM_PROGRAM_ID,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(ExpandMarketInstructionData::new(num_sectors))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    /// Closes an empty market, closing its token accounts and refunding all rent to the closer. Any
+    /// tokens left in the market's token accounts are sent to the closer's associated token
+    /// accounts.
+    pub fn close_market(&self, closer: Address) -> SingleSignerInstruction {
+        CloseMarket {
+            event_authority: event_authority::ID,
+            closer,
+            market_account: self.market,
+            base_market_ata: self.base_market_ata,
+            quote_market_ata: self.quote_market_ata,
+            closer_base_ata: self.get_base_ata(&closer),
+            closer_quote_ata: self.get_quote_ata(&closer),
+            base_mint: self.base.mint_address,
+            quote_mint: self.quote.mint_address,
+            base_token_program: self.base.token_program,
+            quote_token_program: self.quote.token_program,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(CloseMarketInstructionData::new())
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
+    pub fn update_market_status(
+        &self,
+        authority: Address,
+        status: MarketStatus,
+    ) -> SingleSignerInstruction {
+        UpdateMarketStatus {
+            event_authority: event_authority::ID,
+            authority,
+            market_account: self.market,
+            dropset_program: dropset::ID,
+        }
+        .create_instruction(UpdateMarketStatusInstructionData::new(status))
+        .try_into()
+        .expect("Should be a single signer instruction")
+    }
+
     fn deposit(
         &self,
         user: Address,
@@ -1,3 +1,7 @@
+use dropset_interface::instructions::{
+    MarketParams,
+    RegisterMarketInstructionData,
+};
 use solana_address::Address;
 use solana_sdk::{
     program_pack::Pack,
@@ -88,7 +92,18 @@ impl E2e {
         let market = MarketContext::new(base, quote);
 
         let register_market_txn = market
-            .register_market(default_payer.pubkey(), 10)
+            .register_market(
+                default_payer.pubkey(),
+                RegisterMarketInstructionData::new(
+                    10,
+                    default_payer.pubkey(),
+                    false,
+                    MarketParams {
+                        fee_authority: default_payer.pubkey(),
+                        ..Default::default()
+                    },
+                ),
+            )
             .send_single_signer(&rpc, &default_payer)
             .await?;
 
@@ -3,20 +3,41 @@ use std::{
     path::PathBuf,
 };
 
-use dropset_interface::state::SYSTEM_PROGRAM_ID;
+use dropset_interface::{
+    instructions::{
+        MarketParams,
+        RegisterMarketInstructionData,
+    },
+    state::{
+        oracle::OraclePrice,
+        sector::NIL,
+        transmutable::Transmutable,
+        SYSTEM_PROGRAM_ID,
+    },
+};
 use mollusk_svm::{
     Mollusk,
     MolluskContext,
 };
 use solana_account::Account;
 use solana_address::Address;
+use solana_instruction::Instruction;
 use solana_sdk::{
     program_pack::Pack,
     pubkey,
     rent::Rent,
 };
-use spl_token_interface::state::Mint;
-use transaction_parser::program_ids::SPL_TOKEN_ID;
+use spl_token_interface::state::{
+    Account as TokenAccount,
+    Mint,
+};
+use transaction_parser::{
+    program_ids::SPL_TOKEN_ID,
+    views::{
+        try_market_view_all_from_owner_and_data,
+        MarketViewAll,
+    },
+};
 
 use crate::{
     context::{
@@ -51,9 +72,7 @@ fn deploy_file_to_program_name(program_name: &str) -> String {
 /// - The SPL token 2022 program
 /// - The associated token program
 /// - The accounts passed
-pub fn new_dropset_mollusk_context(
-    accounts: Vec<(Address, Account)>,
-) -> MolluskContext<HashMap<Address, Account>> {
+pub fn new_dropset_mollusk_context(accounts: Vec<(Address, Account)>) -> DropsetMolluskContext {
     let mut mollusk = Mollusk::new(&dropset::ID, &deploy_file_to_program_name("dropset.so"));
     mollusk_svm_programs_token::token::add_program(&mut mollusk);
     mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
@@ -72,6 +91,9 @@ pub fn new_dropset_mollusk_context(
     context
 }
 
+/// A mollusk context backed by the simple hashmap account store.
+pub type DropsetMolluskContext = MolluskContext<HashMap<Address, Account>>;
+
 pub const MOLLUSK_DEFAULT_MINT_AUTHORITY: Address =
     pubkey!("mint1authority11111111111111111111111111111");
 pub const MOLLUSK_DEFAULT_NUM_SECTORS: u16 = 10;
@@ -103,7 +125,23 @@ pub const MOLLUSK_DEFAULT_MARKET: MarketContext = MarketContext {
 /// the default market.
 pub fn new_dropset_mollusk_context_with_default_market(
     accounts: Vec<(Address, Account)>,
-) -> (MolluskContext<HashMap<Address, Account>>, MarketContext) {
+) -> (DropsetMolluskContext, MarketContext) {
+    new_dropset_mollusk_context_with_market(accounts, false)
+}
+
+/// Same as [`new_dropset_mollusk_context_with_default_market`], but the default market is
+/// permissioned, so only traders allowlisted by [`MOLLUSK_DEFAULT_MINT_AUTHORITY`] can register
+/// seats and take liquidity.
+pub fn new_dropset_mollusk_context_with_default_permissioned_market(
+    accounts: Vec<(Address, Account)>,
+) -> (DropsetMolluskContext, MarketContext) {
+    new_dropset_mollusk_context_with_market(accounts, true)
+}
+
+fn new_dropset_mollusk_context_with_market(
+    accounts: Vec<(Address, Account)>,
+    is_permissioned: bool,
+) -> (DropsetMolluskContext, MarketContext) {
     let mint_authority_addr_and_account = (
         MOLLUSK_DEFAULT_MINT_AUTHORITY,
         Account {
@@ -137,7 +175,18 @@ pub fn new_dropset_mollusk_context_with_default_market(
     .expect("Should create quote mint instructions");
 
     let register_market: solana_instruction::Instruction = MOLLUSK_DEFAULT_MARKET
-        .register_market(MOLLUSK_DEFAULT_MINT_AUTHORITY, MOLLUSK_DEFAULT_NUM_SECTORS)
+        .register_market(
+            MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            RegisterMarketInstructionData::new(
+                MOLLUSK_DEFAULT_NUM_SECTORS,
+                MOLLUSK_DEFAULT_MINT_AUTHORITY,
+                is_permissioned,
+                MarketParams {
+                    fee_authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+                    ..Default::default()
+                },
+            ),
+        )
         .into();
 
     res.process_instruction_chain(&[
@@ -151,19 +200,97 @@ pub fn new_dropset_mollusk_context_with_default_market(
     (res, MOLLUSK_DEFAULT_MARKET)
 }
 
+/// Creates a system account with enough lamports to pay for any rent in a mollusk test.
+pub fn funded_system_account() -> Account {
+    Account::new(100_000_000_000, 0, &SYSTEM_PROGRAM_ID)
+}
+
+/// Returns the instructions that create a user's base and quote ATAs, mint `base_amount` and
+/// `quote_amount` to them with [`MOLLUSK_DEFAULT_MINT_AUTHORITY`], and deposit both amounts into
+/// the user's seat on `market`, registering the seat if they don't have one yet.
+///
+/// Zero amounts are minted but not deposited, since deposits can't be zero.
+pub fn fund_and_deposit_instructions(
+    market: &MarketContext,
+    user: Address,
+    base_amount: u64,
+    quote_amount: u64,
+) -> anyhow::Result<Vec<Instruction>> {
+    let mut instructions = vec![
+        market.base.create_ata(&user, &user),
+        market.quote.create_ata(&user, &user),
+        market.base.mint_to(
+            &MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            &market.get_base_ata(&user),
+            base_amount,
+        )?,
+        market.quote.mint_to(
+            &MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            &market.get_quote_ata(&user),
+            quote_amount,
+        )?,
+    ];
+    if base_amount != 0 {
+        instructions.push(market.deposit_base(user, base_amount, NIL).into());
+    }
+    if quote_amount != 0 {
+        instructions.push(market.deposit_quote(user, quote_amount, NIL).into());
+    }
+    Ok(instructions)
+}
+
+/// Loads and parses the current state of `market`'s market account in a mollusk context.
+pub fn market_view(
+    context: &DropsetMolluskContext,
+    market: &MarketContext,
+) -> anyhow::Result<MarketViewAll> {
+    let account = context
+        .account_store
+        .borrow()
+        .get(&market.market)
+        .cloned()
+        .ok_or(anyhow::anyhow!("Market account doesn't exist"))?;
+    try_market_view_all_from_owner_and_data(account.owner, &account.data)
+}
+
+/// Returns the token balance of a token account in a mollusk context, or zero if it doesn't exist.
+pub fn token_balance(context: &DropsetMolluskContext, token_account: &Address) -> u64 {
+    context
+        .account_store
+        .borrow()
+        .get(token_account)
+        .and_then(|account| TokenAccount::unpack(&account.data).ok())
+        .map_or(0, |token_account| token_account.amount)
+}
+
+/// Creates a synthetic oracle account storing an [`OraclePrice`], to be passed to
+/// [`new_dropset_mollusk_context`] and used as a market's oracle.
+///
+/// `dropset` doesn't check the oracle account's owner, so it's simply owned by the system program.
+pub fn oracle_price_account(encoded_price: u32, publish_time: u64) -> Account {
+    Account {
+        data: OraclePrice::new(encoded_price, publish_time)
+            .as_bytes()
+            .to_vec(),
+        lamports: Rent::default().minimum_balance(OraclePrice::LEN),
+        owner: SYSTEM_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::anyhow;
     use dropset_interface::state::{
+        compaction_phase::CompactionPhase,
         market_header::MARKET_ACCOUNT_DISCRIMINANT,
-        sector::NIL,
+        market_status::MarketStatus,
+        oracle::NO_ORACLE,
+        user_order_sectors::MAX_ORDERS,
     };
     use spl_associated_token_account_interface::address::get_associated_token_address;
-    use transaction_parser::views::{
-        try_market_view_all_from_owner_and_data,
-        MarketHeaderView,
-        MarketViewAll,
-    };
+    use transaction_parser::views::MarketHeaderView;
 
     use super::*;
     use crate::pda::find_market_address;
@@ -247,7 +374,24 @@ mod tests {
                 quote_mint: MOLLUSK_DEFAULT_QUOTE_TOKEN.mint_address,
                 market_bump: bump,
                 nonce: 1, // The register market event.
-                _padding: [0, 0, 0],
+                authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+                fee_authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+                taker_fee_bps: 0,
+                maker_rebate_bps: 0,
+                protocol_fees: 0,
+                status: MarketStatus::Active,
+                tick_size: 1,
+                base_lot_size: 1,
+                min_base_order_size: 0,
+                min_quote_order_size: 0,
+                max_orders: MAX_ORDERS,
+                is_permissioned: false,
+                oracle: NO_ORACLE,
+                oracle_band_bps: 0,
+                oracle_max_staleness: 0,
+                oracle_price: 0,
+                oracle_publish_time: 0,
+                compaction_phase: CompactionPhase::Idle,
             }
         );
 
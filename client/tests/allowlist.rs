@@ -0,0 +1,285 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        new_dropset_mollusk_context_with_default_permissioned_market,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        LimitOrderInstructionData,
+        MarketOrderInstructionData,
+        PostOrderInstructionData,
+        SeatMarketOrderInstructionData,
+        SelfTradePrevention,
+        SwapInstructionData,
+        TimeInForce,
+    },
+    state::sector::NIL,
+};
+use mollusk_svm::result::ProgramResult;
+use price::{
+    OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+};
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const AUTHORITY: Address = MOLLUSK_DEFAULT_MINT_AUTHORITY;
+const PRICE: u32 = 10_000_000;
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+fn data_len(mollusk: &DropsetMolluskContext, address: &Address) -> usize {
+    mollusk
+        .account_store
+        .borrow()
+        .get(address)
+        .map_or(0, |account| account.data.len())
+}
+
+fn process_ok(mollusk: &DropsetMolluskContext, instructions: &[Instruction]) {
+    assert!(mollusk
+        .process_instruction_chain(instructions)
+        .program_result
+        .is_ok());
+}
+
+/// Sets up the default permissioned market with an allowlisted maker resting an ask, and funds the
+/// token accounts of each of the passed traders without depositing anything.
+fn setup(traders: &[Address]) -> anyhow::Result<(DropsetMolluskContext, MarketContext)> {
+    let maker = Address::new_unique();
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_permissioned_market(
+        [maker]
+            .iter()
+            .chain(traders)
+            .map(|user| (*user, funded_system_account()))
+            .collect(),
+    );
+
+    let ask = market.post_order(
+        maker,
+        PostOrderInstructionData::new(OrderInfoArgs::new_unscaled(PRICE, 10), false, NIL, 0, 0),
+    );
+    let instructions = [
+        vec![market.add_allowlisted_trader(AUTHORITY, maker).into()],
+        fund_and_deposit_instructions(&market, maker, 10, 0)?,
+        vec![ask.into()],
+    ]
+    .concat();
+    process_ok(&mollusk, &instructions);
+
+    for trader in traders {
+        process_ok(
+            &mollusk,
+            &fund_and_deposit_instructions(&market, *trader, 0, 0)?,
+        );
+    }
+
+    Ok((mollusk, market))
+}
+
+#[test]
+fn deposit_requires_allowlisted_seat() -> anyhow::Result<()> {
+    let trader = Address::new_unique();
+    let (mollusk, market) = setup(&[trader])?;
+
+    // An unlisted trader can't register a seat by depositing.
+    let mint = market
+        .base
+        .mint_to(&AUTHORITY, &market.get_base_ata(&trader), 10)?;
+    process_ok(&mollusk, &[mint]);
+    let deposit: Instruction = market.deposit_base(trader, 10, NIL).into();
+    let market_len_before = data_len(&mollusk, &market.market);
+    assert_eq!(
+        mollusk.process_instruction(&deposit).program_result,
+        custom_error(DropsetError::TraderNotAllowlisted)
+    );
+
+    // Allowlisting the trader registers an empty, allowlisted seat for them to deposit into.
+    let num_free_before = market_view(&mollusk, &market)?.header.num_free_sectors;
+    let add: Instruction = market.add_allowlisted_trader(AUTHORITY, trader).into();
+    process_ok(&mollusk, &[add.clone()]);
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(view.header.num_free_sectors, num_free_before - 1);
+    assert!(view.users[&trader].seat.is_allowlisted);
+
+    // Allowlisting the trader again reuses their seat instead of registering a new one.
+    process_ok(&mollusk, &[add]);
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(view.header.num_free_sectors, num_free_before - 1);
+    assert_eq!(data_len(&mollusk, &market.market), market_len_before);
+
+    process_ok(&mollusk, &[deposit]);
+    assert_eq!(
+        market_view(&mollusk, &market)?.users[&trader]
+            .seat
+            .base_available,
+        10
+    );
+
+    Ok(())
+}
+
+#[test]
+fn takers_must_be_allowlisted() -> anyhow::Result<()> {
+    const QUOTE: u64 = 100 * PRICE as u64;
+    let [listed, removed, outsider]: [Address; 3] = core::array::from_fn(|_| Address::new_unique());
+    let (mollusk, market) = setup(&[listed, removed, outsider])?;
+
+    // Both seated traders are allowlisted and deposit quote, then one is removed from the allowlist
+    // but keeps their seat. The outsider never gets a seat and only holds tokens.
+    let mut instructions = vec![];
+    for trader in [listed, removed] {
+        instructions.push(market.add_allowlisted_trader(AUTHORITY, trader).into());
+        instructions.push(market.quote.mint_to(
+            &AUTHORITY,
+            &market.get_quote_ata(&trader),
+            2 * QUOTE,
+        )?);
+        instructions.push(market.deposit_quote(trader, QUOTE, NIL).into());
+    }
+    instructions.push(
+        market
+            .remove_allowlisted_trader(AUTHORITY, removed, NIL)
+            .into(),
+    );
+    instructions.push(
+        market
+            .quote
+            .mint_to(&AUTHORITY, &market.get_quote_ata(&outsider), QUOTE)?,
+    );
+    process_ok(&mollusk, &instructions);
+    assert!(
+        !market_view(&mollusk, &market)?.users[&removed]
+            .seat
+            .is_allowlisted
+    );
+
+    let limit_order = |trader: Address| -> Instruction {
+        market
+            .limit_order(
+                trader,
+                LimitOrderInstructionData::new(
+                    OrderInfoArgs::new_unscaled(PRICE, 1),
+                    true,
+                    NIL,
+                    SelfTradePrevention::CancelTaking,
+                    0,
+                    0,
+                ),
+            )
+            .into()
+    };
+    let seat_market_order = |trader: Address| -> Instruction {
+        market
+            .seat_market_order(
+                trader,
+                SeatMarketOrderInstructionData::new(
+                    1,
+                    true,
+                    true,
+                    ENCODED_PRICE_INFINITY,
+                    0,
+                    TimeInForce::ImmediateOrCancel,
+                    SelfTradePrevention::CancelTaking,
+                    NIL,
+                ),
+            )
+            .into()
+    };
+    let market_order = |trader: Address| -> Instruction {
+        market
+            .market_order(
+                trader,
+                MarketOrderInstructionData::new(
+                    1,
+                    true,
+                    true,
+                    ENCODED_PRICE_INFINITY,
+                    0,
+                    TimeInForce::ImmediateOrCancel,
+                    SelfTradePrevention::CancelTaking,
+                ),
+            )
+            .into()
+    };
+    let swap = |trader: Address| -> Instruction {
+        market
+            .swap(trader, SwapInstructionData::new(1, true, true, 1, u64::MAX))
+            .into()
+    };
+
+    for instruction in [
+        limit_order(removed),
+        seat_market_order(removed),
+        market_order(removed),
+        swap(removed),
+        market_order(outsider),
+        swap(outsider),
+    ] {
+        assert_eq!(
+            mollusk.process_instruction(&instruction).program_result,
+            custom_error(DropsetError::TraderNotAllowlisted)
+        );
+    }
+
+    // Every kind of taker order fills for the allowlisted trader.
+    process_ok(
+        &mollusk,
+        &[
+            limit_order(listed),
+            seat_market_order(listed),
+            market_order(listed),
+            swap(listed),
+        ],
+    );
+    assert_eq!(market_view(&mollusk, &market)?.asks[0].base_remaining, 6);
+
+    Ok(())
+}
+
+#[test]
+fn allowlist_is_authority_only() {
+    let [trader, stranger]: [Address; 2] = core::array::from_fn(|_| Address::new_unique());
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_permissioned_market(vec![
+        (trader, funded_system_account()),
+        (stranger, funded_system_account()),
+    ]);
+
+    let add = market.add_allowlisted_trader(stranger, trader);
+    assert_eq!(
+        mollusk.process_instruction(&add.into()).program_result,
+        custom_error(DropsetError::IncorrectMarketAuthority)
+    );
+
+    let add = market.add_allowlisted_trader(AUTHORITY, trader);
+    process_ok(&mollusk, &[add.into()]);
+    let remove = market.remove_allowlisted_trader(stranger, trader, NIL);
+    assert_eq!(
+        mollusk.process_instruction(&remove.into()).program_result,
+        custom_error(DropsetError::IncorrectMarketAuthority)
+    );
+}
+
+#[test]
+fn allowlist_requires_permissioned_market() {
+    let trader = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(trader, funded_system_account())]);
+
+    let add = market.add_allowlisted_trader(AUTHORITY, trader);
+    assert_eq!(
+        mollusk.process_instruction(&add.into()).program_result,
+        custom_error(DropsetError::MarketNotPermissioned)
+    );
+}
@@ -0,0 +1,193 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        AmendOrderInstructionData,
+        PostOrderInstructionData,
+    },
+    state::sector::{
+        SectorIndex,
+        NIL,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use price::OrderInfoArgs;
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+use transaction_parser::views::OrderView;
+
+const BASE_DEPOSIT: u64 = 100;
+const QUOTE_DEPOSIT: u64 = 1_000_000_000;
+const PRICE: u32 = 10_000_000;
+const ORDER_SIZE: u64 = 10;
+const FIRST_CLIENT_ORDER_ID: u64 = 1;
+const SECOND_CLIENT_ORDER_ID: u64 = 2;
+
+/// Sets up the default market with a user resting two bids of [`ORDER_SIZE`] base at [`PRICE`].
+/// The first bid, with [`FIRST_CLIENT_ORDER_ID`], is ahead of the second in the queue.
+fn setup(expiry: u64) -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address)> {
+    let user = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(user, funded_system_account())]);
+
+    let post = |client_order_id: u64| -> Instruction {
+        market
+            .post_order(
+                user,
+                PostOrderInstructionData::new(
+                    OrderInfoArgs::new_unscaled(PRICE, ORDER_SIZE),
+                    true,
+                    NIL,
+                    expiry,
+                    client_order_id,
+                ),
+            )
+            .into()
+    };
+
+    let instructions = [
+        fund_and_deposit_instructions(&market, user, BASE_DEPOSIT, QUOTE_DEPOSIT)?,
+        vec![post(FIRST_CLIENT_ORDER_ID), post(SECOND_CLIENT_ORDER_ID)],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    Ok((mollusk, market, user))
+}
+
+/// Returns the market's bids from the front of the book to the back.
+fn bids(mollusk: &DropsetMolluskContext, market: &MarketContext) -> anyhow::Result<Vec<OrderView>> {
+    Ok(market_view(mollusk, market)?.bids)
+}
+
+fn amend_bid(
+    market: &MarketContext,
+    user: Address,
+    order_sector_index: SectorIndex,
+    price_mantissa: u32,
+    size: u64,
+) -> Instruction {
+    market
+        .amend_order(
+            user,
+            AmendOrderInstructionData::new(
+                order_sector_index,
+                true,
+                OrderInfoArgs::new_unscaled(price_mantissa, size),
+                NIL,
+            ),
+        )
+        .into()
+}
+
+/// Amends the first bid to `new_size` and checks the user's collateral and the bid's queue
+/// position afterwards.
+fn amend_and_check(new_size: u64, expect_requeued: bool) -> anyhow::Result<()> {
+    let (mollusk, market, user) = setup(0)?;
+
+    let bids_before = bids(&mollusk, &market)?;
+    let first = bids_before[0].clone();
+    assert_eq!(first.client_order_id, FIRST_CLIENT_ORDER_ID);
+    let quote_available_before = market_view(&mollusk, &market)?.users[&user]
+        .seat
+        .quote_available;
+
+    let amend = amend_bid(&market, user, first.index, PRICE, new_size);
+    assert!(mollusk.process_instruction(&amend).program_result.is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let amended = view
+        .bids
+        .iter()
+        .find(|order| order.client_order_id == FIRST_CLIENT_ORDER_ID)
+        .expect("The amended order should still be on the book");
+    assert_eq!(amended.base_remaining, new_size);
+
+    // The user's collateral changes by exactly the change in the bid's quote remaining.
+    let quote_available_after = view.users[&user].seat.quote_available;
+    assert_eq!(
+        quote_available_after as i128 - quote_available_before as i128,
+        first.quote_remaining as i128 - amended.quote_remaining as i128,
+    );
+
+    // A shrunk order keeps its sector and its place at the front of the queue, while a grown order
+    // moves behind the other order at its price.
+    let client_order_ids: Vec<u64> = view.bids.iter().map(|o| o.client_order_id).collect();
+    if expect_requeued {
+        assert!(quote_available_after < quote_available_before);
+        assert_eq!(
+            client_order_ids,
+            [SECOND_CLIENT_ORDER_ID, FIRST_CLIENT_ORDER_ID]
+        );
+    } else {
+        assert!(quote_available_after > quote_available_before);
+        assert_eq!(amended.index, first.index);
+        assert_eq!(
+            client_order_ids,
+            [FIRST_CLIENT_ORDER_ID, SECOND_CLIENT_ORDER_ID]
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn amend_decrease_keeps_queue_position() -> anyhow::Result<()> {
+    amend_and_check(ORDER_SIZE / 2, false)
+}
+
+#[test]
+fn amend_increase_requeues() -> anyhow::Result<()> {
+    amend_and_check(ORDER_SIZE * 2, true)
+}
+
+#[test]
+fn amend_price_mismatch() -> anyhow::Result<()> {
+    let (mollusk, market, user) = setup(0)?;
+    let first = bids(&mollusk, &market)?[0].clone();
+
+    let amend = amend_bid(&market, user, first.index, PRICE - 1_000_000, ORDER_SIZE);
+    assert_eq!(
+        mollusk.process_instruction(&amend).program_result,
+        ProgramResult::Failure(ProgramError::Custom(
+            DropsetError::AmendPriceMismatch as u32
+        ))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn amend_expired_order() -> anyhow::Result<()> {
+    const EXPIRY: u64 = 100;
+    let (mut mollusk, market, user) = setup(EXPIRY)?;
+    let first = bids(&mollusk, &market)?[0].clone();
+
+    // Amending is allowed before the order expires.
+    let amend = amend_bid(&market, user, first.index, PRICE, ORDER_SIZE / 2);
+    assert!(mollusk.process_instruction(&amend).program_result.is_ok());
+
+    mollusk.mollusk.sysvars.clock.unix_timestamp = EXPIRY as i64;
+    let amend = amend_bid(&market, user, first.index, PRICE, ORDER_SIZE / 4);
+    assert_eq!(
+        mollusk.process_instruction(&amend).program_result,
+        ProgramResult::Failure(ProgramError::Custom(
+            DropsetError::OrderAlreadyExpired as u32
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+    },
+};
+use dropset_interface::{
+    instructions::{
+        CancelAllOrdersInstructionData,
+        PostOrderInstructionData,
+    },
+    state::sector::NIL,
+};
+use price::OrderInfoArgs;
+use solana_address::Address;
+use solana_instruction::Instruction;
+
+const BASE_DEPOSIT: u64 = 100;
+const QUOTE_DEPOSIT: u64 = 1_000_000_000;
+const BID_PRICES: [u32; 2] = [9_000_000, 8_000_000];
+const ASK_PRICES: [u32; 2] = [11_000_000, 12_000_000];
+const ORDER_SIZE: u64 = 10;
+
+/// Sets up the default market with a user resting two bids and two asks of [`ORDER_SIZE`] base.
+fn setup() -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address)> {
+    let user = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(user, funded_system_account())]);
+
+    let post = |price_mantissa: u32, is_bid: bool| -> Instruction {
+        market
+            .post_order(
+                user,
+                PostOrderInstructionData::new(
+                    OrderInfoArgs::new_unscaled(price_mantissa, ORDER_SIZE),
+                    is_bid,
+                    NIL,
+                    0,
+                    0,
+                ),
+            )
+            .into()
+    };
+
+    let instructions = [
+        fund_and_deposit_instructions(&market, user, BASE_DEPOSIT, QUOTE_DEPOSIT)?,
+        BID_PRICES.iter().map(|price| post(*price, true)).collect(),
+        ASK_PRICES.iter().map(|price| post(*price, false)).collect(),
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    let user_data = &market_view(&mollusk, &market)?.users[&user];
+    assert_eq!(user_data.bids.len(), BID_PRICES.len());
+    assert_eq!(user_data.asks.len(), ASK_PRICES.len());
+
+    Ok((mollusk, market, user))
+}
+
+/// Cancels all of the user's orders on the given sides and checks that only those sides are
+/// emptied and that their collateral is returned to the user's seat.
+fn cancel_all_and_check(cancel_bids: bool, cancel_asks: bool) -> anyhow::Result<()> {
+    let (mollusk, market, user) = setup()?;
+
+    let cancel_all = market.cancel_all_orders(
+        user,
+        CancelAllOrdersInstructionData::new(cancel_bids, cancel_asks, NIL),
+    );
+    assert!(mollusk
+        .process_instruction(&cancel_all.into())
+        .program_result
+        .is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let user_data = &view.users[&user];
+    let bids_left = if cancel_bids { 0 } else { BID_PRICES.len() };
+    let asks_left = if cancel_asks { 0 } else { ASK_PRICES.len() };
+    assert_eq!(user_data.bids.len(), bids_left);
+    assert_eq!(user_data.asks.len(), asks_left);
+    assert_eq!(view.bids.len(), bids_left);
+    assert_eq!(view.asks.len(), asks_left);
+
+    // Bids lock quote and asks lock base, so only the cancelled sides' collateral is refunded.
+    let quote_locked = BID_PRICES
+        .iter()
+        .map(|price| *price as u64 * ORDER_SIZE)
+        .sum::<u64>();
+    let base_locked = ORDER_SIZE * ASK_PRICES.len() as u64;
+    let expected_quote = if cancel_bids {
+        QUOTE_DEPOSIT
+    } else {
+        QUOTE_DEPOSIT - quote_locked
+    };
+    let expected_base = if cancel_asks {
+        BASE_DEPOSIT
+    } else {
+        BASE_DEPOSIT - base_locked
+    };
+    assert_eq!(user_data.seat.quote_available, expected_quote);
+    assert_eq!(user_data.seat.base_available, expected_base);
+
+    Ok(())
+}
+
+#[test]
+fn cancel_all_bids() -> anyhow::Result<()> {
+    cancel_all_and_check(true, false)
+}
+
+#[test]
+fn cancel_all_asks() -> anyhow::Result<()> {
+    cancel_all_and_check(false, true)
+}
+
+#[test]
+fn cancel_all_bids_and_asks() -> anyhow::Result<()> {
+    cancel_all_and_check(true, true)
+}
@@ -0,0 +1,240 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        token_balance,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        MarketParams,
+        PostOrderInstructionData,
+        SeatMarketOrderInstructionData,
+        SelfTradePrevention,
+        TimeInForce,
+        UpdateMarketParamsInstructionData,
+    },
+    state::sector::NIL,
+};
+use mollusk_svm::result::ProgramResult;
+use price::{
+    OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+};
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const AUTHORITY: Address = MOLLUSK_DEFAULT_MINT_AUTHORITY;
+
+fn lamports(mollusk: &DropsetMolluskContext, address: &Address) -> u64 {
+    mollusk
+        .account_store
+        .borrow()
+        .get(address)
+        .map_or(0, |account| account.lamports)
+}
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+/// Creates the closer's base and quote token accounts, which receive any tokens left in the market.
+fn create_closer_atas(mollusk: &DropsetMolluskContext, market: &MarketContext, closer: Address) {
+    let create_atas = [
+        market.base.create_ata(&closer, &closer),
+        market.quote.create_ata(&closer, &closer),
+    ];
+    assert!(mollusk
+        .process_instruction_chain(&create_atas)
+        .program_result
+        .is_ok());
+}
+
+/// Closes the market with the market authority and checks that all rent went to the authority and
+/// the account is no longer a market afterwards.
+fn close_and_check(mollusk: &DropsetMolluskContext, market: &MarketContext) -> anyhow::Result<()> {
+    let rent = lamports(mollusk, &market.market)
+        + lamports(mollusk, &market.base_market_ata)
+        + lamports(mollusk, &market.quote_market_ata);
+    let authority_lamports_before = lamports(mollusk, &AUTHORITY);
+
+    let close: Instruction = market.close_market(AUTHORITY).into();
+    assert!(mollusk.process_instruction(&close).program_result.is_ok());
+
+    assert_eq!(
+        lamports(mollusk, &AUTHORITY),
+        authority_lamports_before + rent
+    );
+    assert_eq!(lamports(mollusk, &market.market), 0);
+    assert_eq!(lamports(mollusk, &market.base_market_ata), 0);
+    assert_eq!(lamports(mollusk, &market.quote_market_ata), 0);
+    assert!(market_view(mollusk, market).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn close_empty_market() -> anyhow::Result<()> {
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![]);
+    create_closer_atas(&mollusk, &market, AUTHORITY);
+
+    close_and_check(&mollusk, &market)
+}
+
+#[test]
+fn close_market_sweeps_remaining_tokens_to_closer() -> anyhow::Result<()> {
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![]);
+    create_closer_atas(&mollusk, &market, AUTHORITY);
+
+    // Tokens sent directly to the market's token accounts aren't owed to any seat, but must not
+    // stop the market from being closed.
+    let donations = [
+        market
+            .base
+            .mint_to(&AUTHORITY, &market.base_market_ata, 7)?,
+        market
+            .quote
+            .mint_to(&AUTHORITY, &market.quote_market_ata, 11)?,
+    ];
+    assert!(mollusk
+        .process_instruction_chain(&donations)
+        .program_result
+        .is_ok());
+
+    close_and_check(&mollusk, &market)?;
+    assert_eq!(token_balance(&mollusk, &market.get_base_ata(&AUTHORITY)), 7);
+    assert_eq!(
+        token_balance(&mollusk, &market.get_quote_ata(&AUTHORITY)),
+        11
+    );
+
+    Ok(())
+}
+
+#[test]
+fn close_market_with_seats() -> anyhow::Result<()> {
+    let user = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(user, funded_system_account())]);
+    create_closer_atas(&mollusk, &market, AUTHORITY);
+
+    let deposit = fund_and_deposit_instructions(&market, user, 10, 0)?;
+    assert!(mollusk
+        .process_instruction_chain(&deposit)
+        .program_result
+        .is_ok());
+
+    let close: Instruction = market.close_market(AUTHORITY).into();
+    assert_eq!(
+        mollusk.process_instruction(&close).program_result,
+        custom_error(DropsetError::MarketNotEmpty)
+    );
+
+    // Once the user closes their seat, the market can be closed.
+    let close_seat = market.close_seat(user, NIL);
+    assert!(mollusk
+        .process_instruction(&close_seat.into())
+        .program_result
+        .is_ok());
+    close_and_check(&mollusk, &market)
+}
+
+#[test]
+fn close_market_with_unswept_fees() -> anyhow::Result<()> {
+    const PRICE: u32 = 10_000_000;
+    const SIZE: u64 = 10;
+    let maker = Address::new_unique();
+    let taker = Address::new_unique();
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (maker, funded_system_account()),
+        (taker, funded_system_account()),
+    ]);
+    create_closer_atas(&mollusk, &market, AUTHORITY);
+
+    // Accrue protocol fees with a single fill, then have both users leave the market.
+    let set_fees = market.update_market_params(
+        AUTHORITY,
+        UpdateMarketParamsInstructionData::new(MarketParams {
+            fee_authority: AUTHORITY,
+            taker_fee_bps: 10,
+            maker_rebate_bps: 5,
+            ..Default::default()
+        }),
+    );
+    let ask = market.post_order(
+        maker,
+        PostOrderInstructionData::new(OrderInfoArgs::new_unscaled(PRICE, SIZE), false, NIL, 0, 0),
+    );
+    let buy = market.seat_market_order(
+        taker,
+        SeatMarketOrderInstructionData::new(
+            SIZE,
+            true,
+            true,
+            ENCODED_PRICE_INFINITY,
+            0,
+            TimeInForce::ImmediateOrCancel,
+            SelfTradePrevention::CancelTaking,
+            NIL,
+        ),
+    );
+    let instructions = [
+        vec![set_fees.into()],
+        fund_and_deposit_instructions(&market, maker, SIZE, 0)?,
+        vec![ask.into()],
+        fund_and_deposit_instructions(&market, taker, 0, 2 * SIZE * PRICE as u64)?,
+        vec![
+            buy.into(),
+            market.close_seat(maker, NIL).into(),
+            market.close_seat(taker, NIL).into(),
+        ],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+    let protocol_fees = market_view(&mollusk, &market)?.header.protocol_fees;
+    assert_ne!(protocol_fees, 0);
+
+    // The fees are owed to the fee authority, so they must be swept before the market is closed.
+    let close: Instruction = market.close_market(AUTHORITY).into();
+    assert_eq!(
+        mollusk.process_instruction(&close).program_result,
+        custom_error(DropsetError::ProtocolFeesNotSwept)
+    );
+
+    let sweep = market.sweep_fees(AUTHORITY);
+    assert!(mollusk
+        .process_instruction(&sweep.into())
+        .program_result
+        .is_ok());
+    close_and_check(&mollusk, &market)?;
+    assert_eq!(
+        token_balance(&mollusk, &market.get_quote_ata(&AUTHORITY)),
+        protocol_fees
+    );
+
+    Ok(())
+}
+
+#[test]
+fn close_market_not_authority() {
+    let closer = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(closer, funded_system_account())]);
+    create_closer_atas(&mollusk, &market, closer);
+
+    let close = market.close_market(closer);
+    assert_eq!(
+        mollusk.process_instruction(&close.into()).program_result,
+        custom_error(DropsetError::IncorrectMarketAuthority)
+    );
+}
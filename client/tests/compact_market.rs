@@ -0,0 +1,166 @@
+use client::mollusk_helpers::{
+    fund_and_deposit_instructions,
+    funded_system_account,
+    market_view,
+    new_dropset_mollusk_context_with_default_market,
+    DropsetMolluskContext,
+    MOLLUSK_DEFAULT_MINT_AUTHORITY,
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        CancelOrderInstructionData,
+        PostOrderInstructionData,
+    },
+    state::{
+        compaction_phase::CompactionPhase,
+        market_header::MarketHeader,
+        market_status::MarketStatus,
+        sector::{
+            NIL,
+            SECTOR_SIZE,
+        },
+        transmutable::Transmutable,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use price::OrderInfoArgs;
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+fn lamports(mollusk: &DropsetMolluskContext, address: &Address) -> u64 {
+    mollusk
+        .account_store
+        .borrow()
+        .get(address)
+        .map_or(0, |account| account.lamports)
+}
+
+fn data_len(mollusk: &DropsetMolluskContext, address: &Address) -> usize {
+    mollusk
+        .account_store
+        .borrow()
+        .get(address)
+        .map_or(0, |account| account.data.len())
+}
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+#[test]
+fn compact_market_over_multiple_instructions() -> anyhow::Result<()> {
+    let [closer_1, closer_2, trader]: [Address; 3] =
+        core::array::from_fn(|_| Address::new_unique());
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(
+        [closer_1, closer_2, trader]
+            .into_iter()
+            .map(|user| (user, funded_system_account()))
+            .collect(),
+    );
+    let authority = MOLLUSK_DEFAULT_MINT_AUTHORITY;
+
+    // Sectors are handed out from the front, so the closers' seats take the first two sectors and
+    // the trader's seat and ask take the next two.
+    let post_ask: Instruction = market
+        .post_order(
+            trader,
+            PostOrderInstructionData::new(
+                OrderInfoArgs::new_unscaled(10_000_000, 10),
+                false,
+                NIL,
+                0,
+                0,
+            ),
+        )
+        .into();
+    let setup = [
+        fund_and_deposit_instructions(&market, closer_1, 10, 0)?,
+        fund_and_deposit_instructions(&market, closer_2, 10, 0)?,
+        fund_and_deposit_instructions(&market, trader, 10, 0)?,
+        vec![
+            post_ask,
+            market.close_seat(closer_1, NIL).into(),
+            market.close_seat(closer_2, NIL).into(),
+        ],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&setup)
+        .program_result
+        .is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let num_sectors = view.header.num_free_sectors + 2;
+    assert_eq!(view.users[&trader].seat.index, 2);
+    assert_eq!(view.users[&trader].asks[0].index, 3);
+
+    // The market must be paused to be compacted.
+    let compact: Instruction = market.compact_market(authority, 1).into();
+    assert_eq!(
+        mollusk.process_instruction(&compact).program_result,
+        custom_error(DropsetError::MarketNotPaused)
+    );
+    let pause = market.update_market_status(authority, MarketStatus::Paused);
+    assert!(mollusk
+        .process_instruction(&pause.into())
+        .program_result
+        .is_ok());
+
+    // Visiting one sector per instruction relocates the trader's seat in the first instruction,
+    // without truncating the market yet.
+    let market_len_before = data_len(&mollusk, &market.market);
+    let authority_lamports_before = lamports(&mollusk, &authority);
+    assert!(mollusk.process_instruction(&compact).program_result.is_ok());
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(view.header.compaction_phase, CompactionPhase::Asks);
+    assert!(view.users[&trader].seat.index < 2);
+    assert_eq!(data_len(&mollusk, &market.market), market_len_before);
+
+    // The market can't be unpaused or expanded mid-compaction.
+    let unpause = market.update_market_status(authority, MarketStatus::Active);
+    assert_eq!(
+        mollusk.process_instruction(&unpause.into()).program_result,
+        custom_error(DropsetError::CompactionInProgress)
+    );
+    let expand = market.expand_market(authority, 1);
+    assert_eq!(
+        mollusk.process_instruction(&expand.into()).program_result,
+        custom_error(DropsetError::CompactionInProgress)
+    );
+
+    // The second instruction relocates the trader's ask, completes the compaction and truncates
+    // every free sector.
+    assert!(mollusk.process_instruction(&compact).program_result.is_ok());
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(view.header.compaction_phase, CompactionPhase::Idle);
+    assert_eq!(view.header.num_free_sectors, 0);
+    assert_eq!(
+        data_len(&mollusk, &market.market),
+        MarketHeader::LEN + 2 * SECTOR_SIZE
+    );
+    assert_eq!(
+        market_len_before,
+        MarketHeader::LEN + num_sectors as usize * SECTOR_SIZE
+    );
+    assert!(lamports(&mollusk, &authority) > authority_lamports_before);
+
+    // The relocated ask points back to the relocated seat and can be cancelled at its new index.
+    let trader_data = &view.users[&trader];
+    let ask = &trader_data.asks[0];
+    assert!(ask.index < 2);
+    assert_eq!(ask.user_seat, trader_data.seat.index);
+    let cancel = market.cancel_order(
+        trader,
+        CancelOrderInstructionData::new(ask.index, false, NIL, 0),
+    );
+    let unpause = market.update_market_status(authority, MarketStatus::Active);
+    assert!(mollusk
+        .process_instruction_chain(&[unpause.into(), cancel.into()])
+        .program_result
+        .is_ok());
+    assert!(market_view(&mollusk, &market)?.asks.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+        MOLLUSK_DEFAULT_NUM_SECTORS,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    state::{
+        market_header::MarketHeader,
+        market_status::MarketStatus,
+        sector::SECTOR_SIZE,
+        transmutable::Transmutable,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use solana_address::Address;
+use solana_program_error::ProgramError;
+use solana_sdk::rent::Rent;
+
+const NUM_SECTORS: u16 = 5;
+
+fn setup() -> (DropsetMolluskContext, MarketContext, Address) {
+    let payer = Address::new_unique();
+    let (mollusk, market) =
+        new_dropset_mollusk_context_with_default_market(vec![(payer, funded_system_account())]);
+    (mollusk, market, payer)
+}
+
+fn lamports(mollusk: &DropsetMolluskContext, address: &Address) -> u64 {
+    mollusk
+        .account_store
+        .borrow()
+        .get(address)
+        .map_or(0, |account| account.lamports)
+}
+
+fn market_data_len(num_sectors: u16) -> usize {
+    MarketHeader::LEN + num_sectors as usize * SECTOR_SIZE
+}
+
+/// Checks that the market was expanded by [`NUM_SECTORS`] free sectors and that the new sectors
+/// can be used.
+fn check_expanded(mollusk: &DropsetMolluskContext, market: &MarketContext) -> anyhow::Result<()> {
+    let num_sectors = MOLLUSK_DEFAULT_NUM_SECTORS + NUM_SECTORS;
+    let data_len = mollusk
+        .account_store
+        .borrow()
+        .get(&market.market)
+        .map_or(0, |account| account.data.len());
+    assert_eq!(data_len, market_data_len(num_sectors));
+    assert_eq!(
+        market_view(mollusk, market)?.header.num_free_sectors,
+        num_sectors as u32
+    );
+
+    // Fill every sector with a seat, including the new ones.
+    let users: Vec<Address> = (0..num_sectors).map(|_| Address::new_unique()).collect();
+    for user in users.iter() {
+        mollusk
+            .account_store
+            .borrow_mut()
+            .insert(*user, funded_system_account());
+        let deposit = fund_and_deposit_instructions(market, *user, 1, 0)?;
+        assert!(mollusk
+            .process_instruction_chain(&deposit)
+            .program_result
+            .is_ok());
+    }
+    let view = market_view(mollusk, market)?;
+    assert_eq!(view.header.num_free_sectors, 0);
+    assert_eq!(view.seats.len(), num_sectors as usize);
+
+    Ok(())
+}
+
+#[test]
+fn expand_market_payer_funds_rent() -> anyhow::Result<()> {
+    let (mollusk, market, payer) = setup();
+    let payer_lamports_before = lamports(&mollusk, &payer);
+
+    let expand = market.expand_market(payer, NUM_SECTORS);
+    assert!(mollusk
+        .process_instruction(&expand.into())
+        .program_result
+        .is_ok());
+
+    let rent = Rent::default();
+    let rent_diff = rent
+        .minimum_balance(market_data_len(MOLLUSK_DEFAULT_NUM_SECTORS + NUM_SECTORS))
+        - rent.minimum_balance(market_data_len(MOLLUSK_DEFAULT_NUM_SECTORS));
+    assert_eq!(
+        lamports(&mollusk, &payer),
+        payer_lamports_before - rent_diff
+    );
+
+    check_expanded(&mollusk, &market)
+}
+
+#[test]
+fn expand_prefunded_market() -> anyhow::Result<()> {
+    let (mollusk, market, payer) = setup();
+
+    // Send the market account enough lamports for the new sectors ahead of time, so that the payer
+    // doesn't need to transfer anything.
+    let prefunded_lamports =
+        Rent::default().minimum_balance(market_data_len(MOLLUSK_DEFAULT_NUM_SECTORS + NUM_SECTORS));
+    mollusk
+        .account_store
+        .borrow_mut()
+        .get_mut(&market.market)
+        .expect("Market account should exist")
+        .lamports = prefunded_lamports;
+    let payer_lamports_before = lamports(&mollusk, &payer);
+
+    let expand = market.expand_market(payer, NUM_SECTORS);
+    assert!(mollusk
+        .process_instruction(&expand.into())
+        .program_result
+        .is_ok());
+
+    // The account is still resized, even though no lamports were transferred.
+    assert_eq!(lamports(&mollusk, &payer), payer_lamports_before);
+    assert_eq!(lamports(&mollusk, &market.market), prefunded_lamports);
+
+    check_expanded(&mollusk, &market)
+}
+
+#[test]
+fn expand_market_zero_sectors() {
+    let (mollusk, market, payer) = setup();
+
+    let expand = market.expand_market(payer, 0);
+    assert_eq!(
+        mollusk.process_instruction(&expand.into()).program_result,
+        ProgramResult::Failure(ProgramError::Custom(
+            DropsetError::InvalidNonZeroInteger as u32
+        ))
+    );
+}
+
+#[test]
+fn expand_closed_market() {
+    let (mollusk, market, payer) = setup();
+
+    let close = market.update_market_status(MOLLUSK_DEFAULT_MINT_AUTHORITY, MarketStatus::Closed);
+    assert!(mollusk
+        .process_instruction(&close.into())
+        .program_result
+        .is_ok());
+
+    let expand = market.expand_market(payer, NUM_SECTORS);
+    assert_eq!(
+        mollusk.process_instruction(&expand.into()).program_result,
+        ProgramResult::Failure(ProgramError::Custom(DropsetError::MarketClosed as u32))
+    );
+}
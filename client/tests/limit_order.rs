@@ -0,0 +1,198 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    instructions::{
+        LimitOrderInstructionData,
+        MarketParams,
+        PostOrderInstructionData,
+        SelfTradePrevention,
+        UpdateMarketParamsInstructionData,
+    },
+    state::sector::NIL,
+};
+use price::{
+    encoded_price,
+    OrderInfoArgs,
+};
+use solana_address::Address;
+
+const TAKER_FEE_BPS: u16 = 10;
+const MAKER_REBATE_BPS: u16 = 5;
+const MAKER_PRICE: u32 = 10_000_000;
+
+/// Sets up the default market with fees, a maker resting a 10 base order at [`MAKER_PRICE`] on the
+/// `maker_is_bid` side of the book and a funded taker with a seat.
+fn setup(
+    maker_is_bid: bool,
+    taker_base: u64,
+    taker_quote: u64,
+) -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address, Address)> {
+    let maker = Address::new_unique();
+    let taker = Address::new_unique();
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (maker, funded_system_account()),
+        (taker, funded_system_account()),
+    ]);
+
+    let set_fees = market.update_market_params(
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+        UpdateMarketParamsInstructionData::new(MarketParams {
+            fee_authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            taker_fee_bps: TAKER_FEE_BPS,
+            maker_rebate_bps: MAKER_REBATE_BPS,
+            ..Default::default()
+        }),
+    );
+    let maker_order = market.post_order(
+        maker,
+        PostOrderInstructionData::new(
+            OrderInfoArgs::new_unscaled(MAKER_PRICE, 10),
+            maker_is_bid,
+            NIL,
+            0,
+            0,
+        ),
+    );
+
+    let instructions = [
+        vec![set_fees.into()],
+        fund_and_deposit_instructions(&market, maker, 100, 1_000_000_000)?,
+        vec![maker_order.into()],
+        fund_and_deposit_instructions(&market, taker, taker_base, taker_quote)?,
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    Ok((mollusk, market, maker, taker))
+}
+
+#[test]
+fn limit_bid_partially_fills_and_posts_remainder() -> anyhow::Result<()> {
+    const TAKER_QUOTE: u64 = 300_000_000;
+    const LIMIT_PRICE: u32 = 11_000_000;
+    let (mollusk, market, maker, taker) = setup(false, 0, TAKER_QUOTE)?;
+    let maker_before = market_view(&mollusk, &market)?.users[&maker].seat.clone();
+
+    // Buy 20 base with a limit above the resting ask, filling its 10 base and posting the rest.
+    let limit_bid = market.limit_order(
+        taker,
+        LimitOrderInstructionData::new(
+            OrderInfoArgs::new_unscaled(LIMIT_PRICE, 20),
+            true,
+            NIL,
+            SelfTradePrevention::CancelTaking,
+            0,
+            0,
+        ),
+    );
+    assert!(mollusk
+        .process_instruction(&limit_bid.into())
+        .program_result
+        .is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let quote_filled = 10 * MAKER_PRICE as u64;
+    let taker_fee = quote_filled * TAKER_FEE_BPS as u64 / 10_000;
+    let maker_rebate = quote_filled * MAKER_REBATE_BPS as u64 / 10_000;
+    let quote_posted = 10 * LIMIT_PRICE as u64;
+
+    // The resting ask is filled entirely and the remainder rests as a bid at the limit price.
+    assert!(view.asks.is_empty());
+    assert_eq!(view.bids.len(), 1);
+    let taker_data = &view.users[&taker];
+    let posted = &taker_data.bids[0];
+    assert_eq!(
+        posted.encoded_price,
+        encoded_price!(LIMIT_PRICE, 0).as_u32()
+    );
+    assert_eq!(posted.base_remaining, 10);
+    assert_eq!(posted.quote_remaining, quote_posted);
+
+    // The taker pays for the fill plus the taker fee, and the posted bid's quote collateral.
+    assert_eq!(taker_data.seat.base_available, 10);
+    assert_eq!(
+        taker_data.seat.quote_available,
+        TAKER_QUOTE - quote_filled - taker_fee - quote_posted
+    );
+
+    // The maker receives the quote filled plus their rebate, and the protocol keeps the rest.
+    let maker_after = &view.users[&maker].seat;
+    assert_eq!(maker_after.base_available, maker_before.base_available);
+    assert_eq!(
+        maker_after.quote_available,
+        maker_before.quote_available + quote_filled + maker_rebate
+    );
+    assert_eq!(view.header.protocol_fees, taker_fee - maker_rebate);
+
+    Ok(())
+}
+
+#[test]
+fn limit_ask_partially_fills_and_posts_remainder() -> anyhow::Result<()> {
+    const TAKER_BASE: u64 = 25;
+    const LIMIT_PRICE: u32 = 9_000_000;
+    let (mollusk, market, maker, taker) = setup(true, TAKER_BASE, 0)?;
+    let maker_before = market_view(&mollusk, &market)?.users[&maker].seat.clone();
+
+    // Sell 25 base with a limit below the resting bid, filling its 10 base and posting the rest.
+    let limit_ask = market.limit_order(
+        taker,
+        LimitOrderInstructionData::new(
+            OrderInfoArgs::new_unscaled(LIMIT_PRICE, TAKER_BASE),
+            false,
+            NIL,
+            SelfTradePrevention::CancelTaking,
+            0,
+            0,
+        ),
+    );
+    assert!(mollusk
+        .process_instruction(&limit_ask.into())
+        .program_result
+        .is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let quote_filled = 10 * MAKER_PRICE as u64;
+    let taker_fee = quote_filled * TAKER_FEE_BPS as u64 / 10_000;
+    let maker_rebate = quote_filled * MAKER_REBATE_BPS as u64 / 10_000;
+
+    assert!(view.bids.is_empty());
+    assert_eq!(view.asks.len(), 1);
+    let taker_data = &view.users[&taker];
+    let posted = &taker_data.asks[0];
+    assert_eq!(
+        posted.encoded_price,
+        encoded_price!(LIMIT_PRICE, 0).as_u32()
+    );
+    assert_eq!(posted.base_remaining, 15);
+    assert_eq!(posted.quote_remaining, 15 * LIMIT_PRICE as u64);
+
+    // All of the taker's base is either filled or used as the posted ask's collateral, and they
+    // receive the quote filled minus the taker fee.
+    assert_eq!(taker_data.seat.base_available, 0);
+    assert_eq!(taker_data.seat.quote_available, quote_filled - taker_fee);
+
+    // The maker's bid collateral was already taken when it was posted, so they only receive base
+    // and their rebate.
+    let maker_after = &view.users[&maker].seat;
+    assert_eq!(maker_after.base_available, maker_before.base_available + 10);
+    assert_eq!(
+        maker_after.quote_available,
+        maker_before.quote_available + maker_rebate
+    );
+    assert_eq!(view.header.protocol_fees, taker_fee - maker_rebate);
+
+    Ok(())
+}
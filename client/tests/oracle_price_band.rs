@@ -0,0 +1,436 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        oracle_price_account,
+        token_balance,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        AmendOrderInstructionData,
+        LimitOrderInstructionData,
+        MarketOrderInstructionData,
+        PostOrderInstructionData,
+        SelfTradePrevention,
+        TimeInForce,
+    },
+    matching::simulate_market_order,
+    state::{
+        market::MarketRef,
+        sector::NIL,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use price::{
+    encoded_price,
+    OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+};
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const BAND_BPS: u16 = 100;
+const MAX_STALENESS_SECS: u32 = 60;
+const NOW: u64 = 100;
+const ORACLE_PRICE: u32 = 20_000_000;
+const MAKER: Address = MOLLUSK_DEFAULT_MINT_AUTHORITY;
+const TAKER_BALANCE: u64 = 1_000_000_000_000;
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+fn post_ask(market: &MarketContext, price_mantissa: u32) -> Instruction {
+    market
+        .post_order(
+            MAKER,
+            PostOrderInstructionData::new(
+                OrderInfoArgs::new_unscaled(price_mantissa, 10),
+                false,
+                NIL,
+                0,
+                0,
+            ),
+        )
+        .into()
+}
+
+fn market_buy(market: &MarketContext, taker: Address, order_size: u64) -> Instruction {
+    market
+        .market_order(
+            taker,
+            MarketOrderInstructionData::new(
+                order_size,
+                true,
+                true,
+                ENCODED_PRICE_INFINITY,
+                0,
+                TimeInForce::ImmediateOrCancel,
+                SelfTradePrevention::CancelTaking,
+            ),
+        )
+        .into()
+}
+
+/// Overwrites the oracle account with a new price published at `publish_time`, moves the clock to
+/// `publish_time` and caches the new price in the market.
+fn publish_oracle_price(
+    mollusk: &mut DropsetMolluskContext,
+    market: &MarketContext,
+    oracle: Address,
+    price_mantissa: u32,
+    publish_time: u64,
+) {
+    mollusk.account_store.borrow_mut().insert(
+        oracle,
+        oracle_price_account(encoded_price!(price_mantissa, 0).as_u32(), publish_time),
+    );
+    mollusk.mollusk.sysvars.clock.unix_timestamp = publish_time as i64;
+    assert!(mollusk
+        .process_instruction(&market.update_oracle_price(oracle))
+        .program_result
+        .is_ok());
+}
+
+/// Sets up the default market with an oracle priced at [`ORACLE_PRICE`] as of [`NOW`], a maker with
+/// base deposited and a taker holding [`TAKER_BALANCE`] quote in their token account.
+fn setup() -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address, Address)> {
+    let oracle = Address::new_unique();
+    let taker = Address::new_unique();
+    let (mut mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (taker, funded_system_account()),
+        (
+            oracle,
+            oracle_price_account(encoded_price!(ORACLE_PRICE, 0).as_u32(), NOW),
+        ),
+    ]);
+    mollusk.mollusk.sysvars.clock.unix_timestamp = NOW as i64;
+
+    let instructions = [
+        vec![
+            market
+                .set_market_oracle(MAKER, oracle, BAND_BPS, MAX_STALENESS_SECS)
+                .into(),
+            market.update_oracle_price(oracle),
+        ],
+        fund_and_deposit_instructions(&market, MAKER, 1_000, 0)?,
+        fund_and_deposit_instructions(&market, taker, 0, 0)?,
+        vec![market
+            .quote
+            .mint_to(&MAKER, &market.get_quote_ata(&taker), TAKER_BALANCE)?],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    Ok((mollusk, market, oracle, taker))
+}
+
+/// Builds on [`setup`] with three asks and moves the oracle so that the cheapest ask is below the
+/// band, the middle ask is within it and the most expensive ask is above it.
+fn setup_asks_around_band() -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address)> {
+    let (mut mollusk, market, oracle, taker) = setup()?;
+
+    // Both asks are within the 1% band around 20.0.
+    assert!(mollusk
+        .process_instruction_chain(&[post_ask(&market, 19_900_000), post_ask(&market, 20_100_000)])
+        .program_result
+        .is_ok());
+    // The most expensive ask is within the 1% band around 20.2.
+    publish_oracle_price(&mut mollusk, &market, oracle, 20_200_000, NOW + 1);
+    assert!(mollusk
+        .process_instruction(&post_ask(&market, 20_300_000))
+        .program_result
+        .is_ok());
+
+    // Only the middle ask is within the 0.5% band around 20.1.
+    let narrow_band = market.set_market_oracle(MAKER, oracle, 50, MAX_STALENESS_SECS);
+    assert!(mollusk
+        .process_instruction(&narrow_band.into())
+        .program_result
+        .is_ok());
+    publish_oracle_price(&mut mollusk, &market, oracle, 20_100_000, NOW + 2);
+
+    Ok((mollusk, market, taker))
+}
+
+#[test]
+fn oracle_price_band() -> anyhow::Result<()> {
+    let (mollusk, market, oracle, _) = setup()?;
+
+    let header = market_view(&mollusk, &market)?.header;
+    assert_eq!(header.oracle, oracle);
+    assert_eq!(header.oracle_band_bps, BAND_BPS);
+    assert_eq!(header.oracle_max_staleness, MAX_STALENESS_SECS);
+    assert_eq!(
+        header.oracle_price,
+        encoded_price!(ORACLE_PRICE, 0).as_u32()
+    );
+    assert_eq!(header.oracle_publish_time, NOW);
+
+    // 20% above the oracle price is outside the 1% band.
+    assert_eq!(
+        mollusk
+            .process_instruction(&post_ask(&market, 24_000_000))
+            .program_result,
+        custom_error(DropsetError::PriceOutsideOracleBand)
+    );
+
+    // 0.5% above the oracle price is inside the band.
+    assert!(mollusk
+        .process_instruction(&post_ask(&market, 20_100_000))
+        .program_result
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn market_order_stops_at_far_band_edge() -> anyhow::Result<()> {
+    let (mollusk, market, taker) = setup_asks_around_band()?;
+
+    let fill = {
+        let store = mollusk.account_store.borrow();
+        let data = &store
+            .get(&market.market)
+            .expect("Market account should exist")
+            .data;
+        // Safety: The market account was initialized by the program.
+        let market_ref = unsafe { MarketRef::from_bytes(data) };
+        simulate_market_order::<_, _, true, true>(
+            &market_ref,
+            &taker,
+            30,
+            ENCODED_PRICE_INFINITY,
+            NOW + 2,
+        )?
+    };
+    // The ask below the band isn't filled and matching stops at the ask above it.
+    assert_eq!(fill.base, 10);
+    assert!(!fill.is_fully_filled);
+
+    assert!(mollusk
+        .process_instruction(&market_buy(&market, taker, 30))
+        .program_result
+        .is_ok());
+    assert_eq!(
+        token_balance(&mollusk, &market.get_base_ata(&taker)),
+        fill.base
+    );
+    assert_eq!(
+        token_balance(&mollusk, &market.get_quote_ata(&taker)),
+        TAKER_BALANCE - fill.quote - fill.taker_fee
+    );
+
+    // Only the ask within the band was filled. The ask below the band was removed and the ask
+    // above it is left on the book.
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(
+        view.asks
+            .iter()
+            .map(|ask| (ask.encoded_price, ask.base_remaining))
+            .collect::<Vec<_>>(),
+        vec![(encoded_price!(20_300_000, 0).as_u32(), 10)]
+    );
+    // The removed ask's base was returned to the maker, who posted 30 of their 1,000 base.
+    assert_eq!(view.users[&MAKER].seat.base_available, 1_000 - 20);
+
+    Ok(())
+}
+
+#[test]
+fn out_of_band_order_is_removed_so_posts_can_cross_it() -> anyhow::Result<()> {
+    let (mollusk, market, taker) = setup_asks_around_band()?;
+    assert!(mollusk
+        .process_instruction(&market.deposit_quote(taker, TAKER_BALANCE, NIL).into())
+        .program_result
+        .is_ok());
+
+    let post_bid = |price_mantissa: u32| -> Instruction {
+        market
+            .post_order(
+                taker,
+                PostOrderInstructionData::new(
+                    OrderInfoArgs::new_unscaled(price_mantissa, 10),
+                    true,
+                    NIL,
+                    0,
+                    0,
+                ),
+            )
+            .into()
+    };
+
+    // A bid within the band crosses the ask below the band, so it can't be posted.
+    assert_eq!(
+        mollusk
+            .process_instruction(&post_bid(20_000_000))
+            .program_result,
+        custom_error(DropsetError::PostOnlyWouldImmediatelyFill)
+    );
+
+    // A limit bid removes the ask below the band instead of skipping it, fills the ask within the
+    // band and then posts the rest of the bid without crossing the ask below the band.
+    let limit_bid = market.limit_order(
+        taker,
+        LimitOrderInstructionData::new(
+            OrderInfoArgs::new_unscaled(20_100_000, 20),
+            true,
+            NIL,
+            SelfTradePrevention::CancelTaking,
+            0,
+            0,
+        ),
+    );
+    assert!(mollusk
+        .process_instruction(&limit_bid.into())
+        .program_result
+        .is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    assert_eq!(
+        view.asks
+            .iter()
+            .map(|ask| ask.encoded_price)
+            .collect::<Vec<_>>(),
+        vec![encoded_price!(20_300_000, 0).as_u32()]
+    );
+    assert_eq!(
+        view.bids
+            .iter()
+            .map(|bid| (bid.encoded_price, bid.base_remaining))
+            .collect::<Vec<_>>(),
+        vec![(encoded_price!(20_100_000, 0).as_u32(), 10)]
+    );
+    assert_eq!(view.users[&taker].seat.base_available, 10);
+
+    // With the ask below the band gone, the bid that crossed it can be posted too.
+    assert!(mollusk
+        .process_instruction(&post_bid(20_000_000))
+        .program_result
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn amend_checks_oracle_band() -> anyhow::Result<()> {
+    let (mollusk, market, _) = setup_asks_around_band()?;
+
+    let asks = market_view(&mollusk, &market)?.asks;
+    let amend = |ask_index: usize, price_mantissa: u32| -> Instruction {
+        market
+            .amend_order(
+                MAKER,
+                AmendOrderInstructionData::new(
+                    asks[ask_index].index,
+                    false,
+                    OrderInfoArgs::new_unscaled(price_mantissa, 5),
+                    NIL,
+                ),
+            )
+            .into()
+    };
+
+    assert_eq!(
+        mollusk
+            .process_instruction(&amend(0, 19_900_000))
+            .program_result,
+        custom_error(DropsetError::PriceOutsideOracleBand)
+    );
+    assert!(mollusk
+        .process_instruction(&amend(1, 20_100_000))
+        .program_result
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn stale_oracle_price_rejects_posts_and_fills() -> anyhow::Result<()> {
+    let (mut mollusk, market, oracle, taker) = setup()?;
+    assert!(mollusk
+        .process_instruction(&post_ask(&market, ORACLE_PRICE))
+        .program_result
+        .is_ok());
+
+    mollusk.mollusk.sysvars.clock.unix_timestamp = (NOW + MAX_STALENESS_SECS as u64 + 1) as i64;
+    for instruction in [
+        post_ask(&market, ORACLE_PRICE),
+        market_buy(&market, taker, 5),
+    ] {
+        assert_eq!(
+            mollusk.process_instruction(&instruction).program_result,
+            custom_error(DropsetError::OraclePriceStale)
+        );
+    }
+
+    // Refreshing the cached price allows posts and fills again.
+    publish_oracle_price(
+        &mut mollusk,
+        &market,
+        oracle,
+        ORACLE_PRICE,
+        NOW + MAX_STALENESS_SECS as u64 + 1,
+    );
+    assert!(mollusk
+        .process_instruction_chain(&[
+            post_ask(&market, ORACLE_PRICE),
+            market_buy(&market, taker, 5)
+        ])
+        .program_result
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn update_oracle_price_rejects_future_publish_time() -> anyhow::Result<()> {
+    let (mollusk, market, oracle, _) = setup()?;
+
+    mollusk.account_store.borrow_mut().insert(
+        oracle,
+        oracle_price_account(encoded_price!(ORACLE_PRICE, 0).as_u32(), NOW + 1),
+    );
+    assert_eq!(
+        mollusk
+            .process_instruction(&market.update_oracle_price(oracle))
+            .program_result,
+        custom_error(DropsetError::InvalidOracleAccount)
+    );
+    assert_eq!(
+        market_view(&mollusk, &market)?.header.oracle_publish_time,
+        NOW
+    );
+
+    Ok(())
+}
+
+#[test]
+fn update_oracle_price_rejects_wrong_oracle() -> anyhow::Result<()> {
+    let (mollusk, market, _, _) = setup()?;
+
+    let other_oracle = Address::new_unique();
+    mollusk.account_store.borrow_mut().insert(
+        other_oracle,
+        oracle_price_account(encoded_price!(ORACLE_PRICE, 0).as_u32(), NOW),
+    );
+    assert_eq!(
+        mollusk
+            .process_instruction(&market.update_oracle_price(other_oracle))
+            .program_result,
+        custom_error(DropsetError::IncorrectOracle)
+    );
+
+    Ok(())
+}
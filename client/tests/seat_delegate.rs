@@ -0,0 +1,510 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+        MOLLUSK_DEFAULT_NUM_SECTORS,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        CancelOrderInstructionData,
+        LimitOrderInstructionData,
+        PostOrderInstructionData,
+        SeatMarketOrderInstructionData,
+        SelfTradePrevention,
+        TimeInForce,
+    },
+    state::{
+        compaction_phase::CompactionPhase,
+        market::MarketRef,
+        market_header::MarketHeader,
+        market_seat::{
+            MarketSeat,
+            NO_SEAT_DELEGATE,
+        },
+        market_status::MarketStatus,
+        sector::{
+            Sector,
+            SectorIndex,
+            NIL,
+            SECTOR_SIZE,
+        },
+        transmutable::Transmutable,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use price::{
+    OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+};
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const PRICE: u32 = 10_000_000;
+
+struct Setup {
+    mollusk: DropsetMolluskContext,
+    market: MarketContext,
+    owner: Address,
+    delegate: Address,
+    seat_index: SectorIndex,
+}
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+fn num_free_sectors(setup: &Setup) -> anyhow::Result<u32> {
+    Ok(market_view(&setup.mollusk, &setup.market)?
+        .header
+        .num_free_sectors)
+}
+
+/// Sets up the default market with an owner holding a funded seat and a delegate without a seat.
+fn setup() -> anyhow::Result<Setup> {
+    let owner = Address::new_unique();
+    let delegate = Address::new_unique();
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (owner, funded_system_account()),
+        (delegate, funded_system_account()),
+    ]);
+
+    let deposit = fund_and_deposit_instructions(&market, owner, 100, 1_000_000_000)?;
+    assert!(mollusk
+        .process_instruction_chain(&deposit)
+        .program_result
+        .is_ok());
+    let seat_index = market_view(&mollusk, &market)?.users[&owner].seat.index;
+
+    Ok(Setup {
+        mollusk,
+        market,
+        owner,
+        delegate,
+        seat_index,
+    })
+}
+
+/// Sets the owner's delegate and checks that the seat reports it.
+fn set_delegate(setup: &Setup, can_take: bool) -> anyhow::Result<()> {
+    let set =
+        setup
+            .market
+            .set_seat_delegate(setup.owner, setup.delegate, can_take, setup.seat_index);
+    assert!(setup
+        .mollusk
+        .process_instruction(&set.into())
+        .program_result
+        .is_ok());
+
+    let seat = market_view(&setup.mollusk, &setup.market)?.users[&setup.owner]
+        .seat
+        .clone();
+    assert_eq!(seat.delegate, setup.delegate);
+    assert_eq!(seat.delegate_can_take, can_take);
+
+    Ok(())
+}
+
+fn post_ask(setup: &Setup, signer: Address, hint: SectorIndex) -> Instruction {
+    setup
+        .market
+        .post_order(
+            signer,
+            PostOrderInstructionData::new(
+                OrderInfoArgs::new_unscaled(PRICE, 10),
+                false,
+                hint,
+                0,
+                0,
+            ),
+        )
+        .into()
+}
+
+#[test]
+fn delegate_posts_and_cancels_orders() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        owner,
+        delegate,
+        seat_index,
+    } = &setup;
+
+    // The delegate is stored in its own sector.
+    let free_before = num_free_sectors(&setup)?;
+    set_delegate(&setup, false)?;
+    assert_eq!(num_free_sectors(&setup)?, free_before - 1);
+
+    // The delegate has no seat of its own, so it must pass the owner's seat index.
+    assert_eq!(
+        mollusk
+            .process_instruction(&post_ask(&setup, *delegate, NIL))
+            .program_result,
+        custom_error(DropsetError::SeatNotFound)
+    );
+    assert!(mollusk
+        .process_instruction(&post_ask(&setup, *delegate, *seat_index))
+        .program_result
+        .is_ok());
+
+    // The order belongs to the owner's seat.
+    let view = market_view(mollusk, market)?;
+    let ask = &view.users[owner].asks[0];
+    assert_eq!(ask.user_seat, *seat_index);
+
+    let cancel = market.cancel_order(
+        *delegate,
+        CancelOrderInstructionData::new(ask.index, false, *seat_index, 0),
+    );
+    assert!(mollusk
+        .process_instruction(&cancel.into())
+        .program_result
+        .is_ok());
+    assert!(market_view(mollusk, market)?.asks.is_empty());
+
+    // Revoking the delegate frees its sector and stops it from posting.
+    let revoke = market.revoke_seat_delegate(*owner, *seat_index);
+    assert!(mollusk
+        .process_instruction(&revoke.into())
+        .program_result
+        .is_ok());
+    assert_eq!(num_free_sectors(&setup)?, free_before);
+    assert_eq!(
+        market_view(mollusk, market)?.users[owner].seat.delegate,
+        NO_SEAT_DELEGATE
+    );
+    assert_eq!(
+        mollusk
+            .process_instruction(&post_ask(&setup, *delegate, *seat_index))
+            .program_result,
+        custom_error(DropsetError::InvalidIndexHint)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn delegate_cannot_withdraw_or_close_seat() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        owner,
+        delegate,
+        seat_index,
+    } = &setup;
+    set_delegate(&setup, true)?;
+
+    let create_atas = [
+        market.base.create_ata(delegate, delegate),
+        market.quote.create_ata(delegate, delegate),
+    ];
+    assert!(mollusk
+        .process_instruction_chain(&create_atas)
+        .program_result
+        .is_ok());
+
+    let withdraw = market.withdraw_base(*delegate, 1, *seat_index);
+    assert_eq!(
+        mollusk.process_instruction(&withdraw.into()).program_result,
+        custom_error(DropsetError::InvalidIndexHint)
+    );
+    let close_seat = market.close_seat(*delegate, *seat_index);
+    assert_eq!(
+        mollusk
+            .process_instruction(&close_seat.into())
+            .program_result,
+        custom_error(DropsetError::InvalidIndexHint)
+    );
+
+    // Closing the seat frees the delegate's sector along with the seat's.
+    let close_seat = market.close_seat(*owner, *seat_index);
+    assert!(mollusk
+        .process_instruction(&close_seat.into())
+        .program_result
+        .is_ok());
+    assert_eq!(
+        num_free_sectors(&setup)?,
+        MOLLUSK_DEFAULT_NUM_SECTORS as u32
+    );
+
+    Ok(())
+}
+
+#[test]
+fn delegate_sector_is_not_a_seat() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        delegate,
+        seat_index,
+        ..
+    } = &setup;
+    set_delegate(&setup, true)?;
+
+    let delegate_sector = {
+        let store = mollusk.account_store.borrow();
+        let data = &store
+            .get(&market.market)
+            .expect("Market account should exist")
+            .data;
+        // Safety: The market account was initialized by the program.
+        let market_ref = unsafe { MarketRef::from_bytes(data) };
+        // Safety: The seat index came from the market view, so it's in-bounds.
+        unsafe { Sector::from_sector_index(market_ref.sectors, *seat_index) }
+            .load_payload::<MarketSeat>()
+            .delegate_sector()
+    };
+    assert_ne!(delegate_sector, NIL);
+
+    let create_atas = [
+        market.base.create_ata(delegate, delegate),
+        market.quote.create_ata(delegate, delegate),
+    ];
+    assert!(mollusk
+        .process_instruction_chain(&create_atas)
+        .program_result
+        .is_ok());
+
+    // The delegate sector stores the delegate's address, but it can't be passed off as a seat
+    // whose user is the delegate.
+    let cancel = market.cancel_order(
+        *delegate,
+        CancelOrderInstructionData::new(0, false, delegate_sector, 0),
+    );
+    let withdraw = market.withdraw_base(*delegate, 1, delegate_sector);
+    let instructions: [Instruction; 2] = [cancel.into(), withdraw.into()];
+    for instruction in instructions {
+        assert_eq!(
+            mollusk.process_instruction(&instruction).program_result,
+            custom_error(DropsetError::InvalidIndexHint)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn delegate_cannot_take_without_permission() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        delegate,
+        seat_index,
+        ..
+    } = &setup;
+
+    let maker = Address::new_unique();
+    mollusk
+        .account_store
+        .borrow_mut()
+        .insert(maker, funded_system_account());
+    let resting_ask = [
+        fund_and_deposit_instructions(market, maker, 10, 0)?,
+        vec![post_ask(&setup, maker, NIL)],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&resting_ask)
+        .program_result
+        .is_ok());
+    set_delegate(&setup, false)?;
+
+    let limit_bid: Instruction = market
+        .limit_order(
+            *delegate,
+            LimitOrderInstructionData::new(
+                OrderInfoArgs::new_unscaled(PRICE, 1),
+                true,
+                *seat_index,
+                SelfTradePrevention::CancelTaking,
+                0,
+                0,
+            ),
+        )
+        .into();
+    let market_buy: Instruction = market
+        .seat_market_order(
+            *delegate,
+            SeatMarketOrderInstructionData::new(
+                1,
+                true,
+                true,
+                ENCODED_PRICE_INFINITY,
+                0,
+                TimeInForce::ImmediateOrCancel,
+                SelfTradePrevention::CancelTaking,
+                *seat_index,
+            ),
+        )
+        .into();
+    assert_eq!(
+        mollusk.process_instruction(&limit_bid).program_result,
+        custom_error(DropsetError::DelegateCannotTake)
+    );
+    assert_eq!(
+        mollusk.process_instruction(&market_buy).program_result,
+        custom_error(DropsetError::DelegateCannotTake)
+    );
+
+    // Updating the delegate reuses its sector.
+    let free_before = num_free_sectors(&setup)?;
+    set_delegate(&setup, true)?;
+    assert_eq!(num_free_sectors(&setup)?, free_before);
+    assert!(mollusk
+        .process_instruction_chain(&[limit_bid, market_buy])
+        .program_result
+        .is_ok());
+    assert_eq!(market_view(mollusk, market)?.asks[0].base_remaining, 8);
+
+    Ok(())
+}
+
+#[test]
+fn only_owner_sets_or_revokes_delegate() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        owner,
+        delegate,
+        seat_index,
+    } = &setup;
+    set_delegate(&setup, false)?;
+
+    // Neither the delegate nor anyone else can act on the owner's seat delegate.
+    let stranger = Address::new_unique();
+    mollusk
+        .account_store
+        .borrow_mut()
+        .insert(stranger, funded_system_account());
+    for signer in [*delegate, stranger] {
+        let set = market.set_seat_delegate(signer, signer, true, *seat_index);
+        assert_eq!(
+            mollusk.process_instruction(&set.into()).program_result,
+            custom_error(DropsetError::InvalidIndexHint)
+        );
+        let set = market.set_seat_delegate(signer, signer, true, NIL);
+        assert_eq!(
+            mollusk.process_instruction(&set.into()).program_result,
+            custom_error(DropsetError::SeatNotFound)
+        );
+        let revoke = market.revoke_seat_delegate(signer, *seat_index);
+        assert_eq!(
+            mollusk.process_instruction(&revoke.into()).program_result,
+            custom_error(DropsetError::InvalidIndexHint)
+        );
+    }
+
+    let seat = market_view(mollusk, market)?.users[owner].seat.clone();
+    assert_eq!(seat.delegate, *delegate);
+    assert!(!seat.delegate_can_take);
+
+    Ok(())
+}
+
+#[test]
+fn compact_market_relocates_delegate() -> anyhow::Result<()> {
+    let setup = setup()?;
+    let Setup {
+        mollusk,
+        market,
+        owner,
+        delegate,
+        seat_index,
+    } = &setup;
+    let authority = MOLLUSK_DEFAULT_MINT_AUTHORITY;
+
+    // Sectors are handed out from the front, so the owner's seat, the closer's seat, the owner's
+    // ask and the delegate take the first four sectors. Closing the closer's seat leaves the
+    // delegate as the only live sector past the three live sectors the market compacts to.
+    let closer = Address::new_unique();
+    mollusk
+        .account_store
+        .borrow_mut()
+        .insert(closer, funded_system_account());
+    let instructions = [
+        fund_and_deposit_instructions(market, closer, 10, 0)?,
+        vec![post_ask(&setup, *owner, *seat_index)],
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+    set_delegate(&setup, false)?;
+    assert!(mollusk
+        .process_instruction(&market.close_seat(closer, NIL).into())
+        .program_result
+        .is_ok());
+    let view = market_view(mollusk, market)?;
+    assert_eq!(*seat_index, 0);
+    assert_eq!(view.users[owner].asks[0].index, 2);
+    assert_eq!(
+        view.header.num_free_sectors,
+        MOLLUSK_DEFAULT_NUM_SECTORS as u32 - 3
+    );
+
+    // Visiting one sector relocates the delegate along with its seat, and stops at the ask.
+    let pause = market.update_market_status(authority, MarketStatus::Paused);
+    let compact: Instruction = market.compact_market(authority, 1).into();
+    assert!(mollusk
+        .process_instruction_chain(&[pause.into(), compact.clone()])
+        .program_result
+        .is_ok());
+    assert_eq!(
+        market_view(mollusk, market)?.header.compaction_phase,
+        CompactionPhase::Asks
+    );
+
+    // The delegate can't be set or revoked mid-compaction, since that allocates or frees sectors.
+    let set = market.set_seat_delegate(*owner, *delegate, true, *seat_index);
+    assert_eq!(
+        mollusk.process_instruction(&set.into()).program_result,
+        custom_error(DropsetError::CompactionInProgress)
+    );
+    let revoke = market.revoke_seat_delegate(*owner, *seat_index);
+    assert_eq!(
+        mollusk.process_instruction(&revoke.into()).program_result,
+        custom_error(DropsetError::CompactionInProgress)
+    );
+
+    assert!(mollusk.process_instruction(&compact).program_result.is_ok());
+    let view = market_view(mollusk, market)?;
+    assert_eq!(view.header.compaction_phase, CompactionPhase::Idle);
+    assert_eq!(view.header.num_free_sectors, 0);
+    let data_len = mollusk
+        .account_store
+        .borrow()
+        .get(&market.market)
+        .map_or(0, |account| account.data.len());
+    assert_eq!(data_len, MarketHeader::LEN + 3 * SECTOR_SIZE);
+
+    // The seat still points to its delegate after the market was truncated, so the delegate can
+    // still cancel the owner's ask.
+    assert_eq!(view.users[owner].seat.delegate, *delegate);
+    let unpause = market.update_market_status(authority, MarketStatus::Active);
+    let cancel = market.cancel_order(
+        *delegate,
+        CancelOrderInstructionData::new(view.users[owner].asks[0].index, false, *seat_index, 0),
+    );
+    assert!(mollusk
+        .process_instruction_chain(&[unpause.into(), cancel.into()])
+        .program_result
+        .is_ok());
+    assert!(market_view(mollusk, market)?.asks.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        MarketParams,
+        PostOrderInstructionData,
+        SeatMarketOrderInstructionData,
+        SelfTradePrevention,
+        TimeInForce,
+        UpdateMarketParamsInstructionData,
+    },
+    state::sector::NIL,
+};
+use mollusk_svm::result::ProgramResult;
+use price::{
+    OrderInfoArgs,
+    ENCODED_PRICE_INFINITY,
+    ENCODED_PRICE_ZERO,
+};
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const TAKER_FEE_BPS: u16 = 10;
+const MAKER_REBATE_BPS: u16 = 5;
+const MAKER_PRICE: u32 = 10_000_000;
+const MAKER_SIZE: u64 = 10;
+const QUOTE_FILLED: u64 = MAKER_SIZE * MAKER_PRICE as u64;
+const TAKER_FEE: u64 = QUOTE_FILLED * TAKER_FEE_BPS as u64 / 10_000;
+const MAKER_REBATE: u64 = QUOTE_FILLED * MAKER_REBATE_BPS as u64 / 10_000;
+
+/// Sets up the default market with fees, a maker resting a [`MAKER_SIZE`] base order at
+/// [`MAKER_PRICE`] on the `maker_is_bid` side of the book and a funded taker with a seat.
+fn setup(
+    maker_is_bid: bool,
+    taker_base: u64,
+    taker_quote: u64,
+) -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address)> {
+    let maker = Address::new_unique();
+    let taker = Address::new_unique();
+    let (mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (maker, funded_system_account()),
+        (taker, funded_system_account()),
+    ]);
+
+    let set_fees = market.update_market_params(
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+        UpdateMarketParamsInstructionData::new(MarketParams {
+            fee_authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            taker_fee_bps: TAKER_FEE_BPS,
+            maker_rebate_bps: MAKER_REBATE_BPS,
+            ..Default::default()
+        }),
+    );
+    let maker_order = market.post_order(
+        maker,
+        PostOrderInstructionData::new(
+            OrderInfoArgs::new_unscaled(MAKER_PRICE, MAKER_SIZE),
+            maker_is_bid,
+            NIL,
+            0,
+            0,
+        ),
+    );
+
+    let instructions = [
+        vec![set_fees.into()],
+        fund_and_deposit_instructions(&market, maker, 100, 1_000_000_000)?,
+        vec![maker_order.into()],
+        fund_and_deposit_instructions(&market, taker, taker_base, taker_quote)?,
+    ]
+    .concat();
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    Ok((mollusk, market, taker))
+}
+
+/// A seat market order for [`MAKER_SIZE`] base with no limit price.
+fn seat_market_order(
+    market: &MarketContext,
+    taker: Address,
+    is_buy: bool,
+    min_output: u64,
+) -> Instruction {
+    let limit_price = if is_buy {
+        ENCODED_PRICE_INFINITY
+    } else {
+        ENCODED_PRICE_ZERO
+    };
+    market
+        .seat_market_order(
+            taker,
+            SeatMarketOrderInstructionData::new(
+                MAKER_SIZE,
+                is_buy,
+                true,
+                limit_price,
+                min_output,
+                TimeInForce::ImmediateOrCancel,
+                SelfTradePrevention::CancelTaking,
+                NIL,
+            ),
+        )
+        .into()
+}
+
+#[test]
+fn seat_market_buy_pays_taker_fee() -> anyhow::Result<()> {
+    const TAKER_QUOTE: u64 = 200_000_000;
+    let (mollusk, market, taker) = setup(false, 0, TAKER_QUOTE)?;
+
+    let buy = seat_market_order(&market, taker, true, MAKER_SIZE);
+    assert!(mollusk.process_instruction(&buy).program_result.is_ok());
+
+    // The taker pays the quote filled plus the taker fee from their seat and receives the base.
+    let view = market_view(&mollusk, &market)?;
+    let seat = &view.users[&taker].seat;
+    assert!(view.asks.is_empty());
+    assert_eq!(seat.base_available, MAKER_SIZE);
+    assert_eq!(seat.quote_available, TAKER_QUOTE - QUOTE_FILLED - TAKER_FEE);
+    assert_eq!(view.header.protocol_fees, TAKER_FEE - MAKER_REBATE);
+
+    Ok(())
+}
+
+#[test]
+fn seat_market_sell_deducts_taker_fee() -> anyhow::Result<()> {
+    let (mollusk, market, taker) = setup(true, MAKER_SIZE, 0)?;
+
+    // The minimum output is exactly the quote filled minus the taker fee.
+    let sell = seat_market_order(&market, taker, false, QUOTE_FILLED - TAKER_FEE);
+    assert!(mollusk.process_instruction(&sell).program_result.is_ok());
+
+    let view = market_view(&mollusk, &market)?;
+    let seat = &view.users[&taker].seat;
+    assert!(view.bids.is_empty());
+    assert_eq!(seat.base_available, 0);
+    assert_eq!(seat.quote_available, QUOTE_FILLED - TAKER_FEE);
+    assert_eq!(view.header.protocol_fees, TAKER_FEE - MAKER_REBATE);
+
+    Ok(())
+}
+
+#[test]
+fn seat_market_order_minimum_output_not_met() -> anyhow::Result<()> {
+    let (mollusk, market, taker) = setup(true, MAKER_SIZE, 0)?;
+
+    // The taker fee is deducted before checking the minimum output, so a sell requiring a single
+    // quote atom more than the quote filled minus the fee fails.
+    let sell = seat_market_order(&market, taker, false, QUOTE_FILLED - TAKER_FEE + 1);
+    assert_eq!(
+        mollusk.process_instruction(&sell).program_result,
+        ProgramResult::Failure(ProgramError::Custom(
+            DropsetError::MinimumOutputNotMet as u32
+        ))
+    );
+
+    Ok(())
+}
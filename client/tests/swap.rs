@@ -0,0 +1,256 @@
+use client::{
+    context::market::MarketContext,
+    mollusk_helpers::{
+        fund_and_deposit_instructions,
+        funded_system_account,
+        market_view,
+        new_dropset_mollusk_context_with_default_market,
+        token_balance,
+        DropsetMolluskContext,
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+    },
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::{
+        MarketParams,
+        PostOrderInstructionData,
+        SwapInstructionData,
+        UpdateMarketParamsInstructionData,
+    },
+    matching::{
+        simulate_swap,
+        SwapQuote,
+    },
+    state::{
+        market::MarketRef,
+        sector::NIL,
+    },
+};
+use mollusk_svm::result::ProgramResult;
+use price::OrderInfoArgs;
+use solana_address::Address;
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+
+const TAKER_BALANCE: u64 = 1_000_000_000;
+const LEVEL_SIZE: u64 = 10;
+const EXPIRY: u64 = 50;
+const NOW: u64 = 100;
+/// The ask price mantissas, from the best price to the worst.
+const ASK_PRICES: [u32; 3] = [10_000_000, 11_000_000, 12_000_000];
+/// The bid price mantissas, from the best price to the worst.
+const BID_PRICES: [u32; 3] = [9_000_000, 8_000_000, 7_000_000];
+/// The price mantissas of orders priced better than the rest of their side of the book, which have
+/// expired by the time the taker swaps.
+const EXPIRED_ASK_PRICE: u32 = 9_500_000;
+const EXPIRED_BID_PRICE: u32 = 9_400_000;
+
+/// Sets up the default market with fees, two makers resting several price levels on both sides of
+/// the book, an expired order at the top of each side and a taker holding [`TAKER_BALANCE`] of
+/// both tokens in their token accounts.
+fn setup() -> anyhow::Result<(DropsetMolluskContext, MarketContext, Address)> {
+    let makers = [Address::new_unique(), Address::new_unique()];
+    let taker = Address::new_unique();
+    let (mut mollusk, market) = new_dropset_mollusk_context_with_default_market(vec![
+        (makers[0], funded_system_account()),
+        (makers[1], funded_system_account()),
+        (taker, funded_system_account()),
+    ]);
+
+    let set_fees = market.update_market_params(
+        MOLLUSK_DEFAULT_MINT_AUTHORITY,
+        UpdateMarketParamsInstructionData::new(MarketParams {
+            fee_authority: MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            taker_fee_bps: 10,
+            maker_rebate_bps: 5,
+            ..Default::default()
+        }),
+    );
+    let post = |maker: Address, price: u32, is_bid: bool, expiry: u64| -> Instruction {
+        market
+            .post_order(
+                maker,
+                PostOrderInstructionData::new(
+                    OrderInfoArgs::new_unscaled(price, LEVEL_SIZE),
+                    is_bid,
+                    NIL,
+                    expiry,
+                    0,
+                ),
+            )
+            .into()
+    };
+
+    let mut instructions = vec![set_fees.into()];
+    for maker in makers {
+        instructions.extend(fund_and_deposit_instructions(
+            &market,
+            maker,
+            100,
+            1_000_000_000,
+        )?);
+    }
+    // Alternate the makers so that each side's fills touch both of them.
+    for (i, (ask_price, bid_price)) in ASK_PRICES.into_iter().zip(BID_PRICES).enumerate() {
+        instructions.push(post(makers[i % 2], ask_price, false, 0));
+        instructions.push(post(makers[(i + 1) % 2], bid_price, true, 0));
+    }
+    instructions.push(post(makers[0], EXPIRED_ASK_PRICE, false, EXPIRY));
+    instructions.push(post(makers[1], EXPIRED_BID_PRICE, true, EXPIRY));
+    instructions.extend([
+        market.base.create_ata(&taker, &taker),
+        market.quote.create_ata(&taker, &taker),
+        market.base.mint_to(
+            &MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            &market.get_base_ata(&taker),
+            TAKER_BALANCE,
+        )?,
+        market.quote.mint_to(
+            &MOLLUSK_DEFAULT_MINT_AUTHORITY,
+            &market.get_quote_ata(&taker),
+            TAKER_BALANCE,
+        )?,
+    ]);
+    assert!(mollusk
+        .process_instruction_chain(&instructions)
+        .program_result
+        .is_ok());
+
+    mollusk.mollusk.sysvars.clock.unix_timestamp = NOW as i64;
+
+    Ok((mollusk, market, taker))
+}
+
+fn custom_error(error: DropsetError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(error as u32))
+}
+
+/// Quotes a swap against the market's current order book with [`simulate_swap`].
+fn quote_swap(
+    mollusk: &DropsetMolluskContext,
+    market: &MarketContext,
+    taker: &Address,
+    is_buy: bool,
+    is_exact_out: bool,
+    amount: u64,
+) -> anyhow::Result<SwapQuote> {
+    let store = mollusk.account_store.borrow();
+    let data = &store
+        .get(&market.market)
+        .expect("Market account should exist")
+        .data;
+    // Safety: The market account was initialized by the program.
+    let market_ref = unsafe { MarketRef::from_bytes(data) };
+    Ok(simulate_swap(
+        &market_ref,
+        taker,
+        amount,
+        is_buy,
+        is_exact_out,
+        NOW,
+    )?)
+}
+
+/// Quotes a swap with [`simulate_swap`], executes it and checks that the taker's token balances
+/// change by exactly the quoted amounts.
+fn simulate_and_swap(is_buy: bool, is_exact_out: bool, amount: u64) -> anyhow::Result<()> {
+    let (mollusk, market, taker) = setup()?;
+
+    let quote = quote_swap(&mollusk, &market, &taker, is_buy, is_exact_out, amount)?;
+    let SwapQuote {
+        amount_in,
+        amount_out,
+        taker_fee,
+        is_fully_filled,
+    } = quote;
+    assert!(amount_in > 0 && amount_out > 0 && taker_fee > 0);
+    // Every swap below is larger than the first price level, so it must skip the expired order and
+    // fill more than one level.
+    assert!(is_fully_filled);
+
+    let (in_ata, out_ata) = if is_buy {
+        (market.get_quote_ata(&taker), market.get_base_ata(&taker))
+    } else {
+        (market.get_base_ata(&taker), market.get_quote_ata(&taker))
+    };
+    let view_before = market_view(&mollusk, &market)?;
+
+    let swap = market.swap(
+        taker,
+        SwapInstructionData::new(amount, is_buy, is_exact_out, amount_out, amount_in),
+    );
+    assert!(mollusk
+        .process_instruction(&swap.into())
+        .program_result
+        .is_ok());
+
+    assert_eq!(token_balance(&mollusk, &in_ata), TAKER_BALANCE - amount_in);
+    assert_eq!(
+        token_balance(&mollusk, &out_ata),
+        TAKER_BALANCE + amount_out
+    );
+
+    // The taker fee minus the makers' rebates accrues to the protocol, so it can only grow by at
+    // most the taker fee.
+    let view_after = market_view(&mollusk, &market)?;
+    let protocol_fees_accrued = view_after.header.protocol_fees - view_before.header.protocol_fees;
+    assert!(protocol_fees_accrued > 0 && protocol_fees_accrued <= taker_fee);
+
+    // The expired order was removed rather than filled, and the best remaining level on the side
+    // that was taken from was partially filled.
+    let (orders_before, orders_after) = if is_buy {
+        (view_before.asks, view_after.asks)
+    } else {
+        (view_before.bids, view_after.bids)
+    };
+    assert_eq!(orders_after.len(), orders_before.len() - 2);
+    assert!(orders_after[0].base_remaining < LEVEL_SIZE);
+
+    Ok(())
+}
+
+#[test]
+fn swap_buy_exact_in_matches_simulation() -> anyhow::Result<()> {
+    simulate_and_swap(true, false, 15 * ASK_PRICES[0] as u64)
+}
+
+#[test]
+fn swap_buy_exact_out_matches_simulation() -> anyhow::Result<()> {
+    simulate_and_swap(true, true, 15)
+}
+
+#[test]
+fn swap_sell_exact_in_matches_simulation() -> anyhow::Result<()> {
+    simulate_and_swap(false, false, 15)
+}
+
+#[test]
+fn swap_sell_exact_out_matches_simulation() -> anyhow::Result<()> {
+    simulate_and_swap(false, true, 15 * BID_PRICES[0] as u64)
+}
+
+#[test]
+fn swap_exact_out_checks_max_input() -> anyhow::Result<()> {
+    for is_buy in [true, false] {
+        let (mollusk, market, taker) = setup()?;
+        let amount = if is_buy { 15 } else { 15 * BID_PRICES[0] as u64 };
+        let SwapQuote {
+            amount_in,
+            amount_out,
+            ..
+        } = quote_swap(&mollusk, &market, &taker, is_buy, true, amount)?;
+
+        // The book fills the exact output for more than the taker is willing to pay.
+        let swap = market.swap(
+            taker,
+            SwapInstructionData::new(amount, is_buy, true, amount_out, amount_in - 1),
+        );
+        assert_eq!(
+            mollusk.process_instruction(&swap.into()).program_result,
+            custom_error(DropsetError::MaximumInputExceeded)
+        );
+    }
+
+    Ok(())
+}
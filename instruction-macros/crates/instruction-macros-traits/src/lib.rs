@@ -2,8 +2,15 @@
 
 mod pack;
 mod tagged;
+mod u8_enum;
 mod unpack;
 
 pub use pack::Pack;
 pub use tagged::Tagged;
 pub use unpack::Unpack;
+
+/// Re-exports used by [`u8_enum`]'s expansion so callers don't need to depend on them directly.
+#[doc(hidden)]
+pub mod __private {
+    pub use solana_program_error::ProgramError;
+}
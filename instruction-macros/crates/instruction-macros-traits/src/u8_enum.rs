@@ -0,0 +1,94 @@
+//! A declarative macro for fieldless `u8` enums passed as instruction arguments or stored in
+//! account data.
+
+/// Defines a fieldless `#[repr(u8)]` enum and implements `TryFrom<u8>`, [`Pack`](crate::Pack) and
+/// [`Unpack`](crate::Unpack) for it.
+///
+/// Every variant must have an explicit discriminant. Unpacking any other byte fails with
+/// `ProgramError::InvalidInstructionData`.
+///
+/// ## Example
+/// ```rust,ignore
+/// u8_enum! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub enum Side {
+///         Bid = 0,
+///         Ask = 1,
+///     }
+/// }
+///
+/// assert_eq!(Side::unpack(&Side::Ask.pack()), Ok(Side::Ask));
+/// assert!(Side::try_from(2).is_err());
+/// ```
+#[macro_export]
+macro_rules! u8_enum {
+    (
+        $(#[$enum_meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $discriminant:literal
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$enum_meta])*
+        #[repr(u8)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant = $discriminant,
+            )+
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = $crate::__private::ProgramError;
+
+            #[inline(always)]
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                match value {
+                    $($discriminant => Ok(Self::$variant),)+
+                    _ => Err($crate::__private::ProgramError::InvalidInstructionData),
+                }
+            }
+        }
+
+        /// # Safety
+        ///
+        /// Writes exactly 1 byte to `dst`.
+        unsafe impl $crate::Pack for $name {
+            type Packed = [u8; 1];
+
+            #[inline(always)]
+            unsafe fn write_bytes(&self, dst: *mut u8) {
+                dst.write(*self as u8)
+            }
+
+            #[inline(always)]
+            fn pack(&self) -> Self::Packed {
+                [*self as u8]
+            }
+        }
+
+        /// # Safety
+        ///
+        /// Reads exactly 1 byte from `src` and fails if the byte isn't a valid discriminant.
+        unsafe impl $crate::Unpack for $name {
+            #[inline(always)]
+            unsafe fn read_bytes(
+                src: *const u8,
+            ) -> Result<Self, $crate::__private::ProgramError> {
+                <Self as TryFrom<u8>>::try_from(src.read())
+            }
+
+            #[inline(always)]
+            fn unpack(data: &[u8]) -> Result<Self, $crate::__private::ProgramError> {
+                if data.is_empty() {
+                    return Err($crate::__private::ProgramError::InvalidInstructionData);
+                }
+
+                // Safety: `data` has at least 1 byte.
+                unsafe { <Self as $crate::Unpack>::read_bytes(data.as_ptr()) }
+            }
+        }
+    };
+}
@@ -2,6 +2,7 @@
 
 pub use instruction_macros_derive::*;
 pub use instruction_macros_traits::{
+    u8_enum,
     Pack,
     Tagged,
     Unpack,
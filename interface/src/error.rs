@@ -35,7 +35,6 @@ pub enum DropsetError {
     InvalidInstructionData,
     IncorrectEventAuthority,
     EventAuthorityMustBeSigner,
-    OrderWithPriceAlreadyExists,
     UserHasMaxOrders,
     OrderNotFound,
     ArithmeticUnderflow,
@@ -45,6 +44,40 @@ pub enum DropsetError {
     InfinityIsNotAFloat,
     PostOnlyWouldImmediatelyFill,
     AmountFilledVsTransferredMismatch,
+    BatchTooLarge,
+    MinimumOutputNotMet,
+    FillOrKillNotFilled,
+    InvalidFeeRates,
+    IncorrectFeeAuthority,
+    AuthorityMustBeSigner,
+    IncorrectMarketAuthority,
+    MarketNotActive,
+    MarketPaused,
+    MarketClosed,
+    InvalidMarketSizes,
+    PriceNotMultipleOfTickSize,
+    SizeNotMultipleOfLotSize,
+    OrderSizeBelowMinimum,
+    SelfTradeAborted,
+    OrderAlreadyExpired,
+    InvalidMaxOrders,
+    AmendPriceMismatch,
+    SeatNotFound,
+    MarketNotEmpty,
+    UserMustBeSigner,
+    DelegateCannotTake,
+    TraderNotAllowlisted,
+    MarketNotPermissioned,
+    PermissionedMarketRequiresAuthority,
+    InvalidOracleBand,
+    IncorrectOracle,
+    InvalidOracleAccount,
+    OraclePriceStale,
+    PriceOutsideOracleBand,
+    MarketNotPaused,
+    CompactionInProgress,
+    ProtocolFeesNotSwept,
+    MaximumInputExceeded,
 }
 
 impl From<DropsetError> for ProgramError {
@@ -99,7 +132,6 @@ impl From<DropsetError> for &'static str {
             DropsetError::InvalidInstructionData => "Instruction data is invalid",
             DropsetError::IncorrectEventAuthority => "The event authority passed isn't correct",
             DropsetError::EventAuthorityMustBeSigner => "The event authority isn't a signer",
-            DropsetError::OrderWithPriceAlreadyExists => "An order with this price already exists",
             DropsetError::UserHasMaxOrders => "User already has the max number of open orders",
             DropsetError::OrderNotFound => "Order not found",
             DropsetError::ArithmeticUnderflow => "Arithmetic underflow",
@@ -111,6 +143,42 @@ impl From<DropsetError> for &'static str {
             DropsetError::AmountFilledVsTransferredMismatch => {
                 "The amount filled doesn't match the amount transferred."
             }
+            DropsetError::BatchTooLarge => "Batch exceeds the max number of items",
+            DropsetError::MinimumOutputNotMet => "Amount received is below the requested minimum",
+            DropsetError::FillOrKillNotFilled => "Fill-or-kill order couldn't be filled entirely",
+            DropsetError::InvalidFeeRates => "Invalid taker fee or maker rebate rate",
+            DropsetError::IncorrectFeeAuthority => "Incorrect fee authority",
+            DropsetError::AuthorityMustBeSigner => "Authority must be a signer",
+            DropsetError::IncorrectMarketAuthority => "Incorrect market authority",
+            DropsetError::MarketNotActive => "Market isn't active",
+            DropsetError::MarketPaused => "Market is paused",
+            DropsetError::MarketClosed => "Market is closed",
+            DropsetError::InvalidMarketSizes => "Invalid tick size or base lot size",
+            DropsetError::PriceNotMultipleOfTickSize => "Price isn't a multiple of the tick size",
+            DropsetError::SizeNotMultipleOfLotSize => "Order size isn't a multiple of the lot size",
+            DropsetError::OrderSizeBelowMinimum => "Order size is below the market minimum",
+            DropsetError::SelfTradeAborted => "Order would match against the user's own order",
+            DropsetError::OrderAlreadyExpired => "Order expiry must be in the future",
+            DropsetError::InvalidMaxOrders => "Invalid max number of orders per side",
+            DropsetError::AmendPriceMismatch => "Amended order price must match the order's price",
+            DropsetError::SeatNotFound => "User does not have a seat on this market",
+            DropsetError::MarketNotEmpty => "Market still has seats or orders",
+            DropsetError::UserMustBeSigner => "User or seat delegate must be a signer",
+            DropsetError::DelegateCannotTake => "Seat delegate isn't allowed to take liquidity",
+            DropsetError::TraderNotAllowlisted => "Trader isn't on the market's allowlist",
+            DropsetError::MarketNotPermissioned => "Market doesn't have a trader allowlist",
+            DropsetError::PermissionedMarketRequiresAuthority => {
+                "Permissioned market must have a market authority"
+            }
+            DropsetError::InvalidOracleBand => "Invalid oracle price band or max staleness",
+            DropsetError::IncorrectOracle => "Oracle account doesn't match the market's oracle",
+            DropsetError::InvalidOracleAccount => "Oracle account data isn't a valid oracle price",
+            DropsetError::OraclePriceStale => "Market's cached oracle price is stale",
+            DropsetError::PriceOutsideOracleBand => "Price is outside the market's oracle band",
+            DropsetError::MarketNotPaused => "Market must be paused",
+            DropsetError::CompactionInProgress => "Market is being compacted",
+            DropsetError::ProtocolFeesNotSwept => "Market still has unswept protocol fees",
+            DropsetError::MaximumInputExceeded => "Amount paid is above the requested maximum",
         }
     }
 }
@@ -9,6 +9,14 @@ mod tests;
 
 use instruction_macros::ProgramInstructionEvent;
 
+use crate::{
+    instructions::{
+        MarketParams,
+        TimeInForce,
+    },
+    state::market_status::MarketStatus,
+};
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, ProgramInstructionEvent)]
 #[cfg_attr(test, derive(strum_macros::FromRepr, strum_macros::EnumIter))]
@@ -35,16 +43,124 @@ pub enum DropsetEventTag {
     #[args(order_sector_index: u32, "The posted order's sector index.")]
     #[args(base_atoms: u64, "The size of the order's base atoms to fill.")]
     #[args(quote_atoms: u64, "The size of the order's quote atoms to fill.")]
+    #[args(client_order_id: u64, "The posted order's client order id. Zero if it has none.")]
     PostOrderEvent,
     #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
     #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
+    #[args(client_order_id: u64, "The cancelled order's client order id. Zero if it has none.")]
     CancelOrderEvent,
     #[args(order_size: u64, "The order size in atoms.")]
     #[args(is_buy: bool, "Whether or not the order is a market buy. If not, it's a market sell.")]
     #[args(is_base: bool, "Whether or not the order size is denominated in base. If not, it's in quote.")]
     #[args(base_filled: u64, "The amount of base atoms filled.")]
     #[args(quote_filled: u64, "The amount of quote atoms filled.")]
+    #[args(time_in_force: TimeInForce, "The time-in-force the order ran with.")]
+    #[args(is_fully_filled: bool, "Whether or not the entire order size was filled.")]
     MarketOrderEvent,
     #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
     CloseSeatEvent,
+    #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
+    #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
+    #[args(base_filled: u64, "The amount of base atoms filled against resting orders.")]
+    #[args(quote_filled: u64, "The amount of quote atoms filled against resting orders.")]
+    #[args(order_sector_index: u32, "The posted remainder's sector index, or `NIL` if nothing was posted.")]
+    #[args(base_posted: u64, "The amount of base atoms posted as a resting order.")]
+    #[args(quote_posted: u64, "The amount of quote atoms posted as a resting order.")]
+    LimitOrderEvent,
+    #[args(maker_seat_sector_index: u32, "The maker's market seat sector index.")]
+    #[args(order_sector_index: u32, "The sector index of the maker order that was filled.")]
+    #[args(encoded_price: u32, "The encoded price of the maker order that was filled.")]
+    #[args(base_filled: u64, "The amount of base atoms filled.")]
+    #[args(quote_filled: u64, "The amount of quote atoms filled.")]
+    #[args(is_order_closed: bool, "Whether or not the maker order was fully filled and closed.")]
+    #[args(taker_fee: u64, "The fee charged to the taker in quote atoms.")]
+    #[args(maker_rebate: u64, "The rebate paid to the maker in quote atoms.")]
+    #[args(client_order_id: u64, "The maker order's client order id. Zero if it has none.")]
+    FillEvent,
+    #[args(amount: u64, "The amount of accrued protocol fees swept in quote atoms.")]
+    SweepFeesEvent,
+    #[args(params: MarketParams, "The market's new parameters.")]
+    UpdateMarketParamsEvent,
+    #[args(previous_authority: Address, "The previous market authority.")]
+    #[args(new_authority: Address, "The new market authority.")]
+    TransferMarketAuthorityEvent,
+    #[args(previous_authority: Address, "The renounced market authority.")]
+    RenounceMarketAuthorityEvent,
+    #[args(previous_status: MarketStatus, "The market's previous status.")]
+    #[args(new_status: MarketStatus, "The market's new status.")]
+    UpdateMarketStatusEvent,
+    #[args(maker_seat_sector_index: u32, "The taker's market seat sector index.")]
+    #[args(order_sector_index: u32, "The sector index of the taker's resting order that was cancelled.")]
+    #[args(encoded_price: u32, "The encoded price of the resting order that was cancelled.")]
+    #[args(base_cancelled: u64, "The amount of base atoms remaining in the cancelled resting order.")]
+    #[args(quote_cancelled: u64, "The amount of quote atoms remaining in the cancelled resting order.")]
+    SelfTradeCancelRestingEvent,
+    #[args(order_sector_index: u32, "The sector index of the taker's resting order that would have been matched.")]
+    #[args(encoded_price: u32, "The encoded price of the resting order that would have been matched.")]
+    #[args(amount_cancelled: u64, "The taker order's unfilled remainder that was cancelled, in the order size's denomination.")]
+    SelfTradeCancelTakingEvent,
+    #[args(maker_seat_sector_index: u32, "The taker's market seat sector index.")]
+    #[args(order_sector_index: u32, "The sector index of the taker's resting order that was decremented.")]
+    #[args(encoded_price: u32, "The encoded price of the resting order that was decremented.")]
+    #[args(base_decremented: u64, "The amount of base atoms decremented from both orders.")]
+    #[args(quote_decremented: u64, "The amount of quote atoms decremented from both orders.")]
+    #[args(is_order_closed: bool, "Whether or not the resting order was decremented entirely and closed.")]
+    SelfTradeDecrementBothEvent,
+    #[args(is_bid: bool, "Whether or not the expired order was a bid. If false, it was an ask.")]
+    #[args(maker_seat_sector_index: u32, "The maker's market seat sector index.")]
+    #[args(order_sector_index: u32, "The expired order's sector index.")]
+    #[args(encoded_price: u32, "The encoded price of the expired order.")]
+    #[args(base_remaining: u64, "The base atoms remaining in the expired order.")]
+    #[args(quote_remaining: u64, "The quote atoms remaining in the expired order.")]
+    ExpireOrderEvent,
+    #[args(is_bid: bool, "Whether or not the amended order is a bid. If false, it's an ask.")]
+    #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
+    #[args(previous_order_sector_index: u32, "The order's sector index before it was amended.")]
+    #[args(order_sector_index: u32, "The order's sector index after it was amended.")]
+    #[args(base_remaining: u64, "The base atoms remaining in the amended order.")]
+    #[args(quote_remaining: u64, "The quote atoms remaining in the amended order.")]
+    #[args(requeued: bool, "Whether or not the order was moved to the back of its price level.")]
+    #[args(client_order_id: u64, "The amended order's client order id. Zero if it has none.")]
+    AmendOrderEvent,
+    #[args(num_sectors_relocated: u32, "The number of live sectors relocated to new sector indices.")]
+    #[args(is_complete: bool, "Whether or not the compaction is complete. Sectors are only truncated once it is.")]
+    #[args(num_sectors_removed: u32, "The number of free sectors truncated from the market account.")]
+    #[args(lamports_refunded: u64, "The lamports refunded to the market authority.")]
+    CompactMarketEvent,
+    #[args(num_sectors_added: u16, "The number of free sectors added to the market account.")]
+    #[args(num_sectors: u32, "The market account's new total number of sectors.")]
+    ExpandMarketEvent,
+    #[args(closer: Address, "The account that closed the market and received its rent.")]
+    #[args(lamports_refunded: u64, "The market account's lamports refunded to the closer.")]
+    #[args(base_swept: u64, "The base atoms left in the market's token account, sent to the closer.")]
+    #[args(quote_swept: u64, "The quote atoms left in the market's token account, sent to the closer.")]
+    CloseMarketEvent,
+    #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
+    #[args(delegate: Address, "The seat's new delegate.")]
+    #[args(can_take: bool, "Whether or not the delegate may take liquidity with the seat's balances.")]
+    SetSeatDelegateEvent,
+    #[args(user_seat_sector_index: u32, "The user's market seat sector index.")]
+    #[args(previous_delegate: Address, "The revoked delegate.")]
+    RevokeSeatDelegateEvent,
+    #[args(trader: Address, "The allowlisted trader.")]
+    #[args(user_seat_sector_index: u32, "The trader's market seat sector index.")]
+    #[args(registered_seat: bool, "Whether or not an empty seat was registered for the trader.")]
+    AddAllowlistedTraderEvent,
+    #[args(trader: Address, "The trader removed from the allowlist.")]
+    #[args(user_seat_sector_index: u32, "The trader's market seat sector index.")]
+    RemoveAllowlistedTraderEvent,
+    #[args(oracle: Address, "The market's new oracle, or the default address if it was removed.")]
+    #[args(band_bps: u16, "The max distance in basis points from the oracle price for posts and fills.")]
+    #[args(max_staleness_secs: u32, "The max age in seconds of the oracle price.")]
+    SetMarketOracleEvent,
+    #[args(encoded_price: u32, "The oracle's new encoded price.")]
+    #[args(publish_time: u64, "The unix timestamp in seconds at which the price was published.")]
+    UpdateOraclePriceEvent,
+    #[args(is_bid: bool, "Whether or not the removed order was a bid. If false, it was an ask.")]
+    #[args(maker_seat_sector_index: u32, "The maker's market seat sector index.")]
+    #[args(order_sector_index: u32, "The removed order's sector index.")]
+    #[args(encoded_price: u32, "The encoded price of the removed order, outside the market's oracle band.")]
+    #[args(base_remaining: u64, "The base atoms remaining in the removed order.")]
+    #[args(quote_remaining: u64, "The quote atoms remaining in the removed order.")]
+    RemoveOutOfBandOrderEvent,
 }
@@ -7,8 +7,20 @@
 //! The `program` feature: [`crate::instructions::generated_program`]
 //! The `client` feature: [`crate::instructions::generated_client`]
 
+mod batch_replace;
+mod market_params;
+mod self_trade_prevention;
+mod swap;
+mod time_in_force;
+
+pub use batch_replace::*;
 use instruction_macros::ProgramInstruction;
+pub use market_params::*;
 use price::OrderInfoArgs;
+pub use self_trade_prevention::*;
+pub use time_in_force::*;
+
+use crate::state::market_status::MarketStatus;
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, ProgramInstruction)]
@@ -29,7 +41,7 @@ pub enum DropsetInstruction {
     #[account(9,           name = "base_token_program",   desc = "The base mint's token program.")]
     #[account(10,          name = "quote_token_program",  desc = "The quote mint's token program.")]
     #[account(11,          name = "dropset_program",      desc = "The dropset program itself, used for the self-CPI.")]
-    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in.")]
+    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
     CloseSeat,
 
     #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
@@ -41,7 +53,7 @@ pub enum DropsetInstruction {
     #[account(6,           name = "token_program",   desc = "The mint's token program.")]
     #[account(7,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
     #[args(amount: u64, "The amount to deposit.")]
-    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in (pass `NIL` when registering a new seat).")]
+    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in (pass `NIL` to find the seat by address, registering a new seat if the user has none and the market isn't permissioned).")]
     Deposit,
 
     #[account(0,           name = "event_authority",     desc = "The event authority PDA signer.")]
@@ -57,6 +69,9 @@ pub enum DropsetInstruction {
     #[account(10,          name = "system_program",      desc = "The system program.")]
     #[account(11,          name = "dropset_program",     desc = "The dropset program itself, used for the self-CPI.")]
     #[args(num_sectors: u16, "The number of sectors to preallocate for the market.")]
+    #[args(authority: Address, "The market authority. Pass `NO_MARKET_AUTHORITY` for a market without one.")]
+    #[args(is_permissioned: bool, "Whether or not only traders allowlisted by the market authority can register seats and take liquidity.")]
+    #[args(params: MarketParams, "The market's initial parameters.")]
     RegisterMarket,
 
     #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
@@ -68,31 +83,37 @@ pub enum DropsetInstruction {
     #[account(6,           name = "token_program",   desc = "The mint's token program.")]
     #[account(7,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
     #[args(amount: u64, "The amount to withdraw.")]
-    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in.")]
+    #[args(sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
     Withdraw,
 
     #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
-    #[account(1, signer,   name = "user",            desc = "The user posting an order.")]
+    #[account(1, signer,   name = "user",            desc = "The user posting an order, or their seat delegate.")]
     #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
     #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
     #[args(order_info_args: OrderInfoArgs, "The order info arguments.")]
     #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
-    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    #[args(expiry: u64, "The unix timestamp in seconds at which the order expires. Zero means it never expires.")]
+    #[args(client_order_id: u64, "The user's own identifier for the order. Zero means it has none.")]
     PostOrder,
 
     #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
-    #[account(1, signer,   name = "user",            desc = "The user canceling an order.")]
+    #[account(1, signer,   name = "user",            desc = "The user canceling an order, or their seat delegate.")]
     #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
     #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
-    #[args(encoded_price: u32, "The encoded price for the order to cancel.")]
+    #[args(order_sector_index: u32, "The sector index of the order to cancel. Ignored if cancelling by client order id.")]
     #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
-    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    #[args(client_order_id: u64, "If non-zero, the client order id of the order to cancel instead of the sector index.")]
     CancelOrder,
 
     #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
-    #[account(1, signer,   name = "user",            desc = "The user posting an order.")]
+    #[account(1, signer,   name = "user",            desc = "The user replacing their orders, or their seat delegate.")]
     #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
     #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(cancels: BatchCancels, "The orders to cancel. Cancels are applied before any posts.")]
+    #[args(posts: BatchPosts, "The orders to post after all cancels have been applied.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
     BatchReplace,
 
     #[account(0,           name = "event_authority",     desc = "The event authority PDA signer.")]
@@ -110,8 +131,194 @@ pub enum DropsetInstruction {
     #[args(order_size: u64, "The order size; aka the number of atoms to fill.")]
     #[args(is_buy: bool, "Whether or not the order is a market buy. If not, it's a market sell.")]
     #[args(is_base: bool, "Whether or not the order size is denominated in base. If not, it's in quote.")]
+    #[args(limit_price: u32, "The worst encoded price to fill at. Matching stops at the first order with a worse price.")]
+    #[args(min_output: u64, "The minimum amount the taker must receive: base atoms for a buy, quote atoms for a sell.")]
+    #[args(time_in_force: TimeInForce, "Whether the order is immediate-or-cancel or fill-or-kill.")]
+    #[args(self_trade_prevention: SelfTradePrevention, "What to do when the order would match against the user's own resting order.")]
     MarketOrder,
 
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The user placing a limit order, or their seat delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(order_info_args: OrderInfoArgs, "The order info arguments. The order's price is the limit price.")]
+    #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    #[args(self_trade_prevention: SelfTradePrevention, "What to do when the order would match against the user's own resting order.")]
+    #[args(expiry: u64, "The unix timestamp in seconds at which the posted remainder expires. Zero means it never expires.")]
+    #[args(client_order_id: u64, "The user's own identifier for the posted remainder. Zero means it has none.")]
+    LimitOrder,
+
+    #[account(0,           name = "event_authority",     desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "fee_authority",       desc = "The market's fee authority.")]
+    #[account(2, writable, name = "market_account",      desc = "The market account PDA.")]
+    #[account(3, writable, name = "fee_recipient_ata",   desc = "The fee authority's associated quote token account.")]
+    #[account(4, writable, name = "quote_market_ata",    desc = "The market's associated quote token account.")]
+    #[account(5,           name = "quote_mint",          desc = "The quote token mint account.")]
+    #[account(6,           name = "quote_token_program", desc = "The quote mint's token program.")]
+    #[account(7,           name = "dropset_program",     desc = "The dropset program itself, used for the self-CPI.")]
+    SweepFees,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(params: MarketParams, "The market's new parameters.")]
+    UpdateMarketParams,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The current market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(new_authority: Address, "The new market authority.")]
+    TransferMarketAuthority,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The current market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    RenounceMarketAuthority,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(status: MarketStatus, "The market's new status.")]
+    UpdateMarketStatus,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The user canceling their orders, or their seat delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(cancel_bids: bool, "Whether or not to cancel all of the user's bids.")]
+    #[args(cancel_asks: bool, "Whether or not to cancel all of the user's asks.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    CancelAllOrders,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(2,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(max_orders: u16, "The max number of expired orders to remove from the book.")]
+    PruneExpiredOrders,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The user amending an order, or their seat delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(order_sector_index: u32, "The sector index of the order to amend.")]
+    #[args(is_bid: bool, "Whether or not the order is a bid. If false, the order is an ask.")]
+    #[args(order_info_args: OrderInfoArgs, "The order info arguments for the order's new size. The price must match the order's price.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    AmendOrder,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The user creating the market order, aka the taker, or their seat delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(order_size: u64, "The order size; aka the number of atoms to fill.")]
+    #[args(is_buy: bool, "Whether or not the order is a market buy. If not, it's a market sell.")]
+    #[args(is_base: bool, "Whether or not the order size is denominated in base. If not, it's in quote.")]
+    #[args(limit_price: u32, "The worst encoded price to fill at. Matching stops at the first order with a worse price.")]
+    #[args(min_output: u64, "The minimum amount the taker must receive: base atoms for a buy, quote atoms for a sell.")]
+    #[args(time_in_force: TimeInForce, "Whether the order is immediate-or-cancel or fill-or-kill.")]
+    #[args(self_trade_prevention: SelfTradePrevention, "What to do when the order would match against the user's own resting order.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    SeatMarketOrder,
+
+    #[account(0,                   name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer, writable, name = "authority",       desc = "The market authority, refunded the market account's excess rent.")]
+    #[account(2, writable,         name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,                   name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(max_sectors: u16, "The max number of live sectors to visit, bounding the compute used. The compaction resumes where it left off in the next call.")]
+    CompactMarket,
+
+    #[account(0,                   name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer, writable, name = "payer",           desc = "The account paying rent for the market account's new sectors.")]
+    #[account(2, writable,         name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,                   name = "system_program",  desc = "The system program.")]
+    #[account(4,                   name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(num_sectors: u16, "The number of sectors to add to the market.")]
+    ExpandMarket,
+
+    #[account(0,                   name = "event_authority",     desc = "The event authority PDA signer.")]
+    #[account(1, signer, writable, name = "closer",              desc = "The market authority, or anyone if the market has no authority. Receives the market's rent.")]
+    #[account(2, writable,         name = "market_account",      desc = "The market account PDA.")]
+    #[account(3, writable,         name = "base_market_ata",     desc = "The market's associated base token account.")]
+    #[account(4, writable,         name = "quote_market_ata",    desc = "The market's associated quote token account.")]
+    #[account(5, writable,         name = "closer_base_ata",     desc = "The closer's base token account, which receives any base tokens left in the market.")]
+    #[account(6, writable,         name = "closer_quote_ata",    desc = "The closer's quote token account, which receives any quote tokens left in the market.")]
+    #[account(7,                   name = "base_mint",           desc = "The base token mint account.")]
+    #[account(8,                   name = "quote_mint",          desc = "The quote token mint account.")]
+    #[account(9,                   name = "base_token_program",  desc = "The base mint's token program.")]
+    #[account(10,                  name = "quote_token_program", desc = "The quote mint's token program.")]
+    #[account(11,                  name = "dropset_program",     desc = "The dropset program itself, used for the self-CPI.")]
+    CloseMarket,
+
+    #[account(0,           name = "event_authority",     desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",                desc = "The user swapping, aka the taker.")]
+    #[account(2, writable, name = "market_account",      desc = "The market account PDA.")]
+    #[account(3, writable, name = "base_user_ata",       desc = "The user's associated base mint token account.")]
+    #[account(4, writable, name = "quote_user_ata",      desc = "The user's associated quote mint token account.")]
+    #[account(5, writable, name = "base_market_ata",     desc = "The market's associated base mint token account.")]
+    #[account(6, writable, name = "quote_market_ata",    desc = "The market's associated quote mint token account.")]
+    #[account(7,           name = "base_mint",           desc = "The base token mint account.")]
+    #[account(8,           name = "quote_mint",          desc = "The quote token mint account.")]
+    #[account(9,           name = "base_token_program",  desc = "The base mint's token program.")]
+    #[account(10,          name = "quote_token_program", desc = "The quote mint's token program.")]
+    #[account(11,          name = "dropset_program",     desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(amount: u64, "The swap size, before fees: the input amount if exact-in, otherwise the output amount.")]
+    #[args(is_buy: bool, "Whether or not the swap buys base with quote. If not, it sells base for quote.")]
+    #[args(is_exact_out: bool, "Whether or not `amount` is the output amount. If not, it's the input amount.")]
+    #[args(min_output: u64, "The minimum amount the taker must receive: base atoms for a buy, quote atoms for a sell.")]
+    #[args(max_input: u64, "The maximum amount the taker may pay, including fees: quote atoms for a buy, base atoms for a sell.")]
+    Swap,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The seat's user setting their delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(delegate: Address, "The address allowed to post, amend and cancel orders for the seat.")]
+    #[args(can_take: bool, "Whether or not the delegate may also take liquidity with the seat's balances.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    SetSeatDelegate,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "user",            desc = "The seat's user revoking their delegate.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(user_sector_index_hint: u32, "A hint indicating which sector the user's seat resides in. Pass `NIL` to find the seat by address.")]
+    RevokeSeatDelegate,
+
+    #[account(0,                   name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer, writable, name = "authority",       desc = "The market authority, paying rent for the trader's seat if it needs a new sector.")]
+    #[account(2, writable,         name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,                   name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(trader: Address, "The trader to allowlist. Registers an empty seat for them if they don't have one.")]
+    AddAllowlistedTrader,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(trader: Address, "The trader to remove from the allowlist.")]
+    #[args(sector_index_hint: u32, "A hint indicating which sector the trader's seat resides in. Pass `NIL` to find the seat by address.")]
+    RemoveAllowlistedTrader,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, signer,   name = "authority",       desc = "The market authority.")]
+    #[account(2, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    #[args(oracle: Address, "The oracle account the market's price bands are centered on. Pass the default address to remove the oracle.")]
+    #[args(band_bps: u16, "The max distance in basis points from the oracle price that orders can be posted or filled at.")]
+    #[args(max_staleness_secs: u32, "The max age in seconds of the oracle price before posts and fills are rejected.")]
+    SetMarketOracle,
+
+    #[account(0,           name = "event_authority", desc = "The event authority PDA signer.")]
+    #[account(1, writable, name = "market_account",  desc = "The market account PDA.")]
+    #[account(2,           name = "oracle",          desc = "The market's oracle account.")]
+    #[account(3,           name = "dropset_program", desc = "The dropset program itself, used for the self-CPI.")]
+    UpdateOraclePrice,
+
     // FlushEvents is an internal instruction and can only be called by the program. It does have
     // instruction data, but it is not used by the program.
     #[account(0, signer,   name = "event_authority", desc = "The event authority PDA signer.")]
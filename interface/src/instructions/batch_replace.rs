@@ -0,0 +1,246 @@
+//! Fixed-size instruction argument types for
+//! [`crate::instructions::DropsetInstruction::BatchReplace`].
+//!
+//! Instruction arguments must have a static length, so each batch is represented as a fixed-size
+//! array of items with a length prefix indicating how many of the items are actually used.
+
+use instruction_macros::{
+    Pack,
+    Unpack,
+};
+use price::OrderInfoArgs;
+use solana_program_error::ProgramError;
+use static_assertions::const_assert;
+
+use crate::error::DropsetError;
+
+/// The max number of cancels and the max number of posts in a single batch.
+///
+/// This is bounded by the transaction size limit rather than
+/// [`crate::state::user_order_sectors::MAX_ORDERS`], so replacing more orders than this takes
+/// multiple batches. A full batch must fit in [`MAX_BATCH_REPLACE_DATA_LEN`], which is checked at
+/// compile time.
+pub const MAX_BATCH_ITEMS: usize = 10;
+
+/// The max length of a serialized transaction, signatures included.
+const PACKET_DATA_SIZE: usize = 1232;
+
+/// An upper bound on the bytes a single signer `BatchReplace` transaction uses outside of the
+/// instruction data: one signature, the message header, the four instruction account keys, the
+/// recent blockhash, the compiled instruction's indices and lengths, and room for a compute budget
+/// program key and two compute budget instructions.
+const BATCH_REPLACE_TRANSACTION_OVERHEAD: usize = 320;
+
+/// The max length of the `BatchReplace` instruction data that still fits in a single transaction.
+pub const MAX_BATCH_REPLACE_DATA_LEN: usize = PACKET_DATA_SIZE - BATCH_REPLACE_TRANSACTION_OVERHEAD;
+
+/// The arguments for a single cancel in a batch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pack, Unpack)]
+pub struct CancelOrderArgs {
+    /// The sector index of the order to cancel.
+    pub order_sector_index: u32,
+    /// Whether or not the order is a bid. If false, the order is an ask.
+    pub is_bid: bool,
+}
+
+impl CancelOrderArgs {
+    #[inline(always)]
+    pub fn new(order_sector_index: u32, is_bid: bool) -> Self {
+        Self {
+            order_sector_index,
+            is_bid,
+        }
+    }
+}
+
+/// The arguments for a single post in a batch.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Pack, Unpack)]
+pub struct PostOrderArgs {
+    /// The order info arguments.
+    pub order_info_args: OrderInfoArgs,
+    /// Whether or not the order is a bid. If false, the order is an ask.
+    pub is_bid: bool,
+    /// The unix timestamp in seconds at which the order expires. Zero means it never expires.
+    pub expiry: u64,
+    /// The user's own identifier for the order. Zero means it has none.
+    pub client_order_id: u64,
+}
+
+impl PostOrderArgs {
+    #[inline(always)]
+    pub fn new(
+        order_info_args: OrderInfoArgs,
+        is_bid: bool,
+        expiry: u64,
+        client_order_id: u64,
+    ) -> Self {
+        Self {
+            order_info_args,
+            is_bid,
+            expiry,
+            client_order_id,
+        }
+    }
+}
+
+/// Implements a length-prefixed, fixed-capacity batch of `$item`s along with its [`Pack`] and
+/// [`Unpack`] implementations.
+///
+/// The packed layout is a single `u8` length followed by [`MAX_BATCH_ITEMS`] packed items. Unused
+/// items are packed as zeroed bytes and ignored when unpacking.
+macro_rules! impl_batch {
+    ($(#[$meta:meta])* $batch:ident, $item:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
+        pub struct $batch {
+            len: u8,
+            items: [$item; MAX_BATCH_ITEMS],
+        }
+
+        impl $batch {
+            /// Creates a new batch from a slice of items.
+            ///
+            /// Fails if there are more than [`MAX_BATCH_ITEMS`] items.
+            pub fn new(items: &[$item]) -> Result<Self, DropsetError> {
+                if items.len() > MAX_BATCH_ITEMS {
+                    return Err(DropsetError::BatchTooLarge);
+                }
+
+                let mut res = Self {
+                    len: items.len() as u8,
+                    ..Default::default()
+                };
+                res.items[..items.len()].clone_from_slice(items);
+
+                Ok(res)
+            }
+
+            /// Returns the used items in the batch.
+            #[inline(always)]
+            pub fn as_slice(&self) -> &[$item] {
+                &self.items[..self.len as usize]
+            }
+
+            #[inline(always)]
+            pub fn len(&self) -> usize {
+                self.len as usize
+            }
+
+            #[inline(always)]
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+        }
+
+        unsafe impl Pack for $batch {
+            type Packed = [u8; 1 + MAX_BATCH_ITEMS * <$item as Pack>::LEN];
+
+            #[inline(always)]
+            unsafe fn write_bytes(&self, dst: *mut u8) {
+                const ITEM_LEN: usize = <$item as Pack>::LEN;
+                dst.write(self.len);
+                for (i, item) in self.items.iter().enumerate() {
+                    item.write_bytes(dst.add(1 + i * ITEM_LEN));
+                }
+            }
+
+            #[inline(always)]
+            fn pack(&self) -> Self::Packed {
+                let mut buf = [0u8; <Self as Pack>::LEN];
+                // Safety: `buf` is exactly `Self::LEN` writable bytes.
+                unsafe { self.write_bytes(buf.as_mut_ptr()) };
+                buf
+            }
+        }
+
+        unsafe impl Unpack for $batch {
+            #[inline(always)]
+            unsafe fn read_bytes(src: *const u8) -> Result<Self, ProgramError> {
+                const ITEM_LEN: usize = <$item as Pack>::LEN;
+                let len = src.read();
+                if len as usize > MAX_BATCH_ITEMS {
+                    return Err(DropsetError::BatchTooLarge.into());
+                }
+
+                let mut res = Self {
+                    len,
+                    ..Default::default()
+                };
+                for (i, item) in res.items.iter_mut().take(len as usize).enumerate() {
+                    *item = <$item as Unpack>::read_bytes(src.add(1 + i * ITEM_LEN))?;
+                }
+
+                Ok(res)
+            }
+
+            #[inline(always)]
+            fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+                if data.len() < <Self as Pack>::LEN {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                // Safety: The length of `data` was just verified as sufficient.
+                unsafe { Self::read_bytes(data.as_ptr()) }
+            }
+        }
+    };
+}
+
+impl_batch!(
+    /// A batch of up to [`MAX_BATCH_ITEMS`] [`CancelOrderArgs`].
+    BatchCancels,
+    CancelOrderArgs
+);
+impl_batch!(
+    /// A batch of up to [`MAX_BATCH_ITEMS`] [`PostOrderArgs`].
+    BatchPosts,
+    PostOrderArgs
+);
+
+// The tag, both full batches, and the `u32` seat hint must fit in a single transaction.
+const_assert!(
+    1 + <BatchCancels as Pack>::LEN + <BatchPosts as Pack>::LEN + size_of::<u32>()
+        <= MAX_BATCH_REPLACE_DATA_LEN
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_round_trip() {
+        let cancels = [
+            CancelOrderArgs::new(3, true),
+            CancelOrderArgs::new(7, false),
+        ];
+        let batch = BatchCancels::new(&cancels).unwrap();
+        assert_eq!(batch.as_slice(), &cancels);
+        assert_eq!(BatchCancels::unpack(&batch.pack()).unwrap(), batch);
+
+        let posts = [PostOrderArgs::new(
+            OrderInfoArgs::new_unscaled(10_000_000, 1),
+            true,
+            1_700_000_000,
+            42,
+        )];
+        let batch = BatchPosts::new(&posts).unwrap();
+        assert_eq!(batch.as_slice(), &posts);
+        assert_eq!(BatchPosts::unpack(&batch.pack()).unwrap(), batch);
+    }
+
+    #[test]
+    fn batch_too_large() {
+        let cancels = [CancelOrderArgs::default(); MAX_BATCH_ITEMS + 1];
+        assert_eq!(
+            BatchCancels::new(&cancels),
+            Err(DropsetError::BatchTooLarge)
+        );
+        assert!(BatchCancels::new(&cancels[..MAX_BATCH_ITEMS]).is_ok());
+
+        let mut packed = BatchCancels::default().pack();
+        packed[0] = MAX_BATCH_ITEMS as u8 + 1;
+        assert!(BatchCancels::unpack(&packed).is_err());
+    }
+}
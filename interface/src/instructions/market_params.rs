@@ -0,0 +1,54 @@
+//! The market parameters instruction argument for
+//! [`crate::instructions::DropsetInstruction::RegisterMarket`] and
+//! [`crate::instructions::DropsetInstruction::UpdateMarketParams`].
+
+use instruction_macros::{
+    Pack,
+    Unpack,
+};
+use solana_address::Address;
+
+use crate::state::user_order_sectors::MAX_ORDERS;
+
+/// The configurable parameters for a market. These are set when the market is registered and can
+/// be updated afterwards by the market authority.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, Pack, Unpack)]
+pub struct MarketParams {
+    /// The address allowed to sweep the market's accrued protocol fees.
+    pub fee_authority: Address,
+    /// The taker fee rate in basis points, charged on the quote filled.
+    pub taker_fee_bps: u16,
+    /// The maker rebate rate in basis points. Can't exceed the taker fee rate.
+    pub maker_rebate_bps: u16,
+    /// The price mantissa of every posted order must be a multiple of the tick size. For example,
+    /// a tick size of 1_000 limits prices to 5 significant digits.
+    pub tick_size: u32,
+    /// The base atoms of every posted order and base-denominated market order must be a multiple
+    /// of the base lot size.
+    pub base_lot_size: u64,
+    /// The minimum base atoms of an order.
+    pub min_base_order_size: u64,
+    /// The minimum quote atoms of an order.
+    pub min_quote_order_size: u64,
+    /// The max number of bids and the max number of asks each user can have open at once. Must be
+    /// between 1 and [`MAX_ORDERS`]. Lowering it doesn't affect orders that are already open.
+    pub max_orders: u8,
+}
+
+impl Default for MarketParams {
+    /// No fees, no restrictions on price granularity or order sizes and the max number of orders
+    /// per side.
+    fn default() -> Self {
+        Self {
+            fee_authority: Address::new_from_array([0; 32]),
+            taker_fee_bps: 0,
+            maker_rebate_bps: 0,
+            tick_size: 1,
+            base_lot_size: 1,
+            min_base_order_size: 0,
+            min_quote_order_size: 0,
+            max_orders: MAX_ORDERS,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! The self-trade prevention instruction argument for
+//! [`crate::instructions::DropsetInstruction::MarketOrder`] and
+//! [`crate::instructions::DropsetInstruction::LimitOrder`].
+
+use instruction_macros::u8_enum;
+
+u8_enum! {
+    /// Determines what happens when a taker order would match against one of the taker's own
+    /// resting orders.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum SelfTradePrevention {
+        /// Cancel the resting order, return its collateral to the taker's seat and keep matching.
+        #[default]
+        CancelResting = 0,
+        /// Stop matching and cancel the taker order's unfilled remainder. The resting order is left
+        /// untouched.
+        CancelTaking = 1,
+        /// Decrement both the resting order and the taker order by their overlapping size without
+        /// trading. The resting order is cancelled if it's decremented entirely.
+        DecrementBoth = 2,
+        /// Fail the instruction.
+        Abort = 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instruction_macros::{
+        Pack,
+        Unpack,
+    };
+
+    use super::*;
+
+    #[test]
+    fn self_trade_prevention_round_trip() {
+        for stp in [
+            SelfTradePrevention::CancelResting,
+            SelfTradePrevention::CancelTaking,
+            SelfTradePrevention::DecrementBoth,
+            SelfTradePrevention::Abort,
+        ] {
+            assert_eq!(SelfTradePrevention::unpack(&stp.pack()).unwrap(), stp);
+        }
+        assert!(SelfTradePrevention::unpack(&[4]).is_err());
+        assert!(SelfTradePrevention::unpack(&[]).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Cross-program invocation helpers for [`crate::instructions::DropsetInstruction::Swap`].
+//!
+//! Like a market order, a swap emits its maker fills through the event self-CPI, so the caller
+//! must pass the event authority and the dropset program account. Indexers rebuild the order book
+//! from these events, and unlike program logs, the self-CPI's instruction data can't be truncated,
+//! so a swap can't skip it. Both accounts have fixed addresses,
+//! [`crate::seeds::event_authority::ID`] and [`crate::program::ID`], so a calling program can
+//! hard-code them.
+//!
+//! Quote a swap off-chain with [`crate::matching::simulate_swap`].
+
+#[cfg(feature = "program")]
+use solana_instruction_view::cpi::Signer;
+#[cfg(feature = "program")]
+use solana_program_error::ProgramResult;
+
+#[cfg(feature = "program")]
+use crate::instructions::{
+    generated_program::Swap,
+    SwapInstructionData,
+};
+
+#[cfg(feature = "program")]
+impl Swap<'_> {
+    /// Swaps exactly `amount_in` atoms of the input asset, before fees: quote for a buy and base
+    /// for a sell. Fails if the `user` would receive less than `min_amount_out`.
+    ///
+    /// The input is bounded by `amount_in` and the market's taker fee, so no maximum input is
+    /// passed.
+    #[inline(always)]
+    pub fn invoke_exact_in(
+        self,
+        signers_seeds: &[Signer],
+        is_buy: bool,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> ProgramResult {
+        self.invoke_signed(
+            signers_seeds,
+            SwapInstructionData::new(amount_in, is_buy, false, min_amount_out, u64::MAX),
+        )
+    }
+
+    /// Swaps for exactly `amount_out` atoms of the output asset, before fees: base for a buy and
+    /// quote for a sell. Any amount the book can't fill is discarded, so fails if the `user` would
+    /// receive less than `min_amount_out`.
+    ///
+    /// The input depends on the prices the book fills at, so fails if the `user` would pay more
+    /// than `max_amount_in`, including fees: quote for a buy and base for a sell.
+    #[inline(always)]
+    pub fn invoke_exact_out(
+        self,
+        signers_seeds: &[Signer],
+        is_buy: bool,
+        amount_out: u64,
+        min_amount_out: u64,
+        max_amount_in: u64,
+    ) -> ProgramResult {
+        self.invoke_signed(
+            signers_seeds,
+            SwapInstructionData::new(amount_out, is_buy, true, min_amount_out, max_amount_in),
+        )
+    }
+}
@@ -0,0 +1,35 @@
+//! The time-in-force instruction argument for
+//! [`crate::instructions::DropsetInstruction::MarketOrder`].
+
+use instruction_macros::u8_enum;
+
+u8_enum! {
+    /// Determines how a market order handles any amount that can't be filled immediately.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum TimeInForce {
+        /// Fill as much as possible up to the limit price and discard the rest.
+        #[default]
+        ImmediateOrCancel = 0,
+        /// Fill the entire order size up to the limit price or fail the instruction.
+        FillOrKill = 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instruction_macros::{
+        Pack,
+        Unpack,
+    };
+
+    use super::*;
+
+    #[test]
+    fn time_in_force_round_trip() {
+        for tif in [TimeInForce::ImmediateOrCancel, TimeInForce::FillOrKill] {
+            assert_eq!(TimeInForce::unpack(&tif.pack()).unwrap(), tif);
+        }
+        assert!(TimeInForce::unpack(&[2]).is_err());
+        assert!(TimeInForce::unpack(&[]).is_err());
+    }
+}
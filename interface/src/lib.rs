@@ -9,6 +9,7 @@ extern crate std;
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod matching;
 pub mod seeds;
 pub mod state;
 pub mod utils;
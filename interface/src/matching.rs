@@ -0,0 +1,866 @@
+//! Matching math shared by the program's market order fills and read-only simulations of them, so
+//! that off-chain quoters compute exactly the amounts the program fills.
+
+use core::{
+    cmp::Ordering,
+    num::{
+        NonZeroU128,
+        NonZeroU64,
+    },
+};
+
+use pinocchio::hint::unlikely;
+use price::{
+    ENCODED_PRICE_INFINITY,
+    ENCODED_PRICE_ZERO,
+};
+use solana_address::{
+    address_eq,
+    Address,
+};
+
+use crate::{
+    error::{
+        DropsetError,
+        DropsetResult,
+    },
+    instructions::{
+        SelfTradePrevention,
+        TimeInForce,
+    },
+    state::{
+        market::{
+            Market,
+            MarketRef,
+        },
+        market_header::MarketHeader,
+        market_seat::MarketSeat,
+        oracle::OracleBand,
+        order::Order,
+        sector::{
+            Sector,
+            SectorIndex,
+            NIL,
+        },
+    },
+};
+
+#[inline(always)]
+pub fn mul_div_checked(
+    multiplicand: u64,
+    multiplier: u64,
+    divisor: NonZeroU64,
+) -> Result<u64, DropsetError> {
+    let intermediate = price::checked_mul!(
+        multiplicand as u128,
+        multiplier as u128,
+        DropsetError::ArithmeticOverflow
+    )?;
+
+    let res = intermediate / NonZeroU128::from(divisor);
+    if res > u64::MAX as u128 {
+        return Err(DropsetError::ArithmeticOverflow);
+    }
+    Ok(res as u64)
+}
+
+/// Whether or not a posted order's encoded price is worse than the taker's limit price.
+///
+/// A market buy fills asks, so asks priced higher than the limit are worse. A market sell fills
+/// bids, so bids priced lower than the limit are worse.
+#[inline(always)]
+pub const fn is_worse_than_limit<const IS_BUY: bool>(encoded_price: u32, limit_price: u32) -> bool {
+    if IS_BUY {
+        encoded_price > limit_price
+    } else {
+        encoded_price < limit_price
+    }
+}
+
+/// Whether or not a posted order's encoded price is past the far side of the oracle band, where
+/// every order after it on the book is priced outside the band too.
+///
+/// A market buy fills asks from the lowest price, so asks above the band are past it. A market sell
+/// fills bids from the highest price, so bids below the band are past it. Orders outside the near
+/// side of the band are priced better for the taker than the band allows, and are removed instead.
+#[inline(always)]
+pub fn is_past_oracle_band<const IS_BUY: bool>(band: &OracleBand, encoded_price: u32) -> bool {
+    let far_side = if IS_BUY { Ordering::Greater } else { Ordering::Less };
+    band.compare(encoded_price) == far_side
+}
+
+/// Returns the amount of a posted order's counter asset matched when `constrained_filled` atoms of
+/// its `constrained_remaining` atoms are filled, rounded down.
+#[inline(always)]
+pub fn counter_asset_fill_amount(
+    constrained_filled: u64,
+    constrained_remaining: u64,
+    counter_remaining: u64,
+) -> Result<u64, DropsetError> {
+    let divisor = NonZeroU64::new(constrained_remaining).ok_or(DropsetError::AmountCannotBeZero)?;
+    mul_div_checked(constrained_filled, counter_remaining, divisor)
+}
+
+/// Whether or not a swap's size is denominated in base.
+///
+/// An exact-in buy and an exact-out sell are sized in quote, whereas an exact-out buy and an
+/// exact-in sell are sized in base.
+#[inline(always)]
+pub const fn is_swap_base_denominated(is_buy: bool, is_exact_out: bool) -> bool {
+    is_buy == is_exact_out
+}
+
+/// Returns the amounts a taker pays in and receives out for a fill, in that order.
+///
+/// The taker fee is paid in quote on top of the quote filled for a buy and deducted from the quote
+/// filled for a sell.
+#[inline(always)]
+pub fn swap_amounts(
+    is_buy: bool,
+    base_filled: u64,
+    quote_filled: u64,
+    taker_fee: u64,
+) -> Result<(u64, u64), DropsetError> {
+    if is_buy {
+        let quote_paid = quote_filled
+            .checked_add(taker_fee)
+            .ok_or(DropsetError::ArithmeticOverflow)?;
+        Ok((quote_paid, base_filled))
+    } else {
+        let quote_received = quote_filled
+            .checked_sub(taker_fee)
+            .ok_or(DropsetError::ArithmeticUnderflow)?;
+        Ok((base_filled, quote_received))
+    }
+}
+
+/// Returns the amounts a taker pays in and receives out for a fill like [`swap_amounts`], failing
+/// if the taker would receive less than `min_output` or pay more than `max_input`.
+#[inline(always)]
+pub fn checked_swap_amounts(
+    is_buy: bool,
+    base_filled: u64,
+    quote_filled: u64,
+    taker_fee: u64,
+    min_output: u64,
+    max_input: u64,
+) -> Result<(u64, u64), DropsetError> {
+    let (amount_in, amount_out) = swap_amounts(is_buy, base_filled, quote_filled, taker_fee)?;
+    if amount_out < min_output {
+        return Err(DropsetError::MinimumOutputNotMet);
+    }
+    if amount_in > max_input {
+        return Err(DropsetError::MaximumInputExceeded);
+    }
+
+    Ok((amount_in, amount_out))
+}
+
+/// The taker's side of a market order, i.e., who is taking and the constraints on the order.
+pub struct TakerOrder<'a> {
+    pub taker: &'a Address,
+    pub order_size: u64,
+    pub limit_price: u32,
+    pub time_in_force: TimeInForce,
+    pub self_trade_prevention: SelfTradePrevention,
+}
+
+/// The amounts a taker order filled, as returned by [`match_taker_order`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AmountsFilled {
+    pub base: u64,
+    pub quote: u64,
+    /// The amount of the order size that was neither filled nor removed by self-trade prevention,
+    /// in the order size's denomination.
+    pub remaining: u64,
+    /// The time-in-force the order was filled with.
+    pub time_in_force: TimeInForce,
+    /// Whether or not the entire order size was filled.
+    pub is_fully_filled: bool,
+    /// The total fee charged to the taker in quote atoms. This is not included in `quote`.
+    pub taker_fee: u64,
+}
+
+/// A snapshot of a posted order, taken before a taker order is matched against it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookOrder {
+    pub base_remaining: u64,
+    pub quote_remaining: u64,
+    pub encoded_price: u32,
+    pub maker_seat_sector: SectorIndex,
+    pub order_sector: SectorIndex,
+    /// The sector index of the next order on the same side of the book.
+    pub next_order_sector: SectorIndex,
+    /// Whether or not the order belongs to the taker.
+    pub is_self_trade: bool,
+    /// Whether or not the order has expired.
+    pub is_expired: bool,
+    pub client_order_id: u64,
+}
+
+impl BookOrder {
+    /// Returns a snapshot of the posted order at `order_sector`, or `None` if `order_sector` is
+    /// NIL.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees `order_sector` is either NIL or points to an order on the book.
+    #[inline(always)]
+    pub unsafe fn load<H, S>(
+        market: &Market<H, S>,
+        order_sector: SectorIndex,
+        taker: &Address,
+        unix_timestamp: u64,
+    ) -> Option<Self>
+    where
+        H: AsRef<MarketHeader>,
+        S: AsRef<[u8]>,
+    {
+        if order_sector == NIL {
+            return None;
+        }
+
+        let sectors = market.sectors.as_ref();
+        // Safety: Caller guarantees the non-NIL order sector index points to an order.
+        let order_sector_data = unsafe { Sector::from_sector_index(sectors, order_sector) };
+        let order = order_sector_data.load_payload::<Order>();
+        // Safety: The user seat sector index is in-bounds, as it came from the order.
+        let maker_seat = unsafe { Sector::from_sector_index(sectors, order.user_seat()) }
+            .load_payload::<MarketSeat>();
+        Some(BookOrder {
+            base_remaining: order.base_remaining(),
+            quote_remaining: order.quote_remaining(),
+            encoded_price: order.encoded_price(),
+            maker_seat_sector: order.user_seat(),
+            order_sector,
+            next_order_sector: order_sector_data.next(),
+            is_self_trade: address_eq(&maker_seat.user, taker),
+            is_expired: order.is_expired(unix_timestamp),
+            client_order_id: order.client_order_id(),
+        })
+    }
+
+    /// The amount remaining in the order in the taker order size's denomination.
+    #[inline(always)]
+    pub const fn constrained_remaining<const BASE_DENOM: bool>(&self) -> u64 {
+        if BASE_DENOM {
+            self.base_remaining
+        } else {
+            self.quote_remaining
+        }
+    }
+
+    /// The amount remaining in the order in the other asset.
+    #[inline(always)]
+    pub const fn counter_remaining<const BASE_DENOM: bool>(&self) -> u64 {
+        if BASE_DENOM {
+            self.quote_remaining
+        } else {
+            self.base_remaining
+        }
+    }
+}
+
+/// The order book a taker order is matched against by [`match_taker_order`].
+///
+/// [`match_taker_order`] decides how each posted order is matched and by how much, and leaves
+/// applying each outcome to the implementor. The program applies them to the market account and
+/// emits an event for each, whereas [`simulate_market_order`] only totals the taker fees, so that
+/// both walk the book identically.
+///
+/// `IS_BUY` is the taker's side: the posted orders are asks for a buy and bids for a sell.
+pub trait OrderBook {
+    type Error: From<DropsetError>;
+
+    /// Returns the market whose book is being matched against.
+    fn market(&self) -> MarketRef<'_>;
+
+    /// Removes an expired order from the book, returning its collateral to the maker.
+    fn remove_expired<const IS_BUY: bool>(&mut self, order: &BookOrder) -> Result<(), Self::Error>;
+
+    /// Removes an order priced outside the near side of the market's oracle band from the book,
+    /// returning its collateral to the maker.
+    fn remove_out_of_band<const IS_BUY: bool>(
+        &mut self,
+        order: &BookOrder,
+    ) -> Result<(), Self::Error>;
+
+    /// Fills `base_filled` and `quote_filled` of a posted order, removing it from the book if it's
+    /// closed by the fill, and returns the taker fee charged on the fill.
+    fn fill<const IS_BUY: bool>(
+        &mut self,
+        order: &BookOrder,
+        base_filled: u64,
+        quote_filled: u64,
+        is_order_closed: bool,
+    ) -> Result<u64, Self::Error>;
+
+    /// Cancels the taker's own posted order for [`SelfTradePrevention::CancelResting`].
+    fn cancel_resting<const IS_BUY: bool>(
+        &mut self,
+        taker: &Address,
+        order: &BookOrder,
+    ) -> Result<(), Self::Error>;
+
+    /// Cancels the `constraint_asset_remaining` left in the taker order when it reaches the taker's
+    /// own posted order for [`SelfTradePrevention::CancelTaking`].
+    fn cancel_taking(
+        &mut self,
+        order: &BookOrder,
+        constraint_asset_remaining: u64,
+    ) -> Result<(), Self::Error>;
+
+    /// Decrements the taker's own posted order by `base_decremented` and `quote_decremented` for
+    /// [`SelfTradePrevention::DecrementBoth`], cancelling it if it's closed, and otherwise
+    /// returning the released collateral to the taker's seat.
+    fn decrement_both<const IS_BUY: bool>(
+        &mut self,
+        taker: &Address,
+        order: &BookOrder,
+        base_decremented: u64,
+        quote_decremented: u64,
+        is_order_closed: bool,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Matches a taker order against the opposite side of the book, from the top of the book down.
+///
+/// `is_buy` determines whether or not it's a market buy or a market sell. `is_base` determines
+/// which asset the order size is in. That is, if the user specifies they want to market buy 1000
+/// quote atoms worth:
+///
+/// `is_buy == true && is_base == false && order_size == 1000`
+///
+/// The taker order's `limit_price` is the worst encoded price the taker is willing to match
+/// against. Matching stops at the first order with a worse price; i.e., an ask priced above the
+/// limit for a market buy or a bid priced below the limit for a market sell. Pass
+/// [`ENCODED_PRICE_INFINITY`] for a market buy or [`ENCODED_PRICE_ZERO`] for a market sell to match
+/// without a price limit.
+///
+/// Its `time_in_force` determines what happens when the order can't be filled entirely. An
+/// [`TimeInForce::ImmediateOrCancel`] order discards the unfilled remainder, whereas a
+/// [`TimeInForce::FillOrKill`] order fails with [`DropsetError::FillOrKillNotFilled`].
+///
+/// Expired orders are removed instead of filled with [`OrderBook::remove_expired`].
+///
+/// If the market has an oracle, filling stops at the first posted order priced past the far side
+/// of the market's oracle band, and it fails with [`DropsetError::OraclePriceStale`] if the cached
+/// oracle price is stale at `unix_timestamp`. Posted orders outside the near side of the band are
+/// priced better for the taker than the band allows, so they're removed instead of filled with
+/// [`OrderBook::remove_out_of_band`]. Otherwise they'd stay at the top of the book, where they'd
+/// block every post priced across them.
+///
+/// Its `self_trade_prevention` determines what happens when the top of the book is one of the
+/// taker's own orders. [`SelfTradePrevention::Abort`] fails with
+/// [`DropsetError::SelfTradeAborted`]. Any amount removed from the taker order by the other modes
+/// isn't filled, so the order can't be fully filled afterwards.
+///
+/// The amounts filled are returned denominated in both base and quote. The ratio of these two
+/// values is effectively the average fill price.
+#[inline(always)]
+pub fn match_taker_order<B: OrderBook>(
+    book: &mut B,
+    taker_order: TakerOrder<'_>,
+    is_buy: bool,
+    is_base: bool,
+    unix_timestamp: u64,
+) -> Result<AmountsFilled, B::Error> {
+    match (is_buy, is_base) {
+        (false, false) => walk_book::<B, false, false>(book, taker_order, unix_timestamp),
+        (true, false) => walk_book::<B, true, false>(book, taker_order, unix_timestamp),
+        (false, true) => walk_book::<B, false, true>(book, taker_order, unix_timestamp),
+        (true, true) => walk_book::<B, true, true>(book, taker_order, unix_timestamp),
+    }
+}
+
+/// See [`match_taker_order`]. `IS_BUY` and `BASE_DENOM` are its `is_buy` and `is_base`.
+#[inline(always)]
+fn walk_book<B: OrderBook, const IS_BUY: bool, const BASE_DENOM: bool>(
+    book: &mut B,
+    taker_order: TakerOrder<'_>,
+    unix_timestamp: u64,
+) -> Result<AmountsFilled, B::Error> {
+    let TakerOrder {
+        taker,
+        order_size,
+        limit_price,
+        time_in_force,
+        self_trade_prevention,
+    } = taker_order;
+
+    // All amounts in this function are in atoms.
+    let mut constraint_asset_remaining = order_size;
+    let mut counter_asset_filled: u64 = 0;
+    let mut taker_fee: u64 = 0;
+    // The amount of the constraint asset removed from the taker order by self-trade prevention.
+    let mut constraint_asset_self_traded: u64 = 0;
+
+    let (oracle_band, mut curr) = {
+        let market = book.market();
+        let oracle_band = market.header.oracle_band(unix_timestamp)?;
+        let head = if IS_BUY {
+            market.header.asks_dll_head()
+        } else {
+            market.header.bids_dll_head()
+        };
+        (oracle_band, head)
+    };
+
+    // Iterate over each order on the book, filling each posted order in whole as long as the
+    // market order has any remaining size.
+    // That is, as long as the amount not filled yet exceeds the amount in the next posted order,
+    // simply close the order and decrement the remaining amount by the amount used to fill the
+    // order. This skips muldiv operations until the very last partial fill.
+    //
+    // The simulator leaves every order on the book, so the walk follows each order's next sector
+    // index instead of re-reading the top of the book. The next sector index is read before the
+    // order can be removed from the book and its sector freed.
+    loop {
+        // Safety: `curr` is either NIL, the head of the orders list or the next sector index of
+        // an order read before it could be removed, so it points to an order on the book.
+        let top_order = unsafe { BookOrder::load(&book.market(), curr, taker, unix_timestamp) };
+        let Some(top_order) = top_order else {
+            break;
+        };
+        curr = top_order.next_order_sector;
+
+        // If there's nothing left to fill, break from the loop. The last order filled cleanly with
+        // no remainder so there's no partial order to fill.
+        if unlikely(constraint_asset_remaining == 0) {
+            break;
+        // Expired orders are skipped and removed from the book, returning the maker's collateral.
+        } else if unlikely(top_order.is_expired) {
+            book.remove_expired::<IS_BUY>(&top_order)?;
+        // If the top order's price is worse than the limit price, the rest of the book is too.
+        } else if is_worse_than_limit::<IS_BUY>(top_order.encoded_price, limit_price) {
+            break;
+        // Orders priced past the far side of the oracle band are never filled, and neither are
+        // any of the orders after them.
+        } else if oracle_band
+            .is_some_and(|band| is_past_oracle_band::<IS_BUY>(&band, top_order.encoded_price))
+        {
+            break;
+        // Orders priced outside the near side of the oracle band are removed without being filled.
+        } else if oracle_band.is_some_and(|band| !band.contains(top_order.encoded_price)) {
+            book.remove_out_of_band::<IS_BUY>(&top_order)?;
+        // The top order belongs to the taker, so prevent the self-trade instead of filling it.
+        } else if unlikely(top_order.is_self_trade) {
+            match self_trade_prevention {
+                SelfTradePrevention::CancelResting => {
+                    book.cancel_resting::<IS_BUY>(taker, &top_order)?;
+                }
+                SelfTradePrevention::CancelTaking => {
+                    book.cancel_taking(&top_order, constraint_asset_remaining)?;
+                    constraint_asset_self_traded = constraint_asset_self_traded
+                        .checked_add(constraint_asset_remaining)
+                        .ok_or(DropsetError::ArithmeticOverflow)?;
+                    constraint_asset_remaining = 0;
+                    break;
+                }
+                SelfTradePrevention::DecrementBoth => {
+                    // The resting order is decremented entirely if it fits in what's left of the
+                    // taker order, which is the same as cancelling it.
+                    let resting_constrained_remaining =
+                        top_order.constrained_remaining::<BASE_DENOM>();
+                    let is_order_closed =
+                        resting_constrained_remaining <= constraint_asset_remaining;
+                    let (constrained_decremented, counter_decremented) = if is_order_closed {
+                        (
+                            resting_constrained_remaining,
+                            top_order.counter_remaining::<BASE_DENOM>(),
+                        )
+                    } else {
+                        (
+                            constraint_asset_remaining,
+                            counter_asset_fill_amount(
+                                constraint_asset_remaining,
+                                resting_constrained_remaining,
+                                top_order.counter_remaining::<BASE_DENOM>(),
+                            )?,
+                        )
+                    };
+                    let (base_decremented, quote_decremented) = if BASE_DENOM {
+                        (constrained_decremented, counter_decremented)
+                    } else {
+                        (counter_decremented, constrained_decremented)
+                    };
+
+                    book.decrement_both::<IS_BUY>(
+                        taker,
+                        &top_order,
+                        base_decremented,
+                        quote_decremented,
+                        is_order_closed,
+                    )?;
+
+                    // Safety: The constrained amount decremented is always <= the constraint asset
+                    // remaining.
+                    constraint_asset_remaining = unsafe {
+                        constraint_asset_remaining.unchecked_sub(constrained_decremented)
+                    };
+                    constraint_asset_self_traded = constraint_asset_self_traded
+                        .checked_add(constrained_decremented)
+                        .ok_or(DropsetError::ArithmeticOverflow)?;
+                }
+                SelfTradePrevention::Abort => return Err(DropsetError::SelfTradeAborted.into()),
+            }
+        } else {
+            // Otherwise, check if this should be a partial fill or a full fill.
+            // If the constrained asset amount remaining in the top order is <= to the amount that
+            // should be filled for the taker, it's a full fill. That is, the maker order can be
+            // completely filled and thus removed from the books.
+            let resting_constrained_remaining = top_order.constrained_remaining::<BASE_DENOM>();
+            let is_order_closed = resting_constrained_remaining <= constraint_asset_remaining;
+            let (constrained_filled, counter_filled) = if is_order_closed {
+                (
+                    resting_constrained_remaining,
+                    top_order.counter_remaining::<BASE_DENOM>(),
+                )
+            } else {
+                // Otherwise, it's a partial fill with the rest of the taker order.
+                (
+                    constraint_asset_remaining,
+                    counter_asset_fill_amount(
+                        constraint_asset_remaining,
+                        resting_constrained_remaining,
+                        top_order.counter_remaining::<BASE_DENOM>(),
+                    )?,
+                )
+            };
+            let (base_filled, quote_filled) = if BASE_DENOM {
+                (constrained_filled, counter_filled)
+            } else {
+                (counter_filled, constrained_filled)
+            };
+
+            let fill_taker_fee =
+                book.fill::<IS_BUY>(&top_order, base_filled, quote_filled, is_order_closed)?;
+
+            // Safety: The constrained amount filled is always <= the constraint asset remaining.
+            constraint_asset_remaining =
+                unsafe { constraint_asset_remaining.unchecked_sub(constrained_filled) };
+            counter_asset_filled = counter_asset_filled
+                .checked_add(counter_filled)
+                .ok_or(DropsetError::ArithmeticOverflow)?;
+            taker_fee = taker_fee
+                .checked_add(fill_taker_fee)
+                .ok_or(DropsetError::ArithmeticOverflow)?;
+
+            if !is_order_closed {
+                // The taker order amount should be completely filled now.
+                debug_assert_eq!(constraint_asset_remaining, 0);
+                break;
+            }
+        }
+    }
+
+    let is_fully_filled = constraint_asset_remaining == 0 && constraint_asset_self_traded == 0;
+    if time_in_force == TimeInForce::FillOrKill && !is_fully_filled {
+        return Err(DropsetError::FillOrKillNotFilled.into());
+    }
+
+    // Safety: The constraint asset remaining and self-traded amounts are only ever moved out of the
+    // order size, so their sum is always <= the order size.
+    let constrained_asset_filled = unsafe {
+        order_size
+            .unchecked_sub(constraint_asset_remaining)
+            .unchecked_sub(constraint_asset_self_traded)
+    };
+
+    let (base, quote) = if BASE_DENOM {
+        (constrained_asset_filled, counter_asset_filled)
+    } else {
+        (counter_asset_filled, constrained_asset_filled)
+    };
+
+    Ok(AmountsFilled {
+        base,
+        quote,
+        remaining: constraint_asset_remaining,
+        time_in_force,
+        is_fully_filled,
+        taker_fee,
+    })
+}
+
+/// A read-only [`OrderBook`] that leaves the market untouched and only charges the market's taker
+/// fee on each fill.
+struct SimulatedBook<'a> {
+    market: MarketRef<'a>,
+}
+
+impl<'a> SimulatedBook<'a> {
+    fn new<H, S>(market: &'a Market<H, S>) -> Self
+    where
+        H: AsRef<MarketHeader>,
+        S: AsRef<[u8]>,
+    {
+        SimulatedBook {
+            market: Market {
+                header: market.header.as_ref(),
+                sectors: market.sectors.as_ref(),
+            },
+        }
+    }
+}
+
+impl OrderBook for SimulatedBook<'_> {
+    type Error = DropsetError;
+
+    #[inline(always)]
+    fn market(&self) -> MarketRef<'_> {
+        Market {
+            header: self.market.header,
+            sectors: self.market.sectors,
+        }
+    }
+
+    #[inline(always)]
+    fn remove_expired<const IS_BUY: bool>(&mut self, _order: &BookOrder) -> DropsetResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn remove_out_of_band<const IS_BUY: bool>(&mut self, _order: &BookOrder) -> DropsetResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn fill<const IS_BUY: bool>(
+        &mut self,
+        _order: &BookOrder,
+        _base_filled: u64,
+        quote_filled: u64,
+        _is_order_closed: bool,
+    ) -> Result<u64, DropsetError> {
+        Ok(self.market.header.taker_fee(quote_filled))
+    }
+
+    #[inline(always)]
+    fn cancel_resting<const IS_BUY: bool>(
+        &mut self,
+        _taker: &Address,
+        _order: &BookOrder,
+    ) -> DropsetResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn cancel_taking(
+        &mut self,
+        _order: &BookOrder,
+        _constraint_asset_remaining: u64,
+    ) -> DropsetResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn decrement_both<const IS_BUY: bool>(
+        &mut self,
+        _taker: &Address,
+        _order: &BookOrder,
+        _base_decremented: u64,
+        _quote_decremented: u64,
+        _is_order_closed: bool,
+    ) -> DropsetResult {
+        Ok(())
+    }
+}
+
+/// The result of simulating a market order against a market's order book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimulatedFill {
+    pub base: u64,
+    pub quote: u64,
+    /// The total fee charged to the taker in quote atoms. This is not included in `quote`.
+    pub taker_fee: u64,
+    /// Whether or not the entire order size was filled.
+    pub is_fully_filled: bool,
+}
+
+impl From<AmountsFilled> for SimulatedFill {
+    fn from(amounts: AmountsFilled) -> Self {
+        SimulatedFill {
+            base: amounts.base,
+            quote: amounts.quote,
+            taker_fee: amounts.taker_fee,
+            is_fully_filled: amounts.is_fully_filled,
+        }
+    }
+}
+
+/// Simulates an immediate-or-cancel market order without mutating the market, walking the book
+/// with the same [`match_taker_order`] the program fills market orders with.
+///
+/// `IS_BUY` and `BASE_DENOM` are [`match_taker_order`]'s `is_buy` and `is_base`. Expired orders and
+/// orders outside the near side of the market's oracle band are skipped rather than removed, and
+/// the `taker`'s own orders are matched with the
+/// [`SelfTradePrevention::CancelTaking`] self-trade prevention mode, which stops matching.
+///
+/// Fails with [`DropsetError::OraclePriceStale`] if the market has an oracle and its cached price
+/// is stale at `unix_timestamp`.
+pub fn simulate_market_order<H, S, const IS_BUY: bool, const BASE_DENOM: bool>(
+    market: &Market<H, S>,
+    taker: &Address,
+    order_size: u64,
+    limit_price: u32,
+    unix_timestamp: u64,
+) -> Result<SimulatedFill, DropsetError>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    let taker_order = TakerOrder {
+        taker,
+        order_size,
+        limit_price,
+        time_in_force: TimeInForce::ImmediateOrCancel,
+        self_trade_prevention: SelfTradePrevention::CancelTaking,
+    };
+    walk_book::<_, IS_BUY, BASE_DENOM>(&mut SimulatedBook::new(market), taker_order, unix_timestamp)
+        .map(SimulatedFill::from)
+}
+
+/// The simulated outcome of a [`crate::instructions::DropsetInstruction::Swap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// The amount the taker pays: quote for a buy, base for a sell.
+    pub amount_in: u64,
+    /// The amount the taker receives: base for a buy, quote for a sell.
+    pub amount_out: u64,
+    /// The total fee charged to the taker in quote atoms.
+    pub taker_fee: u64,
+    /// Whether or not the entire swap amount was filled.
+    pub is_fully_filled: bool,
+}
+
+/// Simulates a [`crate::instructions::DropsetInstruction::Swap`] without mutating the market,
+/// returning the same amounts and failing with the same errors the program would, other than the
+/// minimum output and maximum input checks, which are left up to the caller.
+pub fn simulate_swap<H, S>(
+    market: &Market<H, S>,
+    taker: &Address,
+    amount: u64,
+    is_buy: bool,
+    is_exact_out: bool,
+    unix_timestamp: u64,
+) -> Result<SwapQuote, DropsetError>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    let is_base = is_swap_base_denominated(is_buy, is_exact_out);
+    market.check_taker_order(taker, is_base, amount)?;
+
+    let AmountsFilled {
+        base,
+        quote,
+        taker_fee,
+        is_fully_filled,
+        ..
+    } = match_taker_order(
+        &mut SimulatedBook::new(market),
+        swap_taker_order(taker, amount, is_buy),
+        is_buy,
+        is_base,
+        unix_timestamp,
+    )?;
+
+    let (amount_in, amount_out) = swap_amounts(is_buy, base, quote, taker_fee)?;
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        taker_fee,
+        is_fully_filled,
+    })
+}
+
+/// Returns the taker order a [`crate::instructions::DropsetInstruction::Swap`] of `amount` atoms
+/// matches with: an immediate-or-cancel order without a limit price that cancels the rest of the
+/// order when it would match against the taker's own resting order.
+#[inline(always)]
+pub fn swap_taker_order(taker: &Address, amount: u64, is_buy: bool) -> TakerOrder<'_> {
+    TakerOrder {
+        taker,
+        order_size: amount,
+        limit_price: if is_buy {
+            ENCODED_PRICE_INFINITY
+        } else {
+            ENCODED_PRICE_ZERO
+        },
+        time_in_force: TimeInForce::ImmediateOrCancel,
+        self_trade_prevention: SelfTradePrevention::CancelTaking,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_asset_fill_amount_rounds_down() {
+        // Filling 1/3 of an order with 10 counter atoms remaining matches 3 of them.
+        assert_eq!(counter_asset_fill_amount(1, 3, 10), Ok(3));
+        assert_eq!(counter_asset_fill_amount(3, 3, 10), Ok(10));
+        assert_eq!(
+            counter_asset_fill_amount(1, 0, 10),
+            Err(DropsetError::AmountCannotBeZero)
+        );
+    }
+
+    #[test]
+    fn swap_denomination_and_amounts() {
+        assert!(!is_swap_base_denominated(true, false));
+        assert!(is_swap_base_denominated(true, true));
+        assert!(is_swap_base_denominated(false, false));
+        assert!(!is_swap_base_denominated(false, true));
+
+        // A buy pays the taker fee on top of the quote filled and a sell has it deducted.
+        assert_eq!(swap_amounts(true, 100, 50, 2), Ok((52, 100)));
+        assert_eq!(swap_amounts(false, 100, 50, 2), Ok((100, 48)));
+        assert_eq!(
+            swap_amounts(false, 100, 1, 2),
+            Err(DropsetError::ArithmeticUnderflow)
+        );
+    }
+
+    #[test]
+    fn checked_swap_amounts_min_output() {
+        // A buy receives base and a sell receives quote minus the taker fee.
+        assert_eq!(
+            checked_swap_amounts(true, 100, 50, 2, 100, u64::MAX),
+            Ok((52, 100))
+        );
+        assert_eq!(
+            checked_swap_amounts(true, 100, 50, 2, 101, u64::MAX),
+            Err(DropsetError::MinimumOutputNotMet)
+        );
+        assert_eq!(
+            checked_swap_amounts(false, 100, 50, 2, 48, u64::MAX),
+            Ok((100, 48))
+        );
+        assert_eq!(
+            checked_swap_amounts(false, 100, 50, 2, 49, u64::MAX),
+            Err(DropsetError::MinimumOutputNotMet)
+        );
+    }
+
+    #[test]
+    fn checked_swap_amounts_max_input() {
+        // A buy pays quote plus the taker fee and a sell pays base.
+        assert_eq!(checked_swap_amounts(true, 100, 50, 2, 0, 52), Ok((52, 100)));
+        assert_eq!(
+            checked_swap_amounts(true, 100, 50, 2, 0, 51),
+            Err(DropsetError::MaximumInputExceeded)
+        );
+        assert_eq!(
+            checked_swap_amounts(false, 100, 50, 2, 0, 100),
+            Ok((100, 48))
+        );
+        assert_eq!(
+            checked_swap_amounts(false, 100, 50, 2, 0, 99),
+            Err(DropsetError::MaximumInputExceeded)
+        );
+    }
+}
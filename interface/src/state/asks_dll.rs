@@ -20,6 +20,7 @@ use crate::{
             SectorIndex,
             NIL,
         },
+        sector_kind::SectorKind,
     },
 };
 
@@ -87,6 +88,8 @@ pub type AskOrdersLinkedList<'a> = LinkedList<'a, AskOrders>;
 /// Operations for the sorted, doubly linked list of sectors containing ask
 /// [`crate::state::order::Order`] payloads.
 impl LinkedListHeaderOperations for AskOrders {
+    const SECTOR_KIND: SectorKind = SectorKind::Ask;
+
     fn head(header: &MarketHeader) -> SectorIndex {
         header.asks_dll_head()
     }
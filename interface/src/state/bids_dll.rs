@@ -20,6 +20,7 @@ use crate::{
             SectorIndex,
             NIL,
         },
+        sector_kind::SectorKind,
     },
 };
 
@@ -87,6 +88,8 @@ pub type BidOrdersLinkedList<'a> = LinkedList<'a, BidOrders>;
 /// Operations for the sorted, doubly linked list of sectors containing bid
 /// [`crate::state::order::Order`] payloads.
 impl LinkedListHeaderOperations for BidOrders {
+    const SECTOR_KIND: SectorKind = SectorKind::Bid;
+
     fn head(header: &MarketHeader) -> SectorIndex {
         header.bids_dll_head()
     }
@@ -0,0 +1,47 @@
+//! See [`CompactionPhase`].
+
+use instruction_macros::u8_enum;
+
+u8_enum! {
+    /// The progress of a market's compaction, which may span multiple
+    /// [`crate::instructions::DropsetInstruction::CompactMarket`] instructions.
+    ///
+    /// A compaction relocates the live sectors past the truncation point by walking the seats, then
+    /// the bids, then the asks.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum CompactionPhase {
+        /// No compaction is in progress.
+        #[default]
+        Idle = 0,
+        /// Relocating seats.
+        Seats = 1,
+        /// Relocating bids.
+        Bids = 2,
+        /// Relocating asks.
+        Asks = 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instruction_macros::{
+        Pack,
+        Unpack,
+    };
+
+    use super::*;
+
+    #[test]
+    fn compaction_phase_round_trip() {
+        for phase in [
+            CompactionPhase::Idle,
+            CompactionPhase::Seats,
+            CompactionPhase::Bids,
+            CompactionPhase::Asks,
+        ] {
+            assert_eq!(CompactionPhase::unpack(&phase.pack()).unwrap(), phase);
+        }
+        assert!(CompactionPhase::unpack(&[4]).is_err());
+        assert!(CompactionPhase::unpack(&[]).is_err());
+    }
+}
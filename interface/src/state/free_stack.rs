@@ -17,6 +17,7 @@ use crate::{
             NIL,
             PAYLOAD_SIZE,
         },
+        sector_kind::SectorKind,
         transmutable::Transmutable,
     },
 };
@@ -26,13 +27,13 @@ use crate::{
 pub struct Stack<'a> {
     /// See [`MarketHeader`].
     header: &'a mut MarketHeader,
-    /// The slab of bytes where all sector data exists, where each sector is an untagged union
-    /// of (any possible sector type | FreePayload).
+    /// The slab of bytes where all sector data exists, where each sector is a union of (any
+    /// possible sector type | FreePayload) tagged by its [`SectorKind`].
     sectors: &'a mut [u8],
 }
 
 #[repr(transparent)]
-/// A free payload is the unused payload portion of the "free" variant of the untagged union of
+/// A free payload is the unused payload portion of the [`SectorKind::Free`] variant of the union of
 /// each sector type (market seat, market order, etc).
 /// Since a free sector only ever reads from the `next` field, it's not necessary to zero out the
 /// payload bytes and thus they should be considered garbage data.
@@ -69,7 +70,8 @@ impl<'a> Stack<'a> {
     }
 
     /// Push a sector at the sector index onto the stack as a free sector by zeroing out its data,
-    /// setting its `next` to the current `top`, and updating the stack `top`.
+    /// marking it as [`SectorKind::Free`], setting its `next` to the current `top`, and updating
+    /// the stack `top`.
     ///
     /// # Safety
     ///
@@ -79,6 +81,7 @@ impl<'a> Stack<'a> {
 
         let sector = unsafe { Sector::from_sector_index_mut(self.sectors, index) };
         sector.zero_out_payload();
+        sector.set_kind(SectorKind::Free);
 
         sector.set_next(curr_top);
         self.set_top(index);
@@ -159,6 +162,38 @@ impl<'a> Stack<'a> {
         Ok(free_index)
     }
 
+    /// Removes every free sector at or past the sector index `end` from the stack, keeping the
+    /// remaining free sectors in the same order.
+    ///
+    /// This should be called before truncating the sectors region at `end`, since the truncated
+    /// sectors can no longer be handed out by the stack.
+    pub fn retain_below(&mut self, end: SectorIndex) {
+        let mut prev_index = NIL;
+        let mut curr_index = self.top();
+
+        while curr_index != NIL {
+            // Safety: `curr_index` is non-NIL and per the free stack impl, must be in-bounds.
+            let next_index = unsafe { Sector::from_sector_index(self.sectors, curr_index) }.next();
+
+            if curr_index < end {
+                prev_index = curr_index;
+            } else {
+                match prev_index {
+                    NIL => self.set_top(next_index),
+                    // Safety: `prev_index` matched against non-NIL and came from the stack
+                    // directly.
+                    prev_index => unsafe {
+                        Sector::from_sector_index_mut(self.sectors, prev_index)
+                            .set_next(next_index);
+                    },
+                }
+                self.header.decrement_num_free_sectors();
+            }
+
+            curr_index = next_index;
+        }
+    }
+
     #[inline(always)]
     pub fn top(&self) -> SectorIndex {
         self.header.free_stack_top()
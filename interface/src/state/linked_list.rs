@@ -11,10 +11,15 @@ use crate::{
             NIL,
             PAYLOAD_SIZE,
         },
+        sector_kind::SectorKind,
+        transmutable::Transmutable,
     },
 };
 
 pub trait LinkedListHeaderOperations {
+    /// The kind of every sector in the list.
+    const SECTOR_KIND: SectorKind;
+
     fn head(header: &MarketHeader) -> SectorIndex;
 
     fn set_head(header: &mut MarketHeader, new_index: SectorIndex);
@@ -71,6 +76,7 @@ impl<'a, T: LinkedListHeaderOperations> LinkedList<'a, T> {
         // Create the new sector with the incoming payload. It has no `prev` and its `next` sector
         // is the current head.
         new_sector.set_payload(payload);
+        new_sector.set_kind(T::SECTOR_KIND);
         new_sector.set_prev(NIL);
         new_sector.set_next(head_index);
 
@@ -99,6 +105,7 @@ impl<'a, T: LinkedListHeaderOperations> LinkedList<'a, T> {
         // Create the new sector with the incoming payload. It has no `next` and its `prev` sector
         // is the current tail.
         new_sector.set_payload(payload);
+        new_sector.set_kind(T::SECTOR_KIND);
         new_sector.set_prev(tail_index);
         new_sector.set_next(NIL);
 
@@ -143,6 +150,7 @@ impl<'a, T: LinkedListHeaderOperations> LinkedList<'a, T> {
         new_sector.set_prev(prev_index);
         new_sector.set_next(next_index);
         new_sector.set_payload(payload);
+        new_sector.set_kind(T::SECTOR_KIND);
 
         if prev_index == NIL {
             // If `prev_index` is NIL, that means `next_index` was the head prior to this insertion,
@@ -194,6 +202,45 @@ impl<'a, T: LinkedListHeaderOperations> LinkedList<'a, T> {
         free_stack.push_free_sector(index);
     }
 
+    /// Moves the sector at the non-NIL sector `index` to the sector at `new_index` by copying its
+    /// bytes, then re-links its neighbors (or the list's head and tail) to the new index.
+    ///
+    /// The sector at `new_index` should be a free sector that was already popped from the free
+    /// stack. The sector at `index` isn't freed and should be considered garbage data afterwards.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees `index` and `new_index` are in-bounds.
+    pub unsafe fn relocate(&mut self, index: SectorIndex, new_index: SectorIndex) {
+        let offset = index as usize * Sector::LEN;
+        self.sectors.copy_within(
+            offset..offset + Sector::LEN,
+            new_index as usize * Sector::LEN,
+        );
+
+        let (prev_index, next_index) = {
+            // Safety: Caller guarantees `new_index` is in-bounds.
+            let sector = unsafe { Sector::from_sector_index(self.sectors, new_index) };
+            (sector.prev(), sector.next())
+        };
+
+        match prev_index {
+            NIL => T::set_head(self.header, new_index),
+            // Safety: `prev_index` matched against non-NIL and came from a sector directly.
+            prev_index => unsafe {
+                Sector::from_sector_index_mut(self.sectors, prev_index).set_next(new_index);
+            },
+        }
+
+        match next_index {
+            NIL => T::set_tail(self.header, new_index),
+            // Safety: `next_index` matched against non-NIL and came from a sector directly.
+            next_index => unsafe {
+                Sector::from_sector_index_mut(self.sectors, next_index).set_prev(new_index);
+            },
+        }
+    }
+
     pub fn iter(&self) -> LinkedListIter<'_> {
         LinkedListIter {
             curr: T::head(self.header),
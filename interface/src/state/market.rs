@@ -1,18 +1,34 @@
 //! The top-level market structure tying together header, seats, and
 //! storage sectors into a unified on-chain representation.
 
-use crate::state::{
-    asks_dll::AskOrdersLinkedList,
-    bids_dll::BidOrdersLinkedList,
-    free_stack::Stack,
-    linked_list::LinkedListIter,
-    market_header::{
-        MarketHeader,
-        MARKET_ACCOUNT_DISCRIMINANT,
+use solana_address::{
+    address_eq,
+    Address,
+};
+
+use crate::{
+    error::{
+        DropsetError,
+        DropsetResult,
+    },
+    state::{
+        asks_dll::AskOrdersLinkedList,
+        bids_dll::BidOrdersLinkedList,
+        free_stack::Stack,
+        linked_list::LinkedListIter,
+        market_header::{
+            MarketHeader,
+            MARKET_ACCOUNT_DISCRIMINANT,
+        },
+        market_seat::MarketSeat,
+        seat_delegate::SeatDelegate,
+        seats_dll::SeatsLinkedList,
+        sector::{
+            Sector,
+            SECTOR_SIZE,
+        },
+        transmutable::Transmutable,
     },
-    seats_dll::SeatsLinkedList,
-    sector::SECTOR_SIZE,
-    transmutable::Transmutable,
 };
 
 pub struct Market<Header, SectorBytes> {
@@ -134,4 +150,54 @@ impl<H: AsRef<MarketHeader>, S: AsRef<[u8]>> Market<H, S> {
     pub fn is_initialized(&self) -> bool {
         self.header.as_ref().discriminant() == MARKET_ACCOUNT_DISCRIMINANT
     }
+
+    /// Returns the seat's delegate, if it has one.
+    #[inline(always)]
+    pub fn seat_delegate(&self, seat: &MarketSeat) -> Option<&SeatDelegate> {
+        if !seat.has_delegate() {
+            return None;
+        }
+        let index = seat.delegate_sector();
+        Sector::check_in_bounds(self.sectors.as_ref(), index).ok()?;
+        // Safety: The delegate sector index was just verified as in-bounds.
+        let sector = unsafe { Sector::from_sector_index(self.sectors.as_ref(), index) };
+        Some(sector.load_payload::<SeatDelegate>())
+    }
+
+    /// Checks that `user` may take liquidity without a seat, i.e. that the market isn't
+    /// permissioned or `user` has an allowlisted seat.
+    ///
+    /// Seats are sorted by user address, so the scan stops at the first seat that sorts after
+    /// `user`.
+    pub fn check_trader_allowlisted(&self, user: &Address) -> DropsetResult {
+        let header = self.header.as_ref();
+        if !header.is_permissioned() {
+            return Ok(());
+        }
+        for (_, sector) in self.iter_seats() {
+            let seat = sector.load_payload::<MarketSeat>();
+            if address_eq(user, &seat.user) {
+                return header.check_seat_allowlisted(seat);
+            }
+            if user < &seat.user {
+                break;
+            }
+        }
+        Err(DropsetError::TraderNotAllowlisted)
+    }
+
+    /// Checks that `taker` may fill a taker order of `order_size` atoms without a seat: the market
+    /// is active, `taker` is allowlisted and the order size is valid for its denomination.
+    #[inline(always)]
+    pub fn check_taker_order(
+        &self,
+        taker: &Address,
+        is_base: bool,
+        order_size: u64,
+    ) -> DropsetResult {
+        let header = self.header.as_ref();
+        header.check_is_active()?;
+        self.check_trader_allowlisted(taker)?;
+        header.check_order_size(is_base, order_size)
+    }
 }
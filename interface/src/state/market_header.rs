@@ -1,5 +1,9 @@
 //! See [`MarketHeader`].
 
+use price::{
+    OrderInfo,
+    MANTISSA_DIGITS_LOWER_BOUND,
+};
 use solana_address::Address;
 use static_assertions::const_assert_eq;
 
@@ -8,15 +12,27 @@ use crate::{
         DropsetError,
         DropsetResult,
     },
+    instructions::MarketParams,
     state::{
+        compaction_phase::CompactionPhase,
+        market_seat::MarketSeat,
+        market_status::MarketStatus,
+        oracle::{
+            OracleBand,
+            MAX_ORACLE_BAND_BPS,
+            NO_ORACLE,
+        },
         sector::{
             LeSectorIndex,
             SectorIndex,
             LE_NIL,
         },
         transmutable::Transmutable,
+        user_order_sectors::MAX_ORDERS,
+        LeU16,
         LeU32,
         LeU64,
+        U16_SIZE,
         U32_SIZE,
         U64_SIZE,
     },
@@ -24,6 +40,16 @@ use crate::{
 
 pub const MARKET_ACCOUNT_DISCRIMINANT: u64 = 0xd00d00b00b00f00du64;
 
+/// The market authority stored for markets without an authority, either because none was set at
+/// registration or because it was renounced. This is the system program ID, which can never sign.
+pub const NO_MARKET_AUTHORITY: Address = Address::new_from_array([0; 32]);
+
+/// The denominator for fee rates expressed in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The max taker fee rate in basis points; i.e., 10%.
+pub const MAX_TAKER_FEE_BPS: u16 = 1_000;
+
 /// The lightweight header for each market account. This header contains metadata used to interpret
 /// a market's account data properly.
 ///
@@ -31,8 +57,9 @@ pub const MARKET_ACCOUNT_DISCRIMINANT: u64 = 0xd00d00b00b00f00du64;
 /// dynamically sized `sectors` region stored as raw bytes: `&[u8]`.
 ///
 /// The metadata stored in the market header is central to interpreting the structures contained
-/// within the market’s `sectors` bytes. This region acts as an untagged union of data structures
-/// that share a common iterable layout, where each item is a sector with some payload type `T`.
+/// within the market’s `sectors` bytes. This region acts as a union of data structures that share a
+/// common iterable layout, where each item is a sector with some payload type `T` tagged by its
+/// [`crate::state::sector_kind::SectorKind`].
 ///
 /// For example, [`MarketHeader::free_stack_top`] exposes the index of the top sector in the free
 /// stack, allowing traversal of all available sectors. The payload type `T` in this case is
@@ -72,8 +99,56 @@ pub struct MarketHeader {
     pub market_bump: u8,
     /// The u64 number of events as LE bytes.
     num_events: LeU64,
-    // Although not necessary, add extra padding to make this alignment 8.
-    _padding: [u8; 3],
+    /// The market authority, allowed to update the market's parameters. Markets without an
+    /// authority store [`NO_MARKET_AUTHORITY`].
+    pub authority: Address,
+    /// The address allowed to sweep the market's accrued protocol fees.
+    pub fee_authority: Address,
+    /// The u16 taker fee rate in basis points as LE bytes, charged on the quote filled.
+    taker_fee_bps: LeU16,
+    /// The u16 maker rebate rate in basis points as LE bytes, paid on the quote filled.
+    maker_rebate_bps: LeU16,
+    /// The u64 amount of accrued protocol fees in quote atoms as LE bytes.
+    protocol_fees: LeU64,
+    /// The market's [`MarketStatus`] as a u8.
+    status: u8,
+    /// The u32 price mantissa tick size as LE bytes.
+    tick_size: LeU32,
+    /// The u64 base lot size in base atoms as LE bytes.
+    base_lot_size: LeU64,
+    /// The u64 minimum order size in base atoms as LE bytes.
+    min_base_order_size: LeU64,
+    /// The u64 minimum order size in quote atoms as LE bytes.
+    min_quote_order_size: LeU64,
+    /// The max number of bids and the max number of asks each user can have open at once.
+    max_orders: u8,
+    /// Whether or not only allowlisted traders can register seats and take liquidity, as a
+    /// non-zero `u8` so that all bit patterns are valid.
+    is_permissioned: u8,
+    /// The price oracle used for the market's price bands. Markets without an oracle store
+    /// [`NO_ORACLE`].
+    pub oracle: Address,
+    /// The u16 max distance in basis points from the oracle price that orders can be posted or
+    /// filled at as LE bytes.
+    oracle_band_bps: LeU16,
+    /// The u32 max age in seconds of the cached oracle price before it's considered stale as LE
+    /// bytes.
+    oracle_max_staleness: LeU32,
+    /// The u32 cached encoded oracle price as LE bytes. Zero until the oracle price is first
+    /// updated.
+    oracle_price: LeU32,
+    /// The u64 unix timestamp in seconds at which the cached oracle price was published as LE
+    /// bytes.
+    oracle_publish_time: LeU64,
+    /// The market's [`CompactionPhase`] as a u8.
+    compaction_phase: u8,
+    /// The u32 number of sectors the in-progress compaction truncates the sectors region to as LE
+    /// bytes. Only meaningful while compacting.
+    compaction_end: LeSectorIndex,
+    /// The u32 sector index of the next sector the in-progress compaction visits in the current
+    /// phase's list as LE bytes. Only meaningful while compacting.
+    compaction_cursor: LeSectorIndex,
+    _padding: [u8; 5],
 }
 
 // Safety:
@@ -84,23 +159,43 @@ pub struct MarketHeader {
 unsafe impl Transmutable for MarketHeader {
     #[allow(clippy::identity_op)]
     const LEN: usize = 0
-    /* discriminant */     + size_of::<LeU64>()
-    /* num_seats */        + size_of::<LeU32>()
-    /* num_bids */         + size_of::<LeU32>()
-    /* num_asks */         + size_of::<LeU32>()
-    /* num_free_sectors */ + size_of::<LeU32>()
-    /* free_stack_top */   + size_of::<LeSectorIndex>()
-    /* seats_dll_head */   + size_of::<LeSectorIndex>()
-    /* seats_dll_tail */   + size_of::<LeSectorIndex>()
-    /* bids_dll_head */    + size_of::<LeSectorIndex>()
-    /* bids_dll_tail */    + size_of::<LeSectorIndex>()
-    /* asks_dll_head */    + size_of::<LeSectorIndex>()
-    /* asks_dll_tail */    + size_of::<LeSectorIndex>()
-    /* base_mint */        + size_of::<Address>()
-    /* quote_mint */       + size_of::<Address>()
-    /* market_bump */      + size_of::<u8>()
-    /* num_events */       + size_of::<LeU64>()
-    /* _padding */         + size_of::<[u8; 3]>();
+    /* discriminant */         + size_of::<LeU64>()
+    /* num_seats */            + size_of::<LeU32>()
+    /* num_bids */             + size_of::<LeU32>()
+    /* num_asks */             + size_of::<LeU32>()
+    /* num_free_sectors */     + size_of::<LeU32>()
+    /* free_stack_top */       + size_of::<LeSectorIndex>()
+    /* seats_dll_head */       + size_of::<LeSectorIndex>()
+    /* seats_dll_tail */       + size_of::<LeSectorIndex>()
+    /* bids_dll_head */        + size_of::<LeSectorIndex>()
+    /* bids_dll_tail */        + size_of::<LeSectorIndex>()
+    /* asks_dll_head */        + size_of::<LeSectorIndex>()
+    /* asks_dll_tail */        + size_of::<LeSectorIndex>()
+    /* base_mint */            + size_of::<Address>()
+    /* quote_mint */           + size_of::<Address>()
+    /* market_bump */          + size_of::<u8>()
+    /* num_events */           + size_of::<LeU64>()
+    /* authority */            + size_of::<Address>()
+    /* fee_authority */        + size_of::<Address>()
+    /* taker_fee_bps */        + size_of::<LeU16>()
+    /* maker_rebate_bps */     + size_of::<LeU16>()
+    /* protocol_fees */        + size_of::<LeU64>()
+    /* status */               + size_of::<u8>()
+    /* tick_size */            + size_of::<LeU32>()
+    /* base_lot_size */        + size_of::<LeU64>()
+    /* min_base_order_size */  + size_of::<LeU64>()
+    /* min_quote_order_size */ + size_of::<LeU64>()
+    /* max_orders */           + size_of::<u8>()
+    /* is_permissioned */      + size_of::<u8>()
+    /* oracle */               + size_of::<Address>()
+    /* oracle_band_bps */      + size_of::<LeU16>()
+    /* oracle_max_staleness */ + size_of::<LeU32>()
+    /* oracle_price */         + size_of::<LeU32>()
+    /* oracle_publish_time */  + size_of::<LeU64>()
+    /* compaction_phase */     + size_of::<u8>()
+    /* compaction_end */       + size_of::<LeSectorIndex>()
+    /* compaction_cursor */    + size_of::<LeSectorIndex>()
+    /* _padding */             + size_of::<[u8; 5]>();
 
     fn validate_bit_patterns(_bytes: &[u8]) -> DropsetResult {
         // All bit patterns are valid: no enums, bools, or other types with invalid states.
@@ -110,6 +205,7 @@ unsafe impl Transmutable for MarketHeader {
 
 const_assert_eq!(MarketHeader::LEN, size_of::<MarketHeader>());
 const_assert_eq!(align_of::<MarketHeader>(), 1);
+const_assert_eq!(MarketHeader::LEN % 8, 0);
 
 /// Helper macro to implement a getter + wrapping add/sub increment/decrement methods for a
 /// `[u8; 4]` field. The field itself represents a u32 counter field for the number of elements
@@ -196,6 +292,10 @@ impl MarketHeader {
 
     impl_get_set_sector_index_field!(asks_dll_tail);
 
+    impl_get_set_sector_index_field!(compaction_end);
+
+    impl_get_set_sector_index_field!(compaction_cursor);
+
     /// Initializes market header data to the header destination pointer with a `core::ptr::write`.
     ///
     /// # Safety
@@ -209,6 +309,8 @@ impl MarketHeader {
         market_bump: u8,
         base_mint: &Address,
         quote_mint: &Address,
+        authority: &Address,
+        params: &MarketParams,
     ) {
         let header = MarketHeader {
             discriminant: MARKET_ACCOUNT_DISCRIMINANT.to_le_bytes(),
@@ -227,7 +329,27 @@ impl MarketHeader {
             quote_mint: *quote_mint,
             market_bump,
             num_events: [0; U64_SIZE],
-            _padding: [0; 3],
+            authority: *authority,
+            fee_authority: params.fee_authority,
+            taker_fee_bps: params.taker_fee_bps.to_le_bytes(),
+            maker_rebate_bps: params.maker_rebate_bps.to_le_bytes(),
+            protocol_fees: [0; U64_SIZE],
+            status: MarketStatus::Active as u8,
+            tick_size: params.tick_size.to_le_bytes(),
+            base_lot_size: params.base_lot_size.to_le_bytes(),
+            min_base_order_size: params.min_base_order_size.to_le_bytes(),
+            min_quote_order_size: params.min_quote_order_size.to_le_bytes(),
+            max_orders: params.max_orders,
+            is_permissioned: 0,
+            oracle: NO_ORACLE,
+            oracle_band_bps: [0; U16_SIZE],
+            oracle_max_staleness: [0; U32_SIZE],
+            oracle_price: [0; U32_SIZE],
+            oracle_publish_time: [0; U64_SIZE],
+            compaction_phase: CompactionPhase::Idle as u8,
+            compaction_end: LE_NIL,
+            compaction_cursor: LE_NIL,
+            _padding: [0; 5],
         };
         core::ptr::write(header_dst_ptr, header);
     }
@@ -245,6 +367,12 @@ impl MarketHeader {
         u64::from_le_bytes(self.discriminant)
     }
 
+    /// Zeroes the discriminant so the account is no longer recognized as an initialized market.
+    #[inline(always)]
+    pub fn clear_discriminant(&mut self) {
+        self.discriminant = [0; U64_SIZE];
+    }
+
     #[inline(always)]
     pub fn num_events(&self) -> u64 {
         u64::from_le_bytes(self.num_events)
@@ -254,4 +382,649 @@ impl MarketHeader {
     pub fn increment_num_events_by(&mut self, amount: u64) {
         self.num_events = (self.num_events().saturating_add(amount)).to_le_bytes();
     }
+
+    #[inline(always)]
+    pub fn taker_fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.taker_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn maker_rebate_bps(&self) -> u16 {
+        u16::from_le_bytes(self.maker_rebate_bps)
+    }
+
+    #[inline(always)]
+    pub fn protocol_fees(&self) -> u64 {
+        u64::from_le_bytes(self.protocol_fees)
+    }
+
+    #[inline(always)]
+    pub fn set_protocol_fees(&mut self, amount: u64) {
+        self.protocol_fees = amount.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn tick_size(&self) -> u32 {
+        u32::from_le_bytes(self.tick_size)
+    }
+
+    #[inline(always)]
+    pub fn base_lot_size(&self) -> u64 {
+        u64::from_le_bytes(self.base_lot_size)
+    }
+
+    #[inline(always)]
+    pub fn min_base_order_size(&self) -> u64 {
+        u64::from_le_bytes(self.min_base_order_size)
+    }
+
+    #[inline(always)]
+    pub fn min_quote_order_size(&self) -> u64 {
+        u64::from_le_bytes(self.min_quote_order_size)
+    }
+
+    #[inline(always)]
+    pub fn max_orders(&self) -> u8 {
+        self.max_orders
+    }
+
+    #[inline(always)]
+    pub fn status(&self) -> MarketStatus {
+        // The status is only ever written from a valid `MarketStatus`, but fall back to the most
+        // restrictive status just in case.
+        MarketStatus::try_from(self.status).unwrap_or(MarketStatus::Paused)
+    }
+
+    /// Sets the market's status. A closed market's status can never be changed, and a market must
+    /// stay paused while it's being compacted.
+    #[inline(always)]
+    pub fn set_status(&mut self, status: MarketStatus) -> DropsetResult {
+        if self.status() == MarketStatus::Closed && status != MarketStatus::Closed {
+            return Err(DropsetError::MarketClosed);
+        }
+        if self.is_compacting() && status != MarketStatus::Paused {
+            return Err(DropsetError::CompactionInProgress);
+        }
+        self.status = status as u8;
+        Ok(())
+    }
+
+    /// Checks that the market is active, i.e., orders can be posted and filled.
+    #[inline(always)]
+    pub fn check_is_active(&self) -> DropsetResult {
+        if !self.status().is_active() {
+            return Err(DropsetError::MarketNotActive);
+        }
+        Ok(())
+    }
+
+    /// Checks that users can cancel their orders, withdraw and close their seats.
+    #[inline(always)]
+    pub fn check_allows_exits(&self) -> DropsetResult {
+        if !self.status().allows_exits() {
+            return Err(DropsetError::MarketPaused);
+        }
+        Ok(())
+    }
+
+    /// Checks that the market hasn't been permanently closed.
+    #[inline(always)]
+    pub fn check_not_closed(&self) -> DropsetResult {
+        if self.status() == MarketStatus::Closed {
+            return Err(DropsetError::MarketClosed);
+        }
+        Ok(())
+    }
+
+    /// Checks that the market is paused, i.e., all user instructions are rejected.
+    #[inline(always)]
+    pub fn check_is_paused(&self) -> DropsetResult {
+        if self.status() != MarketStatus::Paused {
+            return Err(DropsetError::MarketNotPaused);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn compaction_phase(&self) -> CompactionPhase {
+        // The phase is only ever written from a valid `CompactionPhase`, but fall back to the
+        // first phase just in case, which restarts the walk without skipping any sectors.
+        CompactionPhase::try_from(self.compaction_phase).unwrap_or(CompactionPhase::Seats)
+    }
+
+    #[inline(always)]
+    pub fn set_compaction_phase(&mut self, phase: CompactionPhase) {
+        self.compaction_phase = phase as u8;
+    }
+
+    /// Whether a compaction has started but not yet relocated every live sector past its
+    /// truncation point.
+    #[inline(always)]
+    pub fn is_compacting(&self) -> bool {
+        self.compaction_phase() != CompactionPhase::Idle
+    }
+
+    /// Checks that the market isn't being compacted, for instructions that allocate or free
+    /// sectors outside of the compaction itself.
+    #[inline(always)]
+    pub fn check_not_compacting(&self) -> DropsetResult {
+        if self.is_compacting() {
+            return Err(DropsetError::CompactionInProgress);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn has_authority(&self) -> bool {
+        self.authority != NO_MARKET_AUTHORITY
+    }
+
+    #[inline(always)]
+    pub fn is_permissioned(&self) -> bool {
+        self.is_permissioned != 0
+    }
+
+    /// Sets whether or not the market is permissioned. This is only set once at registration.
+    #[inline(always)]
+    pub fn set_permissioned(&mut self, is_permissioned: bool) {
+        self.is_permissioned = is_permissioned as u8;
+    }
+
+    /// Checks that a seat's user may register and take liquidity, i.e. that the market isn't
+    /// permissioned or the seat is allowlisted.
+    #[inline(always)]
+    pub fn check_seat_allowlisted(&self, seat: &MarketSeat) -> DropsetResult {
+        if self.is_permissioned() && !seat.is_allowlisted() {
+            return Err(DropsetError::TraderNotAllowlisted);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn has_oracle(&self) -> bool {
+        self.oracle != NO_ORACLE
+    }
+
+    #[inline(always)]
+    pub fn oracle_band_bps(&self) -> u16 {
+        u16::from_le_bytes(self.oracle_band_bps)
+    }
+
+    #[inline(always)]
+    pub fn oracle_max_staleness(&self) -> u32 {
+        u32::from_le_bytes(self.oracle_max_staleness)
+    }
+
+    #[inline(always)]
+    pub fn oracle_price(&self) -> u32 {
+        u32::from_le_bytes(self.oracle_price)
+    }
+
+    #[inline(always)]
+    pub fn oracle_publish_time(&self) -> u64 {
+        u64::from_le_bytes(self.oracle_publish_time)
+    }
+
+    /// Validates and then sets the market's price oracle and price band, clearing the cached oracle
+    /// price. Setting the oracle to [`NO_ORACLE`] disables the price bands.
+    #[inline(always)]
+    pub fn set_oracle(
+        &mut self,
+        oracle: &Address,
+        band_bps: u16,
+        max_staleness: u32,
+    ) -> DropsetResult {
+        if *oracle != NO_ORACLE
+            && (band_bps == 0 || band_bps > MAX_ORACLE_BAND_BPS || max_staleness == 0)
+        {
+            return Err(DropsetError::InvalidOracleBand);
+        }
+        self.oracle = *oracle;
+        self.oracle_band_bps = band_bps.to_le_bytes();
+        self.oracle_max_staleness = max_staleness.to_le_bytes();
+        self.set_oracle_price(0, 0);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_oracle_price(&mut self, encoded_price: u32, publish_time: u64) {
+        self.oracle_price = encoded_price.to_le_bytes();
+        self.oracle_publish_time = publish_time.to_le_bytes();
+    }
+
+    /// Returns the market's current oracle price band, or `None` if the market has no oracle.
+    ///
+    /// Fails if the cached oracle price was never updated or is older than the max staleness, so
+    /// that orders are never checked against an outdated price.
+    #[inline(always)]
+    pub fn oracle_band(&self, unix_timestamp: u64) -> Result<Option<OracleBand>, DropsetError> {
+        if !self.has_oracle() {
+            return Ok(None);
+        }
+        let oracle_price = self.oracle_price();
+        let age = unix_timestamp.saturating_sub(self.oracle_publish_time());
+        if oracle_price == 0 || age > self.oracle_max_staleness() as u64 {
+            return Err(DropsetError::OraclePriceStale);
+        }
+        Ok(Some(OracleBand {
+            oracle_price,
+            band_bps: self.oracle_band_bps(),
+        }))
+    }
+
+    /// Checks that an encoded price is within the market's oracle price band, if it has one.
+    #[inline(always)]
+    pub fn check_oracle_band(&self, encoded_price: u32, unix_timestamp: u64) -> DropsetResult {
+        match self.oracle_band(unix_timestamp)? {
+            Some(band) => band.check(encoded_price),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the market's current configurable parameters.
+    #[inline(always)]
+    pub fn params(&self) -> MarketParams {
+        MarketParams {
+            fee_authority: self.fee_authority,
+            taker_fee_bps: self.taker_fee_bps(),
+            maker_rebate_bps: self.maker_rebate_bps(),
+            tick_size: self.tick_size(),
+            base_lot_size: self.base_lot_size(),
+            min_base_order_size: self.min_base_order_size(),
+            min_quote_order_size: self.min_quote_order_size(),
+            max_orders: self.max_orders(),
+        }
+    }
+
+    /// Validates and then sets the market's configurable parameters.
+    #[inline(always)]
+    pub fn set_params(&mut self, params: &MarketParams) -> DropsetResult {
+        Self::check_params(params)?;
+        self.fee_authority = params.fee_authority;
+        self.taker_fee_bps = params.taker_fee_bps.to_le_bytes();
+        self.maker_rebate_bps = params.maker_rebate_bps.to_le_bytes();
+        self.tick_size = params.tick_size.to_le_bytes();
+        self.base_lot_size = params.base_lot_size.to_le_bytes();
+        self.min_base_order_size = params.min_base_order_size.to_le_bytes();
+        self.min_quote_order_size = params.min_quote_order_size.to_le_bytes();
+        self.max_orders = params.max_orders;
+        Ok(())
+    }
+
+    /// Checks that the market parameters are valid.
+    #[inline(always)]
+    pub fn check_params(params: &MarketParams) -> DropsetResult {
+        Self::check_fee_rates(params.taker_fee_bps, params.maker_rebate_bps)?;
+        // The tick size can't exceed the lowest valid price mantissa, otherwise there may be no
+        // valid price mantissa that is a multiple of it.
+        if params.tick_size == 0
+            || params.tick_size > MANTISSA_DIGITS_LOWER_BOUND
+            || params.base_lot_size == 0
+        {
+            return Err(DropsetError::InvalidMarketSizes);
+        }
+        if params.max_orders == 0 || params.max_orders > MAX_ORDERS {
+            return Err(DropsetError::InvalidMaxOrders);
+        }
+        Ok(())
+    }
+
+    /// Checks that an order to be posted on the book uses a price that is a multiple of the tick
+    /// size, a base amount that is a multiple of the base lot size, and meets the minimum order
+    /// sizes.
+    #[inline(always)]
+    pub fn check_order_info(&self, order_info: &OrderInfo) -> DropsetResult {
+        if order_info.encoded_price.price_mantissa() % self.tick_size() != 0 {
+            return Err(DropsetError::PriceNotMultipleOfTickSize);
+        }
+        self.check_base_order_size(order_info.base_atoms)?;
+        self.check_quote_order_size(order_info.quote_atoms)
+    }
+
+    /// Checks that a base order size is a multiple of the base lot size and meets the minimum base
+    /// order size.
+    #[inline(always)]
+    pub fn check_base_order_size(&self, base_atoms: u64) -> DropsetResult {
+        if base_atoms % self.base_lot_size() != 0 {
+            return Err(DropsetError::SizeNotMultipleOfLotSize);
+        }
+        if base_atoms < self.min_base_order_size() {
+            return Err(DropsetError::OrderSizeBelowMinimum);
+        }
+        Ok(())
+    }
+
+    /// Checks that a quote order size meets the minimum quote order size.
+    #[inline(always)]
+    pub fn check_quote_order_size(&self, quote_atoms: u64) -> DropsetResult {
+        if quote_atoms < self.min_quote_order_size() {
+            return Err(DropsetError::OrderSizeBelowMinimum);
+        }
+        Ok(())
+    }
+
+    /// Checks a taker order size against [`Self::check_base_order_size`] if `is_base`, and against
+    /// [`Self::check_quote_order_size`] otherwise.
+    #[inline(always)]
+    pub fn check_order_size(&self, is_base: bool, order_size: u64) -> DropsetResult {
+        if is_base {
+            self.check_base_order_size(order_size)
+        } else {
+            self.check_quote_order_size(order_size)
+        }
+    }
+
+    /// Checks that the taker fee doesn't exceed [`MAX_TAKER_FEE_BPS`] and that the maker rebate
+    /// doesn't exceed the taker fee, so that the protocol never pays out more than it collects.
+    #[inline(always)]
+    pub fn check_fee_rates(taker_fee_bps: u16, maker_rebate_bps: u16) -> DropsetResult {
+        if taker_fee_bps > MAX_TAKER_FEE_BPS || maker_rebate_bps > taker_fee_bps {
+            return Err(DropsetError::InvalidFeeRates);
+        }
+        Ok(())
+    }
+
+    /// Returns the taker fee for a fill of `quote_filled` quote atoms, rounded up.
+    #[inline(always)]
+    pub fn taker_fee(&self, quote_filled: u64) -> u64 {
+        let numerator = quote_filled as u128 * self.taker_fee_bps() as u128;
+        // The fee rate is always <= 100%, so the result always fits in a u64.
+        numerator.div_ceil(BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Returns the maker rebate for a fill of `quote_filled` quote atoms, rounded down.
+    ///
+    /// Since the rebate rate never exceeds the taker fee rate and the taker fee is rounded up, the
+    /// maker rebate never exceeds the taker fee for the same fill.
+    #[inline(always)]
+    pub fn maker_rebate(&self, quote_filled: u64) -> u64 {
+        let numerator = quote_filled as u128 * self.maker_rebate_bps() as u128;
+        // The rebate rate is always <= 100%, so the result always fits in a u64.
+        (numerator / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+
+    use price::{
+        EncodedPrice,
+        BIAS,
+    };
+
+    use super::*;
+
+    fn header_with_params(params: &MarketParams) -> MarketHeader {
+        let mut header = MaybeUninit::<MarketHeader>::uninit();
+        // Safety: `header` is valid for writes of `MarketHeader::LEN` bytes and is fully
+        // initialized by `MarketHeader::init`.
+        unsafe {
+            MarketHeader::init(
+                header.as_mut_ptr(),
+                0,
+                &Address::new_from_array([1; 32]),
+                &Address::new_from_array([2; 32]),
+                &Address::new_from_array([3; 32]),
+                params,
+            );
+            header.assume_init()
+        }
+    }
+
+    fn header_with_fees(taker_fee_bps: u16, maker_rebate_bps: u16) -> MarketHeader {
+        header_with_params(&MarketParams {
+            fee_authority: Address::new_from_array([4; 32]),
+            taker_fee_bps,
+            maker_rebate_bps,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn fee_rates() {
+        assert!(MarketHeader::check_fee_rates(0, 0).is_ok());
+        assert!(MarketHeader::check_fee_rates(MAX_TAKER_FEE_BPS, MAX_TAKER_FEE_BPS).is_ok());
+        assert_eq!(
+            MarketHeader::check_fee_rates(MAX_TAKER_FEE_BPS + 1, 0),
+            Err(DropsetError::InvalidFeeRates)
+        );
+        assert_eq!(
+            MarketHeader::check_fee_rates(5, 6),
+            Err(DropsetError::InvalidFeeRates)
+        );
+    }
+
+    #[test]
+    fn update_params() {
+        let mut header = header_with_fees(5, 2);
+        let params = MarketParams {
+            fee_authority: Address::new_from_array([5; 32]),
+            taker_fee_bps: 10,
+            maker_rebate_bps: 10,
+            tick_size: 1_000,
+            base_lot_size: 100,
+            min_base_order_size: 0,
+            min_quote_order_size: 0,
+            max_orders: 8,
+        };
+        header.set_params(&params).unwrap();
+        assert_eq!(header.params(), params);
+        assert_eq!(header.max_orders(), 8);
+
+        // Invalid params are rejected and leave the current params untouched.
+        let invalid_fees = MarketParams {
+            fee_authority: Address::new_from_array([6; 32]),
+            taker_fee_bps: 1,
+            maker_rebate_bps: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            header.set_params(&invalid_fees),
+            Err(DropsetError::InvalidFeeRates)
+        );
+        for (tick_size, base_lot_size) in [(0, 1), (MANTISSA_DIGITS_LOWER_BOUND + 1, 1), (1, 0)] {
+            let invalid_sizes = MarketParams {
+                tick_size,
+                base_lot_size,
+                ..Default::default()
+            };
+            assert_eq!(
+                header.set_params(&invalid_sizes),
+                Err(DropsetError::InvalidMarketSizes)
+            );
+        }
+        for max_orders in [0, MAX_ORDERS + 1] {
+            let invalid_max_orders = MarketParams {
+                max_orders,
+                ..Default::default()
+            };
+            assert_eq!(
+                header.set_params(&invalid_max_orders),
+                Err(DropsetError::InvalidMaxOrders)
+            );
+        }
+        assert_eq!(header.params(), params);
+    }
+
+    #[test]
+    fn order_sizes() {
+        let header = header_with_params(&MarketParams {
+            tick_size: 1_000,
+            base_lot_size: 100,
+            min_base_order_size: 500,
+            min_quote_order_size: 50,
+            ..Default::default()
+        });
+        let order_info = |price_mantissa: u32, base_atoms: u64, quote_atoms: u64| OrderInfo {
+            encoded_price: EncodedPrice::new(price_mantissa.try_into().unwrap(), BIAS),
+            base_atoms,
+            quote_atoms,
+        };
+
+        assert!(header
+            .check_order_info(&order_info(12_345_000, 500, 50))
+            .is_ok());
+        assert_eq!(
+            header.check_order_info(&order_info(12_345_600, 500, 50)),
+            Err(DropsetError::PriceNotMultipleOfTickSize)
+        );
+        assert_eq!(
+            header.check_order_info(&order_info(12_345_000, 550, 50)),
+            Err(DropsetError::SizeNotMultipleOfLotSize)
+        );
+        assert_eq!(
+            header.check_order_info(&order_info(12_345_000, 400, 50)),
+            Err(DropsetError::OrderSizeBelowMinimum)
+        );
+        assert_eq!(
+            header.check_order_info(&order_info(12_345_000, 500, 49)),
+            Err(DropsetError::OrderSizeBelowMinimum)
+        );
+    }
+
+    #[test]
+    fn seat_allowlisting() {
+        let mut header = header_with_fees(0, 0);
+        let mut seat = MarketSeat::new(Address::new_from_array([5; 32]), 0, 0);
+        assert!(!seat.is_allowlisted());
+
+        // Every seat is allowed on a market that isn't permissioned.
+        assert!(header.check_seat_allowlisted(&seat).is_ok());
+
+        header.set_permissioned(true);
+        assert_eq!(
+            header.check_seat_allowlisted(&seat),
+            Err(DropsetError::TraderNotAllowlisted)
+        );
+        seat.set_allowlisted(true);
+        assert!(header.check_seat_allowlisted(&seat).is_ok());
+    }
+
+    #[test]
+    fn oracle_band() {
+        let mut header = header_with_fees(0, 0);
+        let oracle = Address::new_from_array([5; 32]);
+        let price = EncodedPrice::new(10_000_000.try_into().unwrap(), BIAS).as_u32();
+        let far_price = EncodedPrice::new(20_000_000.try_into().unwrap(), BIAS).as_u32();
+
+        // Markets without an oracle have no price bands.
+        assert!(!header.has_oracle());
+        assert_eq!(header.oracle_band(100), Ok(None));
+        assert!(header.check_oracle_band(far_price, 100).is_ok());
+
+        for (band_bps, max_staleness) in [(0, 60), (MAX_ORACLE_BAND_BPS + 1, 60), (100, 0)] {
+            assert_eq!(
+                header.set_oracle(&oracle, band_bps, max_staleness),
+                Err(DropsetError::InvalidOracleBand)
+            );
+        }
+        header.set_oracle(&oracle, 100, 60).unwrap();
+        assert!(header.has_oracle());
+
+        // The band can't be used until the oracle price is updated.
+        assert_eq!(header.oracle_band(100), Err(DropsetError::OraclePriceStale));
+
+        header.set_oracle_price(price, 100);
+        assert!(header.check_oracle_band(price, 160).is_ok());
+        assert_eq!(
+            header.check_oracle_band(far_price, 160),
+            Err(DropsetError::PriceOutsideOracleBand)
+        );
+        assert_eq!(
+            header.check_oracle_band(price, 161),
+            Err(DropsetError::OraclePriceStale)
+        );
+
+        // Removing the oracle disables the price bands and clears the cached price.
+        header.set_oracle(&NO_ORACLE, 0, 0).unwrap();
+        assert!(header.check_oracle_band(far_price, 161).is_ok());
+        assert_eq!(header.oracle_price(), 0);
+    }
+
+    #[test]
+    fn market_status() {
+        let mut header = header_with_fees(0, 0);
+        assert_eq!(header.status(), MarketStatus::Active);
+        assert!(header.check_is_active().is_ok());
+        assert!(header.check_allows_exits().is_ok());
+        assert!(header.check_not_closed().is_ok());
+
+        header.set_status(MarketStatus::CancelOnly).unwrap();
+        assert_eq!(header.check_is_active(), Err(DropsetError::MarketNotActive));
+        assert!(header.check_allows_exits().is_ok());
+
+        header.set_status(MarketStatus::Paused).unwrap();
+        assert_eq!(header.check_is_active(), Err(DropsetError::MarketNotActive));
+        assert_eq!(header.check_allows_exits(), Err(DropsetError::MarketPaused));
+
+        // A closed market still allows exits but can never be reopened.
+        header.set_status(MarketStatus::Closed).unwrap();
+        assert_eq!(header.check_is_active(), Err(DropsetError::MarketNotActive));
+        assert!(header.check_allows_exits().is_ok());
+        assert_eq!(header.check_not_closed(), Err(DropsetError::MarketClosed));
+        assert_eq!(
+            header.set_status(MarketStatus::Active),
+            Err(DropsetError::MarketClosed)
+        );
+        assert_eq!(header.status(), MarketStatus::Closed);
+    }
+
+    #[test]
+    fn compaction_keeps_market_paused() {
+        let mut header = header_with_fees(0, 0);
+        assert!(!header.is_compacting());
+        assert!(header.check_not_compacting().is_ok());
+        assert_eq!(header.check_is_paused(), Err(DropsetError::MarketNotPaused));
+
+        header.set_status(MarketStatus::Paused).unwrap();
+        assert!(header.check_is_paused().is_ok());
+
+        // The market can't leave the paused status until the compaction is complete.
+        header.set_compaction_phase(CompactionPhase::Bids);
+        assert!(header.is_compacting());
+        assert_eq!(
+            header.check_not_compacting(),
+            Err(DropsetError::CompactionInProgress)
+        );
+        for status in [
+            MarketStatus::Active,
+            MarketStatus::CancelOnly,
+            MarketStatus::Closed,
+        ] {
+            assert_eq!(
+                header.set_status(status),
+                Err(DropsetError::CompactionInProgress)
+            );
+        }
+        assert_eq!(header.status(), MarketStatus::Paused);
+
+        header.set_compaction_phase(CompactionPhase::Idle);
+        header.set_status(MarketStatus::Active).unwrap();
+    }
+
+    #[test]
+    fn fee_rounding() {
+        let header = header_with_fees(5, 2);
+        assert_eq!(header.taker_fee_bps(), 5);
+        assert_eq!(header.maker_rebate_bps(), 2);
+        assert_eq!(header.protocol_fees(), 0);
+        assert!(header.has_authority());
+        assert!(!header.is_permissioned());
+
+        // 0.05% of 10_000 is exactly 5 and 0.02% of it is exactly 2.
+        assert_eq!(header.taker_fee(10_000), 5);
+        assert_eq!(header.maker_rebate(10_000), 2);
+
+        // The taker fee rounds up and the maker rebate rounds down.
+        assert_eq!(header.taker_fee(1), 1);
+        assert_eq!(header.maker_rebate(1), 0);
+        assert_eq!(header.taker_fee(0), 0);
+
+        // Max amounts don't overflow.
+        assert_eq!(
+            header.taker_fee(u64::MAX),
+            (u64::MAX as u128 * 5).div_ceil(10_000) as u64
+        );
+    }
 }
@@ -11,7 +11,11 @@ use crate::{
     state::{
         sector::{
             AllBitPatternsValid,
+            LeSectorIndex,
             Payload,
+            SectorIndex,
+            LE_NIL,
+            NIL,
             PAYLOAD_SIZE,
         },
         transmutable::Transmutable,
@@ -20,12 +24,21 @@ use crate::{
     },
 };
 
+/// The delegate reported for seats without one. No account can sign for the zero address.
+pub const NO_SEAT_DELEGATE: Address = Address::new_from_array([0; 32]);
+
 /// Represents a user's position within a market.
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MarketSeat {
     /// The user's public key.
     pub user: Address,
+    /// The [`SectorIndex`] of the seat's [`crate::state::seat_delegate::SeatDelegate`] as LE
+    /// bytes, or [`NIL`] if the seat has no delegate.
+    delegate_sector: LeSectorIndex,
+    /// Whether or not the market authority allowlisted the user, as a non-zero `u8` so that all
+    /// bit patterns are valid. Only checked on permissioned markets.
+    is_allowlisted: u8,
     /// The u64 amount of base the maker can withdraw as LE bytes.
     base_available: [u8; U64_SIZE],
     /// The u64 amount of quote the maker can withdraw as LE bytes.
@@ -39,12 +52,39 @@ impl MarketSeat {
     pub fn new(user: Address, base: u64, quote: u64) -> Self {
         MarketSeat {
             user,
+            delegate_sector: LE_NIL,
+            is_allowlisted: 0,
             base_available: base.to_le_bytes(),
             quote_available: quote.to_le_bytes(),
             user_order_sectors: UserOrderSectors::default(),
         }
     }
 
+    #[inline(always)]
+    pub fn delegate_sector(&self) -> SectorIndex {
+        u32::from_le_bytes(self.delegate_sector)
+    }
+
+    #[inline(always)]
+    pub fn set_delegate_sector(&mut self, index: SectorIndex) {
+        self.delegate_sector = index.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn has_delegate(&self) -> bool {
+        self.delegate_sector() != NIL
+    }
+
+    #[inline(always)]
+    pub fn is_allowlisted(&self) -> bool {
+        self.is_allowlisted != 0
+    }
+
+    #[inline(always)]
+    pub fn set_allowlisted(&mut self, is_allowlisted: bool) {
+        self.is_allowlisted = is_allowlisted as u8;
+    }
+
     #[inline(always)]
     pub fn base_available(&self) -> u64 {
         u64::from_le_bytes(self.base_available)
@@ -128,8 +168,12 @@ impl MarketSeat {
 // - `size_of` and `align_of` are checked below.
 // - All bit patterns are valid.
 unsafe impl Transmutable for MarketSeat {
-    const LEN: usize =
-        size_of::<Address>() + size_of::<u64>() + size_of::<u64>() + UserOrderSectors::LEN;
+    const LEN: usize = size_of::<Address>()
+        + size_of::<LeSectorIndex>()
+        + size_of::<u8>()
+        + size_of::<u64>()
+        + size_of::<u64>()
+        + UserOrderSectors::LEN;
 
     #[inline(always)]
     fn validate_bit_patterns(_bytes: &[u8]) -> crate::error::DropsetResult {
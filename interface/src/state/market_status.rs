@@ -0,0 +1,59 @@
+//! See [`MarketStatus`].
+
+use instruction_macros::u8_enum;
+
+u8_enum! {
+    /// The trading status of a market, set by the market authority.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum MarketStatus {
+        /// All instructions are allowed.
+        #[default]
+        Active = 0,
+        /// Orders can't be posted or filled, but users can still cancel orders, withdraw and
+        /// close their seats.
+        CancelOnly = 1,
+        /// All user instructions are rejected.
+        Paused = 2,
+        /// The market is permanently closed. Users can still cancel orders, withdraw and close
+        /// their seats, but the status can never be changed again.
+        Closed = 3,
+    }
+}
+
+impl MarketStatus {
+    /// Whether orders can be posted and filled.
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        *self == Self::Active
+    }
+
+    /// Whether users can cancel their orders, withdraw and close their seats.
+    #[inline(always)]
+    pub fn allows_exits(&self) -> bool {
+        *self != Self::Paused
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instruction_macros::{
+        Pack,
+        Unpack,
+    };
+
+    use super::*;
+
+    #[test]
+    fn market_status_round_trip() {
+        for status in [
+            MarketStatus::Active,
+            MarketStatus::CancelOnly,
+            MarketStatus::Paused,
+            MarketStatus::Closed,
+        ] {
+            assert_eq!(MarketStatus::unpack(&status.pack()).unwrap(), status);
+        }
+        assert!(MarketStatus::unpack(&[4]).is_err());
+        assert!(MarketStatus::unpack(&[]).is_err());
+    }
+}
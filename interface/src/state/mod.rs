@@ -3,14 +3,19 @@
 
 pub mod asks_dll;
 pub mod bids_dll;
+pub mod compaction_phase;
 pub mod free_stack;
 pub mod linked_list;
 pub mod market;
 pub mod market_header;
 pub mod market_seat;
+pub mod market_status;
+pub mod oracle;
 pub mod order;
+pub mod seat_delegate;
 pub mod seats_dll;
 pub mod sector;
+pub mod sector_kind;
 pub mod transmutable;
 pub mod user_order_sectors;
 
@@ -0,0 +1,267 @@
+//! See [`OraclePrice`] and [`OracleBand`].
+
+use core::cmp::Ordering;
+
+use price::{
+    split_encoded_price,
+    ValidatedPriceMantissa,
+};
+use solana_address::Address;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    error::{
+        DropsetError,
+        DropsetResult,
+    },
+    state::{
+        market_header::BPS_DENOMINATOR,
+        transmutable::Transmutable,
+        LeU32,
+        LeU64,
+    },
+};
+
+pub const ORACLE_PRICE_DISCRIMINANT: u64 = 0xd00d00b00b00cafeu64;
+
+/// The oracle stored for markets without one.
+pub const NO_ORACLE: Address = Address::new_from_array([0; 32]);
+
+/// The max oracle price band in basis points; i.e., 50%.
+pub const MAX_ORACLE_BAND_BPS: u16 = 5_000;
+
+/// The account data layout of a price oracle a market can reference for its price bands.
+///
+/// `dropset` doesn't publish prices itself. An oracle account can be owned by any program as long
+/// as its data starts with this layout, so it's up to the market authority to reference an oracle
+/// whose publisher they trust.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    /// The u64 oracle account's account discriminant as LE bytes.
+    discriminant: LeU64,
+    /// The u32 oracle price as LE bytes, encoded the same way as order prices.
+    encoded_price: LeU32,
+    _padding: [u8; 4],
+    /// The u64 unix timestamp in seconds at which the price was published as LE bytes.
+    publish_time: LeU64,
+}
+
+// Safety:
+//
+// - Stable layout with `#[repr(C)]`.
+// - `size_of` and `align_of` are checked below.
+// - All bit patterns are valid.
+unsafe impl Transmutable for OraclePrice {
+    #[allow(clippy::identity_op)]
+    const LEN: usize = 0
+    /* discriminant */  + size_of::<LeU64>()
+    /* encoded_price */ + size_of::<LeU32>()
+    /* _padding */      + size_of::<[u8; 4]>()
+    /* publish_time */  + size_of::<LeU64>();
+
+    fn validate_bit_patterns(_bytes: &[u8]) -> DropsetResult {
+        // All bit patterns are valid: no enums, bools, or other types with invalid states.
+        Ok(())
+    }
+}
+
+const_assert_eq!(OraclePrice::LEN, size_of::<OraclePrice>());
+const_assert_eq!(align_of::<OraclePrice>(), 1);
+
+impl OraclePrice {
+    pub fn new(encoded_price: u32, publish_time: u64) -> Self {
+        Self {
+            discriminant: ORACLE_PRICE_DISCRIMINANT.to_le_bytes(),
+            encoded_price: encoded_price.to_le_bytes(),
+            _padding: [0; 4],
+            publish_time: publish_time.to_le_bytes(),
+        }
+    }
+
+    /// Loads an oracle price from the start of an oracle account's data, checking that it's
+    /// initialized and that its price is a valid, non-zero encoded price.
+    #[inline(always)]
+    pub fn load_checked(data: &[u8]) -> Result<&Self, DropsetError> {
+        let bytes = data
+            .get(..Self::LEN)
+            .ok_or(DropsetError::InvalidOracleAccount)?;
+        let oracle_price = Self::load(bytes)?;
+        if u64::from_le_bytes(oracle_price.discriminant) != ORACLE_PRICE_DISCRIMINANT {
+            return Err(DropsetError::InvalidOracleAccount);
+        }
+        let (_, price_mantissa) = split_encoded_price(oracle_price.encoded_price());
+        ValidatedPriceMantissa::try_from(price_mantissa)
+            .map_err(|_| DropsetError::InvalidOracleAccount)?;
+        Ok(oracle_price)
+    }
+
+    #[inline(always)]
+    pub fn encoded_price(&self) -> u32 {
+        u32::from_le_bytes(self.encoded_price)
+    }
+
+    #[inline(always)]
+    pub fn publish_time(&self) -> u64 {
+        u64::from_le_bytes(self.publish_time)
+    }
+
+    /// This method is sound because:
+    ///
+    /// - `Self` is exactly `Self::LEN` bytes.
+    /// - Size and alignment are verified with const assertions.
+    /// - All fields are byte-safe, `Copy`, non-pointer/reference u8 arrays.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        unsafe { &*(self as *const Self as *const [u8; Self::LEN]) }
+    }
+}
+
+/// The range of prices within `band_bps` basis points of an oracle price, both given as encoded
+/// prices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleBand {
+    pub oracle_price: u32,
+    pub band_bps: u16,
+}
+
+impl OracleBand {
+    /// Compares an encoded price to the band: [`Ordering::Less`] if it's below the band's lower
+    /// bound, [`Ordering::Greater`] if it's above the band's upper bound and [`Ordering::Equal`] if
+    /// it's within the band, inclusive of its bounds.
+    ///
+    /// Both prices are decoded into their price mantissa and exponent. Price mantissas always have
+    /// the same number of digits, so prices whose exponents differ by more than one are over an
+    /// order of magnitude apart, which is always outside a band of at most
+    /// [`MAX_ORACLE_BAND_BPS`].
+    #[inline(always)]
+    pub fn compare(&self, encoded_price: u32) -> Ordering {
+        let (price_exponent, price_mantissa) = split_encoded_price(encoded_price);
+        let (oracle_exponent, oracle_mantissa) = split_encoded_price(self.oracle_price);
+
+        let (price, oracle) = match price_exponent as i16 - oracle_exponent as i16 {
+            0 => (price_mantissa as u64, oracle_mantissa as u64),
+            1 => (price_mantissa as u64 * 10, oracle_mantissa as u64),
+            -1 => (price_mantissa as u64, oracle_mantissa as u64 * 10),
+            difference if difference > 1 => return Ordering::Greater,
+            _ => return Ordering::Less,
+        };
+
+        // Mantissas have at most 8 digits, so none of these products overflow.
+        let band_bps = self.band_bps as u64;
+        let scaled_price = price * BPS_DENOMINATOR;
+        if scaled_price < oracle * (BPS_DENOMINATOR - band_bps) {
+            Ordering::Less
+        } else if scaled_price > oracle * (BPS_DENOMINATOR + band_bps) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Whether or not an encoded price is within the band, inclusive of its bounds.
+    #[inline(always)]
+    pub fn contains(&self, encoded_price: u32) -> bool {
+        self.compare(encoded_price) == Ordering::Equal
+    }
+
+    /// Checks that an encoded price is within the band.
+    #[inline(always)]
+    pub fn check(&self, encoded_price: u32) -> DropsetResult {
+        if !self.contains(encoded_price) {
+            return Err(DropsetError::PriceOutsideOracleBand);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use price::encoded_price;
+
+    use super::*;
+
+    #[test]
+    fn band_bounds() {
+        // A 1% band around 10.000000.
+        let band = OracleBand {
+            oracle_price: encoded_price!(10_000_000, 0).as_u32(),
+            band_bps: 100,
+        };
+
+        assert!(band.contains(encoded_price!(10_000_000, 0).as_u32()));
+        assert!(band.contains(encoded_price!(10_100_000, 0).as_u32()));
+        assert!(band.contains(encoded_price!(99_000_000, -1).as_u32()));
+        assert!(!band.contains(encoded_price!(10_100_001, 0).as_u32()));
+        assert!(!band.contains(encoded_price!(98_999_999, -1).as_u32()));
+        assert_eq!(
+            band.check(encoded_price!(12_000_000, 0).as_u32()),
+            Err(DropsetError::PriceOutsideOracleBand)
+        );
+
+        // Prices more than an order of magnitude apart are always outside the band.
+        let widest = OracleBand {
+            band_bps: MAX_ORACLE_BAND_BPS,
+            ..band
+        };
+        assert!(widest.contains(encoded_price!(15_000_000, 0).as_u32()));
+        assert!(widest.contains(encoded_price!(50_000_000, -1).as_u32()));
+        assert!(!widest.contains(encoded_price!(10_000_000, 2).as_u32()));
+        assert!(!widest.contains(encoded_price!(10_000_000, -2).as_u32()));
+    }
+
+    #[test]
+    fn band_compare() {
+        // A 1% band around 10.000000.
+        let band = OracleBand {
+            oracle_price: encoded_price!(10_000_000, 0).as_u32(),
+            band_bps: 100,
+        };
+
+        assert_eq!(
+            band.compare(encoded_price!(10_100_000, 0).as_u32()),
+            Ordering::Equal
+        );
+        assert_eq!(
+            band.compare(encoded_price!(10_100_001, 0).as_u32()),
+            Ordering::Greater
+        );
+        assert_eq!(
+            band.compare(encoded_price!(98_999_999, -1).as_u32()),
+            Ordering::Less
+        );
+        assert_eq!(
+            band.compare(encoded_price!(10_000_000, 2).as_u32()),
+            Ordering::Greater
+        );
+        assert_eq!(
+            band.compare(encoded_price!(10_000_000, -2).as_u32()),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn load_oracle_price() {
+        let price = encoded_price!(12_345_678, 0).as_u32();
+        let oracle_price = OraclePrice::new(price, 42);
+        let data = [oracle_price.as_bytes().as_slice(), &[0; 8]].concat();
+
+        // Trailing data after the oracle price layout is ignored.
+        let loaded = OraclePrice::load_checked(&data).unwrap();
+        assert_eq!(loaded.encoded_price(), price);
+        assert_eq!(loaded.publish_time(), 42);
+
+        assert_eq!(
+            OraclePrice::load_checked(&data[..OraclePrice::LEN - 1]),
+            Err(DropsetError::InvalidOracleAccount)
+        );
+        assert_eq!(
+            OraclePrice::load_checked(&[0; OraclePrice::LEN]),
+            Err(DropsetError::InvalidOracleAccount)
+        );
+        assert_eq!(
+            OraclePrice::load_checked(OraclePrice::new(0, 42).as_bytes()),
+            Err(DropsetError::InvalidOracleAccount)
+        );
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
             LeSectorIndex,
             Payload,
             SectorIndex,
+            LE_NIL,
             PAYLOAD_SIZE,
         },
         transmutable::Transmutable,
@@ -50,8 +51,21 @@ pub trait OrdersCollection {
         S: AsRef<[u8]>;
 }
 
-const ORDER_PADDING: usize =
-    PAYLOAD_SIZE - (size_of::<LeEncodedPrice>() + size_of::<LeSectorIndex>() + U64_SIZE + U64_SIZE);
+const ORDER_PADDING: usize = PAYLOAD_SIZE
+    - (size_of::<LeEncodedPrice>()
+        + size_of::<LeSectorIndex>()
+        + size_of::<LeSectorIndex>()
+        + size_of::<LeSectorIndex>()
+        + U64_SIZE
+        + U64_SIZE
+        + U64_SIZE
+        + U64_SIZE);
+
+/// The expiry value for an order that never expires.
+pub const NO_EXPIRY: u64 = 0;
+
+/// The client order id value for an order posted without one.
+pub const NO_CLIENT_ORDER_ID: u64 = 0;
 
 /// Represents a maker order in the orderbook.
 #[repr(C)]
@@ -61,23 +75,38 @@ pub struct Order {
     encoded_price: LeEncodedPrice,
     /// This enables O(1) indexing from a user/maker's orders -> their seat.
     user_seat: LeSectorIndex,
+    /// The [`SectorIndex`] of the previous order in the user's list of orders on the same side of
+    /// the book as LE bytes. See [`crate::state::user_order_sectors::UserOrders`].
+    prev_user_order: LeSectorIndex,
+    /// The [`SectorIndex`] of the next order in the user's list of orders on the same side of the
+    /// book as LE bytes. See [`crate::state::user_order_sectors::UserOrders`].
+    next_user_order: LeSectorIndex,
     /// The u64 number of base atoms left remaining to fill as LE bytes.
     base_remaining: [u8; U64_SIZE],
     /// The u64 number of quote atoms left remaining to fill as LE bytes.
     quote_remaining: [u8; U64_SIZE],
+    /// The u64 unix timestamp in seconds at which the order expires as LE bytes, or [`NO_EXPIRY`].
+    expiry: [u8; U64_SIZE],
+    /// The u64 client order id supplied by the user as LE bytes, or [`NO_CLIENT_ORDER_ID`].
+    client_order_id: [u8; U64_SIZE],
     /// Padding to fill the rest of the sector payload size.
     _padding: [u8; ORDER_PADDING],
 }
 
 impl Order {
-    /// Create a new order from the order info and the user seat.
+    /// Create a new order from the order info and the user seat that never expires and has no
+    /// client order id.
     #[inline(always)]
     pub fn new(order_info: OrderInfo, user_seat: SectorIndex) -> Self {
         Self {
             encoded_price: order_info.encoded_price.into(),
             user_seat: user_seat.to_le_bytes(),
+            prev_user_order: LE_NIL,
+            next_user_order: LE_NIL,
             base_remaining: order_info.base_atoms.to_le_bytes(),
             quote_remaining: order_info.quote_atoms.to_le_bytes(),
+            expiry: NO_EXPIRY.to_le_bytes(),
+            client_order_id: NO_CLIENT_ORDER_ID.to_le_bytes(),
             _padding: [0u8; ORDER_PADDING],
         }
     }
@@ -97,6 +126,31 @@ impl Order {
         u32::from_le_bytes(self.user_seat)
     }
 
+    #[inline(always)]
+    pub fn set_user_seat(&mut self, user_seat: SectorIndex) {
+        self.user_seat = user_seat.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn prev_user_order(&self) -> SectorIndex {
+        u32::from_le_bytes(self.prev_user_order)
+    }
+
+    #[inline(always)]
+    pub fn set_prev_user_order(&mut self, index: SectorIndex) {
+        self.prev_user_order = index.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn next_user_order(&self) -> SectorIndex {
+        u32::from_le_bytes(self.next_user_order)
+    }
+
+    #[inline(always)]
+    pub fn set_next_user_order(&mut self, index: SectorIndex) {
+        self.next_user_order = index.to_le_bytes();
+    }
+
     #[inline(always)]
     pub fn base_remaining(&self) -> u64 {
         u64::from_le_bytes(self.base_remaining)
@@ -117,6 +171,34 @@ impl Order {
         self.quote_remaining = amount.to_le_bytes();
     }
 
+    #[inline(always)]
+    pub fn expiry(&self) -> u64 {
+        u64::from_le_bytes(self.expiry)
+    }
+
+    #[inline(always)]
+    pub fn set_expiry(&mut self, expiry: u64) {
+        self.expiry = expiry.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn client_order_id(&self) -> u64 {
+        u64::from_le_bytes(self.client_order_id)
+    }
+
+    #[inline(always)]
+    pub fn set_client_order_id(&mut self, client_order_id: u64) {
+        self.client_order_id = client_order_id.to_le_bytes();
+    }
+
+    /// Whether or not the order has expired as of the passed unix timestamp in seconds. An order
+    /// with [`NO_EXPIRY`] never expires.
+    #[inline(always)]
+    pub fn is_expired(&self, unix_timestamp: u64) -> bool {
+        let expiry = self.expiry();
+        expiry != NO_EXPIRY && unix_timestamp >= expiry
+    }
+
     /// This method is sound because:
     ///
     /// - `Self` is exactly `Self::LEN` bytes.
@@ -197,6 +279,27 @@ mod tests {
         order.set_quote_remaining(quote_after);
         assert_eq!(order.base_remaining(), base_after);
         assert_eq!(order.quote_remaining(), quote_after);
+        assert_eq!(order.client_order_id(), NO_CLIENT_ORDER_ID);
+        let client_order_id = 333_333_333;
+        order.set_client_order_id(client_order_id);
+        assert_eq!(order.client_order_id(), client_order_id);
+    }
+
+    #[test]
+    fn order_expiry() {
+        let order_info =
+            to_order_info((10_000_000, 5, 8, 0).into()).expect("Should create order info");
+        let mut order = Order::new(order_info, 17);
+        assert_eq!(order.expiry(), NO_EXPIRY);
+        assert!(!order.is_expired(0));
+        assert!(!order.is_expired(u64::MAX));
+
+        const EXPIRY: u64 = 1_700_000_000;
+        order.set_expiry(EXPIRY);
+        assert_eq!(order.expiry(), EXPIRY);
+        assert!(!order.is_expired(EXPIRY - 1));
+        assert!(order.is_expired(EXPIRY));
+        assert!(order.is_expired(EXPIRY + 1));
     }
 
     #[test]
@@ -212,11 +315,15 @@ mod tests {
         let order = Order::new(order_info, USER_SEAT);
         assert_eq!(
             [
-                &0u32.to_le_bytes(),                // Encoded price.
-                &USER_SEAT.to_le_bytes(),           // User seat.
-                BASE_ATOMS.to_le_bytes().as_ref(),  // Base remaining.
-                QUOTE_ATOMS.to_le_bytes().as_ref(), // Quote remaining.
-                [0u8; ORDER_PADDING].as_ref(),      // Padding.
+                &0u32.to_le_bytes(),                       // Encoded price.
+                &USER_SEAT.to_le_bytes(),                  // User seat.
+                &LE_NIL,                                   // Previous user order.
+                &LE_NIL,                                   // Next user order.
+                BASE_ATOMS.to_le_bytes().as_ref(),         // Base remaining.
+                QUOTE_ATOMS.to_le_bytes().as_ref(),        // Quote remaining.
+                NO_EXPIRY.to_le_bytes().as_ref(),          // Expiry.
+                NO_CLIENT_ORDER_ID.to_le_bytes().as_ref(), // Client order id.
+                [0u8; ORDER_PADDING].as_ref(),             // Padding.
             ]
             .concat(),
             order.as_bytes()
@@ -0,0 +1,82 @@
+//! See [`SeatDelegate`].
+
+use solana_address::Address;
+use static_assertions::const_assert_eq;
+
+use crate::state::{
+    sector::{
+        AllBitPatternsValid,
+        Payload,
+        PAYLOAD_SIZE,
+    },
+    transmutable::Transmutable,
+};
+
+const SEAT_DELEGATE_PADDING: usize = PAYLOAD_SIZE - (size_of::<Address>() + size_of::<u8>());
+
+/// A seat's delegate, stored in its own sector so that only seats with a delegate use the space.
+/// The seat points to it with [`crate::state::market_seat::MarketSeat::delegate_sector`].
+///
+/// The delegate address is stored last so that a user-chosen address never sits where a
+/// [`crate::state::market_seat::MarketSeat`] stores its user.
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatDelegate {
+    /// Whether or not the delegate may also take liquidity with the seat's balances, as a non-zero
+    /// `u8` so that all bit patterns are valid.
+    can_take: u8,
+    /// Padding to fill the rest of the sector payload size.
+    _padding: [u8; SEAT_DELEGATE_PADDING],
+    /// An address the seat's user allows to post, amend and cancel orders on their behalf. It can
+    /// never withdraw from or close the seat.
+    pub delegate: Address,
+}
+
+impl SeatDelegate {
+    pub fn new(delegate: Address, can_take: bool) -> Self {
+        SeatDelegate {
+            can_take: can_take as u8,
+            _padding: [0; SEAT_DELEGATE_PADDING],
+            delegate,
+        }
+    }
+
+    #[inline(always)]
+    pub fn can_take(&self) -> bool {
+        self.can_take != 0
+    }
+
+    /// This method is sound because:
+    ///
+    /// - `Self` is exactly `Self::LEN` bytes.
+    /// - Size and alignment are verified with const assertions.
+    /// - All fields are byte-safe, `Copy`, non-pointer/reference u8 arrays.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        unsafe { &*(self as *const Self as *const [u8; Self::LEN]) }
+    }
+}
+
+// Safety:
+//
+// - Stable layout with `#[repr(C)]`.
+// - `size_of` and `align_of` are checked below.
+// - All bit patterns are valid.
+unsafe impl Transmutable for SeatDelegate {
+    const LEN: usize = size_of::<SeatDelegate>();
+
+    #[inline(always)]
+    fn validate_bit_patterns(_bytes: &[u8]) -> crate::error::DropsetResult {
+        // All bit patterns are valid: no enums, bools, or other types with invalid states.
+        Ok(())
+    }
+}
+
+const_assert_eq!(size_of::<SeatDelegate>(), PAYLOAD_SIZE);
+const_assert_eq!(align_of::<SeatDelegate>(), 1);
+
+// Safety: Const asserts ensure size_of::<SeatDelegate>() == PAYLOAD_SIZE.
+unsafe impl Payload for SeatDelegate {}
+
+// Safety: All bit patterns are valid.
+unsafe impl AllBitPatternsValid for SeatDelegate {}
@@ -8,6 +8,7 @@ use crate::state::{
     },
     market_header::MarketHeader,
     sector::SectorIndex,
+    sector_kind::SectorKind,
 };
 
 pub struct Seats;
@@ -17,6 +18,8 @@ pub type SeatsLinkedList<'a> = LinkedList<'a, Seats>;
 /// Operations for the sorted, doubly linked list of sectors containing
 /// [`crate::state::market_seat::MarketSeat`] payloads.
 impl LinkedListHeaderOperations for Seats {
+    const SECTOR_KIND: SectorKind = SectorKind::Seat;
+
     #[inline(always)]
     fn head(header: &MarketHeader) -> SectorIndex {
         header.seats_dll_head()
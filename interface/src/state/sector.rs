@@ -16,12 +16,13 @@ use crate::{
     },
     state::{
         market_seat::MarketSeat,
+        sector_kind::SectorKind,
         transmutable::Transmutable,
         U32_SIZE,
     },
 };
 
-pub const SECTOR_SIZE: usize = 136;
+pub const SECTOR_SIZE: usize = 72;
 
 /// A sentinel value that marks 1-past the last valid sector index of a collection of sectors.
 ///
@@ -75,6 +76,8 @@ pub struct Sector {
     /// This field is unused in the free stack implementation and should be treated as garbage data
     /// while a [`Sector`] is considered freed.
     prev: LeSectorIndex,
+    /// The [`SectorKind`] of the sector's payload as a u8.
+    kind: u8,
     /// The raw payload bytes for a [`Sector`], representing some type `T` that implements
     /// [`Payload`].
     payload: [u8; PAYLOAD_SIZE],
@@ -137,6 +140,17 @@ impl Sector {
         self.next = index.to_le_bytes();
     }
 
+    /// Whether or not the sector currently holds a payload of the passed kind.
+    #[inline(always)]
+    pub fn is_kind(&self, kind: SectorKind) -> bool {
+        self.kind == kind as u8
+    }
+
+    #[inline(always)]
+    pub fn set_kind(&mut self, kind: SectorKind) {
+        self.kind = kind as u8;
+    }
+
     #[inline(always)]
     pub fn set_payload(&mut self, payload: &[u8; PAYLOAD_SIZE]) {
         // Safety: both payloads are exactly `PAYLOAD_SIZE` long, and the incoming payload
@@ -0,0 +1,49 @@
+//! See [`SectorKind`].
+
+use instruction_macros::u8_enum;
+
+u8_enum! {
+    /// The kind of payload a [`crate::state::sector::Sector`] currently holds.
+    ///
+    /// Sector indices passed in by users are only bounds-checked, so the kind is what ensures that
+    /// a sector index hint actually points to a live sector of the expected type.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum SectorKind {
+        /// A sector in the free stack. Newly allocated, zeroed sectors are free.
+        #[default]
+        Free = 0,
+        /// A [`crate::state::market_seat::MarketSeat`] in the seats list.
+        Seat = 1,
+        /// A bid [`crate::state::order::Order`] in the bids list.
+        Bid = 2,
+        /// An ask [`crate::state::order::Order`] in the asks list.
+        Ask = 3,
+        /// A [`crate::state::seat_delegate::SeatDelegate`] pointed to by its seat.
+        SeatDelegate = 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instruction_macros::{
+        Pack,
+        Unpack,
+    };
+
+    use super::*;
+
+    #[test]
+    fn sector_kind_round_trip() {
+        for kind in [
+            SectorKind::Free,
+            SectorKind::Seat,
+            SectorKind::Bid,
+            SectorKind::Ask,
+            SectorKind::SeatDelegate,
+        ] {
+            assert_eq!(SectorKind::unpack(&kind.pack()).unwrap(), kind);
+        }
+        assert!(SectorKind::unpack(&[5]).is_err());
+        assert!(SectorKind::unpack(&[]).is_err());
+    }
+}
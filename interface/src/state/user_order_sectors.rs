@@ -1,7 +1,3 @@
-use price::{
-    EncodedPrice,
-    LeEncodedPrice,
-};
 use static_assertions::const_assert_eq;
 
 use crate::{
@@ -10,10 +6,14 @@ use crate::{
         DropsetResult,
     },
     state::{
+        market_seat::MarketSeat,
+        order::Order,
         sector::{
             LeSectorIndex,
+            Sector,
             SectorIndex,
             LE_NIL,
+            NIL,
         },
         transmutable::Transmutable,
     },
@@ -21,15 +21,14 @@ use crate::{
 
 /// The max number of orders a single user/address can have for a single market for bids or asks.
 /// That is, each user can have [`MAX_ORDERS`] bids and [`MAX_ORDERS`] asks for a single market.
-pub const MAX_ORDERS: u8 = 5;
-
-/// Helper const for [`MAX_ORDERS`] as a usize.
-pub const MAX_ORDERS_USIZE: usize = MAX_ORDERS as usize;
-
-/// The [`OrderSectors`] that maps the prices of a user's bids and asks to their corresponding
-/// orders' sector indices in the market account data.
 ///
-/// `bids` and `asks` both have a maximum [`MAX_ORDERS`] orders.
+/// A user's orders are linked through the orders themselves, so this doesn't affect the size of a
+/// seat. It bounds the work done when walking a user's orders. Each market chooses its own max
+/// number of orders per side up to this bound with
+/// [`crate::instructions::MarketParams::max_orders`].
+pub const MAX_ORDERS: u8 = 16;
+
+/// The heads of a user's bid and ask [`UserOrders`] lists, stored in the user's seat.
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct UserOrderSectors {
@@ -37,153 +36,272 @@ pub struct UserOrderSectors {
     pub asks: OrderSectors,
 }
 
-/// An array of [`MAX_ORDERS`] [`PriceToIndexEntry`]s that maps unique prices to a sector index.
+/// The head sector index and length of a user's list of orders on one side of the book.
 ///
-/// By default, each [`PriceToIndexEntry`] represents an unused item by mapping an encoded price u32
-/// value of `0` to the [`LE_NIL`] sector index.
-#[repr(transparent)]
+/// The list's links are stored in each order's `prev_user_order` and `next_user_order` fields,
+/// which makes adding, removing and relocating an order O(1). Checking whether an order belongs to
+/// the list walks at most the market's max number of orders per side.
+#[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct OrderSectors([PriceToIndexEntry; MAX_ORDERS_USIZE]);
+pub struct OrderSectors {
+    /// The little endian bytes representing the [`SectorIndex`] of the first order in the list.
+    head: LeSectorIndex,
+    /// The number of orders in the list.
+    num_orders: u8,
+}
 
 impl Default for OrderSectors {
     fn default() -> Self {
-        Self([PriceToIndexEntry::new_free(); MAX_ORDERS_USIZE])
+        Self {
+            head: LE_NIL,
+            num_orders: 0,
+        }
     }
 }
 
 impl OrderSectors {
-    /// Attempt to find and return the sector index for the order corresponding to the passed
-    /// encoded price.
     #[inline(always)]
-    pub fn get(&self, target_price: &LeEncodedPrice) -> Option<SectorIndex> {
-        self.0.iter().find_map(
-            |PriceToIndexEntry {
-                 encoded_price,
-                 sector_index,
-             }| {
-                match encoded_price.as_slice() == target_price.as_slice() {
-                    true => Some(u32::from_le_bytes(*sector_index)),
-                    false => None,
-                }
-            },
-        )
+    pub fn head(&self) -> SectorIndex {
+        SectorIndex::from_le_bytes(self.head)
     }
 
-    /// Fallibly add a [`PriceToIndexEntry`] to a user's orders.
-    ///
-    /// Fails if the user already has [`MAX_ORDERS`] or the price already has an existing order.
-    ///
-    /// The order's sector index passed should be non-NIL or the [`crate::state::sector::Sector`]
-    /// after mutation will continue to be treated as if it were free.
+    /// Returns the number of orders in the list.
     #[inline(always)]
-    pub fn add(
-        &mut self,
-        new_price: &LeEncodedPrice,
-        order_index: &LeSectorIndex,
-    ) -> DropsetResult {
-        // Check if the price already exists in an entry and fail early if it does.
-        if self
-            .iter()
-            .any(|e| e.encoded_price.as_slice() == new_price.as_slice())
-        {
-            return Err(DropsetError::OrderWithPriceAlreadyExists);
-        }
-
-        let entry = self
-            .iter_mut()
-            .find(|e| e.is_free())
-            .ok_or(DropsetError::UserHasMaxOrders)?;
+    pub fn num_orders(&self) -> usize {
+        self.num_orders as usize
+    }
 
-        entry.encoded_price = *new_price;
-        entry.sector_index = *order_index;
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.num_orders == 0
+    }
 
-        Ok(())
+    /// Returns an iterator over the sector indices and orders in the list, given the sectors the
+    /// list's orders are stored in.
+    #[inline(always)]
+    pub fn iter<'a>(&self, sectors: &'a [u8]) -> UserOrdersIter<'a> {
+        UserOrdersIter {
+            curr: self.head(),
+            remaining: self.num_orders(),
+            sectors,
+        }
     }
 
-    /// Fallibly remove a [`PriceToIndexEntry`] from a user's orders.
-    ///
-    /// Fails if the user does not have an order corresponding to the passed encoded price.
+    /// Returns whether or not the passed sector index is one of the orders in the list.
     ///
-    /// Note that the encoded price does not have to be validated since it's doing a simple match
-    /// on equality and isn't stored anywhere.
-    ///
-    /// Returns the mapped order's sector index.
+    /// Note that the sector index does not have to be validated since it's only compared for
+    /// equality against the list's sector indices, which are never NIL.
     #[inline(always)]
-    pub fn remove(&mut self, encoded_price: u32) -> Result<LeSectorIndex, DropsetError> {
-        let entry = self
-            .0
-            .iter_mut()
-            .find(|e| e.encoded_price.as_slice() == &encoded_price.to_le_bytes())
-            .ok_or(DropsetError::OrderNotFound)?;
+    pub fn contains(&self, sectors: &[u8], order_index: SectorIndex) -> bool {
+        self.iter(sectors).any(|(index, _)| index == order_index)
+    }
+}
 
-        let sector_index = entry.sector_index;
+/// A user's doubly linked list of orders on one side of the book, headed by the [`OrderSectors`]
+/// in the user's seat.
+pub struct UserOrders<'a> {
+    sectors: &'a mut [u8],
+    seat_index: SectorIndex,
+    is_bid: bool,
+}
 
-        mark_as_free(entry);
+impl<'a> UserOrders<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees `seat_index` is in-bounds of `sectors` and points to a valid seat.
+    #[inline(always)]
+    pub unsafe fn new(sectors: &'a mut [u8], seat_index: SectorIndex, is_bid: bool) -> Self {
+        Self {
+            sectors,
+            seat_index,
+            is_bid,
+        }
+    }
 
-        Ok(sector_index)
+    #[inline(always)]
+    fn order_sectors(&self) -> &OrderSectors {
+        // Safety: The seat index was verified as in-bounds and pointing to a seat on construction.
+        let seat = unsafe { Sector::from_sector_index(self.sectors, self.seat_index) }
+            .load_payload::<MarketSeat>();
+        if self.is_bid {
+            &seat.user_order_sectors.bids
+        } else {
+            &seat.user_order_sectors.asks
+        }
     }
 
-    /// Returns an array of copied sector indices from the mapped entries.
     #[inline(always)]
-    pub fn to_sector_indices(&self) -> [SectorIndex; MAX_ORDERS_USIZE] {
-        core::array::from_fn(|i| {
-            let item = self.0[i];
-            SectorIndex::from_le_bytes(item.sector_index)
-        })
+    fn order_sectors_mut(&mut self) -> &mut OrderSectors {
+        // Safety: The seat index was verified as in-bounds and pointing to a seat on construction.
+        let seat = unsafe { Sector::from_sector_index_mut(self.sectors, self.seat_index) }
+            .load_payload_mut::<MarketSeat>();
+        if self.is_bid {
+            &mut seat.user_order_sectors.bids
+        } else {
+            &mut seat.user_order_sectors.asks
+        }
     }
 
+    /// # Safety
+    ///
+    /// Caller guarantees `index` is in-bounds and points to a valid order.
     #[inline(always)]
-    pub fn iter(&self) -> core::slice::Iter<'_, PriceToIndexEntry> {
-        self.0.iter()
+    unsafe fn order_mut(&mut self, index: SectorIndex) -> &mut Order {
+        // Safety: Caller guarantees `index` is in-bounds.
+        unsafe { Sector::from_sector_index_mut(self.sectors, index) }.load_payload_mut::<Order>()
     }
 
+    /// Returns the number of orders in the list.
     #[inline(always)]
-    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, PriceToIndexEntry> {
-        self.0.iter_mut()
+    pub fn num_orders(&self) -> usize {
+        self.order_sectors().num_orders()
     }
-}
 
-/// The paired encoded price and sector index for an order.
-///
-/// If the sector index equals [`LE_NIL`], it's considered a freed entry, otherwise, it contains an
-/// existing, valid pair of encoded price to sector index.
-#[repr(C)]
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct PriceToIndexEntry {
-    pub encoded_price: LeEncodedPrice,
-    pub sector_index: LeSectorIndex,
-}
+    /// Returns whether or not the passed sector index is one of the orders in the list.
+    #[inline(always)]
+    pub fn contains(&self, order_index: SectorIndex) -> bool {
+        self.order_sectors().contains(self.sectors, order_index)
+    }
 
-impl PriceToIndexEntry {
-    /// Create a new [`PriceToIndexEntry`] that is free.
     #[inline(always)]
-    pub fn new_free() -> Self {
-        Self {
-            encoded_price: LeEncodedPrice::zero(),
-            sector_index: LE_NIL,
+    pub fn iter(&self) -> UserOrdersIter<'_> {
+        self.order_sectors().iter(self.sectors)
+    }
+
+    /// Fallibly add the order at `order_index` to the front of the user's orders.
+    ///
+    /// Fails if the user already has `max_orders` orders, where `max_orders` is the market's max
+    /// number of orders per side and never exceeds [`MAX_ORDERS`]. The order's price doesn't have
+    /// to be unique.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees `order_index` is in-bounds and points to a valid order that isn't in any
+    /// user's orders.
+    #[inline(always)]
+    pub unsafe fn add(&mut self, order_index: SectorIndex, max_orders: u8) -> DropsetResult {
+        if self.num_orders() >= max_orders as usize {
+            return Err(DropsetError::UserHasMaxOrders);
         }
+
+        let head_index = self.order_sectors().head();
+        // Safety: Caller guarantees `order_index` points to a valid order.
+        let order = unsafe { self.order_mut(order_index) };
+        order.set_prev_user_order(NIL);
+        order.set_next_user_order(head_index);
+
+        if head_index != NIL {
+            // Safety: `head_index` is non-NIL and per the list impl, points to a valid order.
+            unsafe { self.order_mut(head_index) }.set_prev_user_order(order_index);
+        }
+
+        let order_sectors = self.order_sectors_mut();
+        order_sectors.head = order_index.to_le_bytes();
+        order_sectors.num_orders += 1;
+
+        Ok(())
     }
 
-    /// Create a new entry given an input encoded price and sector index.
+    /// Fallibly remove the order at `order_index` from the user's orders.
+    ///
+    /// Fails if the user does not have an order at the passed sector index, which is what ensures
+    /// that an order being removed belongs to the user.
     #[inline(always)]
-    pub fn new(encoded_price: EncodedPrice, sector_index: &SectorIndex) -> Self {
-        Self {
-            encoded_price: encoded_price.into(),
-            sector_index: sector_index.to_le_bytes(),
+    pub fn remove(&mut self, order_index: SectorIndex) -> DropsetResult {
+        if !self.contains(order_index) {
+            return Err(DropsetError::OrderNotFound);
         }
+
+        let (prev_index, next_index) = {
+            // Safety: `order_index` was just found in the list, so it points to a valid order.
+            let order = unsafe { self.order_mut(order_index) };
+            (order.prev_user_order(), order.next_user_order())
+        };
+
+        // Safety: `prev_index` and `next_index` are non-NIL when matched and came from an order in
+        // the list, so they point to valid orders.
+        unsafe { self.relink(prev_index, next_index, next_index, prev_index) };
+
+        self.order_sectors_mut().num_orders -= 1;
+
+        Ok(())
     }
 
+    /// Update the sector index of one of the user's orders after the order is moved from
+    /// `old_index` to `new_index`, by re-linking its neighbors (or the list's head) to the new
+    /// index.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees `old_index` was in the user's orders and that `new_index` is in-bounds and
+    /// points to a copy of the order, with its links unchanged.
     #[inline(always)]
-    pub fn is_free(&self) -> bool {
-        self.sector_index == LE_NIL
+    pub unsafe fn replace(&mut self, new_index: SectorIndex) {
+        let (prev_index, next_index) = {
+            // Safety: Caller guarantees `new_index` points to a valid order.
+            let order = unsafe { self.order_mut(new_index) };
+            (order.prev_user_order(), order.next_user_order())
+        };
+
+        // Safety: The moved order's neighbors are non-NIL when matched and point to valid orders.
+        unsafe { self.relink(prev_index, new_index, next_index, new_index) };
+    }
+
+    /// Sets the `next_user_order` of the order at `prev_index` (or the list's head if it's NIL) to
+    /// `new_next` and the `prev_user_order` of the order at `next_index` to `new_prev`.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees that `prev_index` and `next_index` are either NIL or point to valid
+    /// orders.
+    #[inline(always)]
+    unsafe fn relink(
+        &mut self,
+        prev_index: SectorIndex,
+        new_next: SectorIndex,
+        next_index: SectorIndex,
+        new_prev: SectorIndex,
+    ) {
+        match prev_index {
+            NIL => self.order_sectors_mut().head = new_next.to_le_bytes(),
+            // Safety: Caller guarantees a non-NIL `prev_index` points to a valid order.
+            prev_index => unsafe { self.order_mut(prev_index) }.set_next_user_order(new_next),
+        }
+
+        if next_index != NIL {
+            // Safety: Caller guarantees a non-NIL `next_index` points to a valid order.
+            unsafe { self.order_mut(next_index) }.set_prev_user_order(new_prev);
+        }
     }
 }
 
-/// Updates an entry to be marked as free.
-#[inline(always)]
-pub fn mark_as_free(entry: &mut PriceToIndexEntry) {
-    entry.encoded_price = LeEncodedPrice::zero();
-    entry.sector_index = LE_NIL;
+/// Iterates over a user's orders, stopping after the list's number of orders even if the last
+/// order's `next_user_order` isn't NIL.
+pub struct UserOrdersIter<'a> {
+    pub curr: SectorIndex,
+    pub remaining: usize,
+    pub sectors: &'a [u8],
+}
+
+impl<'a> Iterator for UserOrdersIter<'a> {
+    type Item = (SectorIndex, &'a Order);
+
+    /// Returns the next order if it's non-NIL and the list has orders remaining, otherwise, returns
+    /// `None`.
+    fn next(&mut self) -> Option<(SectorIndex, &'a Order)> {
+        if self.curr == NIL || self.remaining == 0 {
+            return None;
+        }
+
+        // Safety: `self.curr` is non-NIL and per the list impl, must be in-bounds.
+        let order =
+            unsafe { Sector::from_sector_index(self.sectors, self.curr) }.load_payload::<Order>();
+        let res = (self.curr, order);
+
+        self.curr = order.next_user_order();
+        self.remaining -= 1;
+        Some(res)
+    }
 }
 
 // Safety:
@@ -192,7 +310,7 @@ pub fn mark_as_free(entry: &mut PriceToIndexEntry) {
 // - `size_of` and `align_of` are checked below.
 // - All bit patterns are valid.
 unsafe impl Transmutable for UserOrderSectors {
-    const LEN: usize = size_of::<PriceToIndexEntry>() * (MAX_ORDERS * 2) as usize;
+    const LEN: usize = OrderSectors::LEN * 2;
 
     #[inline(always)]
     fn validate_bit_patterns(_bytes: &[u8]) -> crate::error::DropsetResult {
@@ -210,7 +328,7 @@ const_assert_eq!(align_of::<UserOrderSectors>(), 1);
 // - `size_of` and `align_of` are checked below.
 // - All bit patterns are valid.
 unsafe impl Transmutable for OrderSectors {
-    const LEN: usize = size_of::<PriceToIndexEntry>() * MAX_ORDERS_USIZE;
+    const LEN: usize = size_of::<LeSectorIndex>() + size_of::<u8>();
 
     #[inline(always)]
     fn validate_bit_patterns(_bytes: &[u8]) -> crate::error::DropsetResult {
@@ -222,332 +340,347 @@ unsafe impl Transmutable for OrderSectors {
 const_assert_eq!(OrderSectors::LEN, size_of::<OrderSectors>());
 const_assert_eq!(align_of::<OrderSectors>(), 1);
 
-// Safety:
-//
-// - Stable layout with `#[repr(C)]`.
-// - `size_of` and `align_of` are checked below.
-// - All bit patterns are valid.
-unsafe impl Transmutable for PriceToIndexEntry {
-    const LEN: usize = size_of::<PriceToIndexEntry>();
-
-    #[inline(always)]
-    fn validate_bit_patterns(_bytes: &[u8]) -> crate::error::DropsetResult {
-        // All bit patterns are valid.
-        Ok(())
-    }
-}
-
-const_assert_eq!(PriceToIndexEntry::LEN, size_of::<PriceToIndexEntry>());
-const_assert_eq!(align_of::<PriceToIndexEntry>(), 1);
-
-// -------------------------------------------------------------------------------------------------
-/// Readable debug views for [`PriceToIndexEntry`]s.
-#[allow(dead_code)]
-#[derive(Debug)]
-struct PriceToIndexEntryView {
-    pub encoded_price: u32,
-    pub sector_index: SectorIndex,
-}
-
-impl From<&PriceToIndexEntry> for PriceToIndexEntryView {
-    fn from(value: &PriceToIndexEntry) -> Self {
-        Self {
-            encoded_price: u32::from_le_bytes(value.encoded_price.as_array()),
-            sector_index: SectorIndex::from_le_bytes(value.sector_index),
-        }
-    }
-}
-
-impl core::fmt::Debug for PriceToIndexEntry {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let is_in_use = !self.is_free();
-        let entry: Option<PriceToIndexEntryView> = is_in_use.then(|| self.into());
-        write!(f, "{:#?}", entry)
-    }
-}
-
 // -------------------------------------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
     use price::{
         biased_exponent,
-        encoded_price,
-        price_mantissa,
-        EncodedPrice,
-        LeEncodedPrice,
-        ValidatedPriceMantissa,
+        to_order_info,
+        OrderInfoArgs,
+        UNBIASED_MAX,
     };
+    use solana_address::Address;
 
     use crate::{
         error::DropsetError,
         state::{
+            market_seat::MarketSeat,
+            order::Order,
             sector::{
-                LeSectorIndex,
+                Sector,
                 SectorIndex,
                 LE_NIL,
+                NIL,
+                SECTOR_SIZE,
             },
             transmutable::Transmutable,
             user_order_sectors::{
                 OrderSectors,
-                PriceToIndexEntry,
                 UserOrderSectors,
+                UserOrders,
                 MAX_ORDERS,
-                MAX_ORDERS_USIZE,
             },
-            U32_SIZE,
         },
     };
 
     extern crate std;
 
-    use std::vec::Vec;
+    use std::{
+        vec,
+        vec::Vec,
+    };
 
-    #[test]
-    fn new_all_free() {
-        let order_sectors = UserOrderSectors::default();
-        // All bids and asks should be free.
-        assert!(order_sectors.asks.iter().all(|ask| ask.is_free()));
-        assert!(order_sectors.bids.iter().all(|bid| bid.is_free()));
+    const SEAT_INDEX: SectorIndex = 0;
+    const N_SECTORS: usize = MAX_ORDERS as usize * 2 + 3;
+
+    /// Test utility function to create sectors with a seat at [`SEAT_INDEX`] and an order at every
+    /// other sector, where the order's encoded price is `10_000_000` plus its sector index.
+    fn sectors_with_seat_and_orders() -> Vec<u8> {
+        let mut sectors = vec![0u8; SECTOR_SIZE * N_SECTORS];
+        let seat = MarketSeat::new(Address::new_from_array([1; 32]), 0, 0);
+        // Safety: All sector indices are in-bounds.
+        unsafe { Sector::from_sector_index_mut(&mut sectors, SEAT_INDEX) }
+            .set_payload(seat.as_bytes());
+
+        for index in 1..N_SECTORS as SectorIndex {
+            write_order(&mut sectors, index, 10_000_000 + index);
+        }
+
+        sectors
     }
 
-    #[test]
-    fn free_entry_transmutable_bytes() {
-        let free_bytes_vec = [[0; U32_SIZE], LE_NIL].concat();
-        let free_bytes: &[u8; U32_SIZE * 2] = free_bytes_vec.as_slice().try_into().unwrap();
-        let new_freed_from_transmute = PriceToIndexEntry::load(free_bytes);
-        assert!(new_freed_from_transmute.is_ok());
-        let new_freed = new_freed_from_transmute.expect("Should transmute");
-        assert!(new_freed.is_free());
-        assert_eq!(new_freed.encoded_price.as_slice(), &[0u8; 4]);
-        assert_eq!(new_freed.sector_index, LE_NIL);
-        assert_eq!(new_freed, &PriceToIndexEntry::new_free());
+    /// Test utility function to write an order for the seat at [`SEAT_INDEX`] to a sector, where
+    /// the order's encoded price is equal to the input price mantissa.
+    fn write_order(sectors: &mut [u8], index: SectorIndex, price_mantissa: u32) {
+        let order_info = to_order_info(OrderInfoArgs::new(
+            price_mantissa,
+            1,
+            biased_exponent!(UNBIASED_MAX),
+            biased_exponent!(-1),
+        ))
+        .expect("The unit test should pass a valid price mantissa");
+        let order = Order::new(order_info, SEAT_INDEX);
+        unsafe { Sector::from_sector_index_mut(sectors, index) }.set_payload(order.as_bytes());
     }
 
-    #[test]
-    fn free_orders_transmutable_bytes() {
-        let free_bytes_vec = [[0; U32_SIZE], LE_NIL].concat();
-        let max_orders_all_freed: [u8; PriceToIndexEntry::LEN * MAX_ORDERS_USIZE] = (0..MAX_ORDERS)
-            .flat_map(|_| free_bytes_vec.iter().cloned())
-            .collect::<Vec<u8>>()
-            .try_into()
-            .unwrap();
+    fn user_orders(sectors: &mut [u8], is_bid: bool) -> UserOrders<'_> {
+        // Safety: The seat is at `SEAT_INDEX`, which is in-bounds.
+        unsafe { UserOrders::new(sectors, SEAT_INDEX, is_bid) }
+    }
 
-        let new_max_orders_all_freed_from_transmute =
-            OrderSectors::load(&max_orders_all_freed).expect("Should transmute");
+    fn seat(sectors: &[u8]) -> &MarketSeat {
+        unsafe { Sector::from_sector_index(sectors, SEAT_INDEX) }.load_payload::<MarketSeat>()
+    }
 
-        assert_eq!(
-            new_max_orders_all_freed_from_transmute,
-            &OrderSectors::default()
-        );
+    /// Returns the list's sector indices from its head to its tail, checking that each order's
+    /// `prev_user_order` points back to the previous order.
+    fn to_sector_indices(sectors: &mut [u8], is_bid: bool) -> Vec<SectorIndex> {
+        let list = user_orders(sectors, is_bid);
+        let mut prev = NIL;
+        let indices = list
+            .iter()
+            .map(|(index, order)| {
+                assert_eq!(order.prev_user_order(), prev);
+                prev = index;
+                index
+            })
+            .collect();
+        indices
     }
 
     #[test]
-    fn happy_path_free_orders() {
+    fn new_all_free() {
         let order_sectors = UserOrderSectors::default();
-        assert_eq!(order_sectors.bids, OrderSectors::default());
-        assert_eq!(order_sectors.asks, OrderSectors::default());
+        // Both the bids and asks should be empty.
+        assert!(order_sectors.bids.is_empty());
+        assert!(order_sectors.asks.is_empty());
+        assert_eq!(order_sectors.bids.head(), NIL);
+        assert_eq!(order_sectors.asks.head(), NIL);
+    }
+
+    #[test]
+    fn free_orders_transmutable_bytes() {
+        let free_bytes = [LE_NIL.as_slice(), &[0]].concat();
+        let free_bytes: &[u8; OrderSectors::LEN] = free_bytes.as_slice().try_into().unwrap();
+        let new_empty_from_transmute = OrderSectors::load(free_bytes).expect("Should transmute");
+        assert_eq!(new_empty_from_transmute, &OrderSectors::default());
     }
 
     #[test]
     fn happy_path_one_bid_one_ask() {
-        let mut order_sectors = UserOrderSectors::default();
-        let bid_encoded_price = encoded_price!(12_345_678, 1);
+        let sectors = &mut sectors_with_seat_and_orders();
         let (bid_index, ask_index): (SectorIndex, SectorIndex) = (10, 11);
-        let ask_encoded_price = encoded_price!(87_654_321, 2);
-        let new_bid_price: &LeEncodedPrice = &bid_encoded_price.into();
-        let new_ask_price: &LeEncodedPrice = &ask_encoded_price.into();
-
-        order_sectors
-            .bids
-            .add(new_bid_price, &bid_index.to_le_bytes())
-            .expect("Should add the mapping");
-        order_sectors
-            .asks
-            .add(new_ask_price, &ask_index.to_le_bytes())
-            .expect("Should add the mapping");
-        assert_eq!(order_sectors.bids.get(new_bid_price).unwrap(), bid_index);
-        assert_eq!(order_sectors.asks.get(new_ask_price).unwrap(), ask_index);
+
+        unsafe { user_orders(sectors, true).add(bid_index, MAX_ORDERS) }
+            .expect("Should add the bid");
+        unsafe { user_orders(sectors, false).add(ask_index, MAX_ORDERS) }
+            .expect("Should add the ask");
+
+        assert_eq!(to_sector_indices(sectors, true), vec![bid_index]);
+        assert_eq!(to_sector_indices(sectors, false), vec![ask_index]);
+        assert_eq!(seat(sectors).user_order_sectors.bids.head(), bid_index);
+        assert_eq!(seat(sectors).user_order_sectors.asks.head(), ask_index);
+        assert!(!user_orders(sectors, true).contains(ask_index));
+        assert!(!user_orders(sectors, false).contains(bid_index));
     }
 
     #[test]
-    fn duplicate_bid_error() {
-        let mut order_sectors = UserOrderSectors::default();
-        let bid_encoded_price = encoded_price!(12_345_678, 1);
-        let bid_index = 10u32;
-        let bid_index_le_bytes = &bid_index.to_le_bytes();
-        let bid_encoded_le_price: &LeEncodedPrice = &bid_encoded_price.into();
-        order_sectors
-            .bids
-            .add(bid_encoded_le_price, bid_index_le_bytes)
-            .expect("Should add the mapping");
-
-        let failed_add = order_sectors
-            .bids
-            .add(bid_encoded_le_price, bid_index_le_bytes);
-
-        assert!(matches!(
-            failed_add,
-            Err(DropsetError::OrderWithPriceAlreadyExists)
-        ));
+    fn multiple_bids_at_same_price() {
+        let sectors = &mut sectors_with_seat_and_orders();
+        let (first_index, second_index): (SectorIndex, SectorIndex) = (10, 11);
+
+        // Give both orders the same price.
+        write_order(sectors, first_index, 12_345_678);
+        write_order(sectors, second_index, 12_345_678);
+
+        let mut bids = user_orders(sectors, true);
+        unsafe { bids.add(first_index, MAX_ORDERS) }.expect("Should add the first bid");
+        unsafe { bids.add(second_index, MAX_ORDERS) }
+            .expect("Should add a second bid at the same price");
+        assert_eq!(bids.num_orders(), 2);
+        assert!(bids.contains(first_index));
+        assert!(bids.contains(second_index));
+
+        // Removing the first order leaves the second order at the same price untouched.
+        bids.remove(first_index)
+            .expect("Should remove the first order");
+        assert!(!bids.contains(first_index));
+        assert_eq!(to_sector_indices(sectors, true), vec![second_index]);
     }
 
     #[test]
     fn remove_nonexistent_order_error() {
-        let mut order_sectors = UserOrderSectors::default();
-        let bid_encoded_price = encoded_price!(12_345_678, 1);
-        let failed_remove = order_sectors.bids.remove(bid_encoded_price.as_u32());
+        let sectors = &mut sectors_with_seat_and_orders();
+        let failed_remove = user_orders(sectors, true).remove(10);
+        assert!(matches!(failed_remove, Err(DropsetError::OrderNotFound)));
+        // The list's NIL terminator never matches.
+        let failed_remove = user_orders(sectors, true).remove(NIL);
+        assert!(matches!(failed_remove, Err(DropsetError::OrderNotFound)));
+
+        // An order on the other side of the book isn't found either.
+        unsafe { user_orders(sectors, false).add(10, MAX_ORDERS) }.expect("Should add the ask");
+        let failed_remove = user_orders(sectors, true).remove(10);
         assert!(matches!(failed_remove, Err(DropsetError::OrderNotFound)));
     }
 
+    #[test]
+    fn iter_stops_after_num_orders() {
+        let sectors = &mut sectors_with_seat_and_orders();
+        let [first, second] = [3, 5];
+        unsafe { user_orders(sectors, true).add(first, MAX_ORDERS) }.expect("Should add the bid");
+
+        // Corrupt the only order's link so the walk would continue past the end of the list.
+        unsafe { Sector::from_sector_index_mut(sectors, first) }
+            .load_payload_mut::<Order>()
+            .set_next_user_order(second);
+
+        let bids = user_orders(sectors, true);
+        assert_eq!(
+            bids.iter().map(|(index, _)| index).collect::<Vec<_>>(),
+            vec![first]
+        );
+        assert!(bids.contains(first));
+        assert!(!bids.contains(second));
+    }
+
     #[test]
     fn remove_order() {
-        let mut order_sectors = UserOrderSectors::default();
-        // All bids should be free.
-        assert!(order_sectors.bids.iter().all(|bid| bid.is_free()));
-        let bid_encoded_price = encoded_price!(12_345_678, 1);
-        let bid_index = 10u32;
-        assert!(order_sectors
-            .bids
-            .add(&bid_encoded_price.into(), &bid_index.to_le_bytes())
-            .is_ok());
-        // Count the number of orders that are in use (not free).
-        let num_orders_in_use = order_sectors
-            .bids
-            .iter()
-            .fold(0, |acc, p| match p.is_free() {
-                true => acc,
-                false => acc + 1,
-            });
-        assert_eq!(num_orders_in_use, 1);
+        let sectors = &mut sectors_with_seat_and_orders();
+        let [first, middle, last] = [3, 5, 7];
+        let mut bids = user_orders(sectors, true);
+        for index in [first, middle, last] {
+            unsafe { bids.add(index, MAX_ORDERS) }.expect("Should add the bid");
+        }
+        // Orders are added to the front of the list.
+        assert_eq!(to_sector_indices(sectors, true), vec![last, middle, first]);
+
+        // Remove from the middle, the head and then the tail.
+        let mut bids = user_orders(sectors, true);
+        assert!(bids.remove(middle).is_ok());
+        assert_eq!(bids.num_orders(), 2);
+        assert_eq!(to_sector_indices(sectors, true), vec![last, first]);
+        assert!(user_orders(sectors, true).remove(last).is_ok());
+        assert_eq!(to_sector_indices(sectors, true), vec![first]);
+        assert!(user_orders(sectors, true).remove(first).is_ok());
+        assert!(to_sector_indices(sectors, true).is_empty());
+        assert_eq!(
+            seat(sectors).user_order_sectors.bids,
+            OrderSectors::default()
+        );
+    }
+
+    #[test]
+    fn replace_order_sector_index() {
+        let sectors = &mut sectors_with_seat_and_orders();
+        let [first, old_index, last] = [3, 5, 7];
+        let new_index = 20;
+        for index in [first, old_index, last] {
+            unsafe { user_orders(sectors, true).add(index, MAX_ORDERS) }
+                .expect("Should add the bid");
+        }
+
+        // Move the middle order to a new sector, then re-link it.
+        sectors.copy_within(
+            old_index as usize * SECTOR_SIZE..(old_index as usize + 1) * SECTOR_SIZE,
+            new_index as usize * SECTOR_SIZE,
+        );
+        unsafe { user_orders(sectors, true).replace(new_index) };
+        assert_eq!(
+            to_sector_indices(sectors, true),
+            vec![last, new_index, first]
+        );
 
-        assert!(order_sectors
-            .bids
-            .remove(bid_encoded_price.as_u32())
-            .is_ok());
-        assert!(order_sectors.bids.iter().all(|bid| bid.is_free()));
+        // Replacing the head updates the seat's head.
+        sectors.copy_within(
+            last as usize * SECTOR_SIZE..(last as usize + 1) * SECTOR_SIZE,
+            (new_index + 1) as usize * SECTOR_SIZE,
+        );
+        unsafe { user_orders(sectors, true).replace(new_index + 1) };
+        assert_eq!(
+            to_sector_indices(sectors, true),
+            vec![new_index + 1, new_index, first]
+        );
+        assert_eq!(user_orders(sectors, true).num_orders(), 3);
+        assert!(!user_orders(sectors, true).contains(old_index));
+        assert!(!user_orders(sectors, true).contains(last));
     }
 
     #[test]
     fn too_many_orders_error() {
-        let mut order_sectors = UserOrderSectors::default();
-        for i in 0..=MAX_ORDERS as u32 {
-            let encoded_price = EncodedPrice::new(
-                ValidatedPriceMantissa::try_from(10_000_000 + i).unwrap(),
-                biased_exponent!(0),
-            );
-
-            if i != MAX_ORDERS as u32 {
-                // Add each new price to both bids and asks and assert it is successful.
-                assert!(order_sectors
-                    .bids
-                    .add(&encoded_price.into(), &i.to_le_bytes())
-                    .is_ok());
-                assert!(order_sectors
-                    .asks
-                    .add(&encoded_price.into(), &i.to_le_bytes())
-                    .is_ok());
+        let sectors = &mut sectors_with_seat_and_orders();
+        for i in 0..=MAX_ORDERS as SectorIndex {
+            let (bid_index, ask_index) = (i * 2 + 1, i * 2 + 2);
+            let bid = unsafe { user_orders(sectors, true).add(bid_index, MAX_ORDERS) };
+            let ask = unsafe { user_orders(sectors, false).add(ask_index, MAX_ORDERS) };
+
+            if i != MAX_ORDERS as SectorIndex {
+                // Add each new order to both bids and asks and assert it is successful.
+                assert!(bid.is_ok());
+                assert!(ask.is_ok());
             } else {
                 // If this is the last order, it should fail, since it's one beyond the max amount.
-                assert!(matches!(
-                    order_sectors
-                        .bids
-                        .add(&encoded_price.into(), &i.to_le_bytes()),
-                    Err(DropsetError::UserHasMaxOrders)
-                ));
-                assert!(matches!(
-                    order_sectors
-                        .asks
-                        .add(&encoded_price.into(), &i.to_le_bytes()),
-                    Err(DropsetError::UserHasMaxOrders)
-                ));
+                assert!(matches!(bid, Err(DropsetError::UserHasMaxOrders)));
+                assert!(matches!(ask, Err(DropsetError::UserHasMaxOrders)));
             }
         }
+        assert_eq!(user_orders(sectors, true).num_orders(), MAX_ORDERS as usize);
     }
 
     #[test]
-    fn repost_arbitrary_order() {
-        let mut order_sectors = UserOrderSectors::default();
-        let index_and_mantissa_pairs: [(u32, ValidatedPriceMantissa); MAX_ORDERS_USIZE] = [
-            (1, ValidatedPriceMantissa::try_from(11_111_111).unwrap()),
-            (2, ValidatedPriceMantissa::try_from(22_222_222).unwrap()),
-            (3, ValidatedPriceMantissa::try_from(33_333_333).unwrap()),
-            (4, ValidatedPriceMantissa::try_from(44_444_444).unwrap()),
-            (5, ValidatedPriceMantissa::try_from(55_555_555).unwrap()),
-        ];
-
-        let index_and_encoded_price_pairs: [(u32, EncodedPrice); MAX_ORDERS_USIZE] =
-            index_and_mantissa_pairs
-                .into_iter()
-                .map(|(i, mantissa)| (i, EncodedPrice::new(mantissa, biased_exponent!(0))))
-                .collect::<std::vec::Vec<_>>()
-                .try_into()
-                .unwrap();
-
-        for (i, encoded_price) in index_and_encoded_price_pairs.iter() {
-            order_sectors
-                .bids
-                .add(&(*encoded_price).into(), &i.to_le_bytes())
-                .unwrap();
-        }
+    fn market_max_orders_error() {
+        let sectors = &mut sectors_with_seat_and_orders();
+        let market_max_orders = 3;
 
-        // All bids should be in use.
-        assert!(order_sectors.bids.iter().all(|bid| !bid.is_free()));
+        for i in 1..=market_max_orders as SectorIndex {
+            assert!(unsafe { user_orders(sectors, true).add(i, market_max_orders) }.is_ok());
+        }
+        assert_eq!(
+            user_orders(sectors, true).num_orders(),
+            market_max_orders as usize
+        );
 
-        let (old_sector_index, old_price) = *index_and_encoded_price_pairs.get(1).unwrap();
+        // The market's max is reached well before [`MAX_ORDERS`].
+        assert!(market_max_orders < MAX_ORDERS);
+        assert!(matches!(
+            unsafe { user_orders(sectors, true).add(10, market_max_orders) },
+            Err(DropsetError::UserHasMaxOrders)
+        ));
 
-        let new_sector_index = 7;
-        let new_price = encoded_price!(77_777_777, 0);
+        // The asks are unaffected by the number of bids.
+        assert!(unsafe { user_orders(sectors, false).add(10, market_max_orders) }.is_ok());
 
-        // Ensure the new price doesn't exist in the bids yet.
-        assert!(order_sectors.bids.get(&new_price.into()).is_none());
+        // Removing a bid frees up room for another one.
+        assert!(user_orders(sectors, true).remove(1).is_ok());
+        assert!(unsafe { user_orders(sectors, true).add(11, market_max_orders) }.is_ok());
+        assert_eq!(
+            user_orders(sectors, true).num_orders(),
+            market_max_orders as usize
+        );
+    }
 
-        // Ensure the old sector index doesn't equal the new index it's being updated to so the
-        // final check is meaningful and not a misleading equality check.
-        assert_ne!(old_sector_index, new_sector_index);
+    #[test]
+    fn repost_arbitrary_order() {
+        let sectors = &mut sectors_with_seat_and_orders();
+        let indices: Vec<SectorIndex> = (1..=MAX_ORDERS as SectorIndex).collect();
+        for index in indices.iter() {
+            unsafe { user_orders(sectors, true).add(*index, MAX_ORDERS) }.unwrap();
+        }
 
-        // Remove the old price.
-        assert!(order_sectors.bids.remove(old_price.as_u32()).is_ok());
+        let old_sector_index = indices[1];
+        let new_sector_index = N_SECTORS as SectorIndex - 1;
 
-        // Add the new price.
-        assert!(order_sectors
-            .bids
-            .add(&new_price.into(), &new_sector_index.to_le_bytes())
-            .is_ok());
+        // Ensure the new order isn't in the bids yet, and that the old sector index doesn't equal
+        // the new one so the final check is meaningful and not a misleading equality check.
+        assert!(!user_orders(sectors, true).contains(new_sector_index));
+        assert_ne!(old_sector_index, new_sector_index);
 
-        // Ensure the old price has been removed and the new price exists and is mapped to the new
-        // sector index.
-        assert!(order_sectors.bids.get(&old_price.into()).is_none());
-        assert!(order_sectors.bids.get(&new_price.into()).is_some());
-        assert_eq!(
-            order_sectors.bids.get(&new_price.into()).unwrap(),
-            new_sector_index
-        );
+        // Remove the old order and add the new one.
+        assert!(user_orders(sectors, true).remove(old_sector_index).is_ok());
+        assert!(unsafe { user_orders(sectors, true).add(new_sector_index, MAX_ORDERS) }.is_ok());
 
-        // Ensure there are no free bids.
-        assert!(order_sectors.bids.iter().all(|bid| !bid.is_free()));
-
-        // Check the final result in whole.
-        let expected_index_and_encoded_price_pairs: [(u32, EncodedPrice); MAX_ORDERS_USIZE] = [
-            (1, price_mantissa!(11_111_111)),
-            (7, price_mantissa!(77_777_777)),
-            (3, price_mantissa!(33_333_333)),
-            (4, price_mantissa!(44_444_444)),
-            (5, price_mantissa!(55_555_555)),
-        ]
-        .into_iter()
-        .map(|(i, mantissa)| (i, EncodedPrice::new(mantissa, biased_exponent!(0))))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
-
-        for (expected, result) in expected_index_and_encoded_price_pairs
+        // The new order is at the front, followed by the others from newest to oldest.
+        let mut expected: Vec<SectorIndex> = indices
             .iter()
-            .zip(order_sectors.bids.iter())
-        {
-            let (expected_sector_index, expected_encoded_price): (&LeSectorIndex, &LeEncodedPrice) =
-                (&expected.0.to_le_bytes(), &expected.1.into());
-            assert_eq!(&result.sector_index, expected_sector_index);
-            assert_eq!(&result.encoded_price, expected_encoded_price);
+            .rev()
+            .copied()
+            .filter(|index| *index != old_sector_index)
+            .collect();
+        expected.insert(0, new_sector_index);
+        assert_eq!(to_sector_indices(sectors, true), expected);
+
+        // Each order's price is still what it was created with.
+        for (index, order) in user_orders(sectors, true).iter() {
+            assert_eq!(order.encoded_price(), 10_000_000 + index);
         }
     }
 }
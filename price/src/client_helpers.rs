@@ -72,6 +72,47 @@ pub fn to_order_info_args(
     ))
 }
 
+/// Rounds a price mantissa down to the nearest multiple of a market's tick size.
+///
+/// Fails if the tick size is zero or if the rounded price mantissa is no longer a valid price
+/// mantissa.
+pub fn round_price_mantissa_to_tick(
+    price_mantissa: u32,
+    tick_size: u32,
+) -> Result<ValidatedPriceMantissa, OrderInfoError> {
+    if tick_size == 0 {
+        return Err(OrderInfoError::InvalidPriceMantissa);
+    }
+    ValidatedPriceMantissa::try_from(price_mantissa - price_mantissa % tick_size)
+}
+
+/// Rounds an order size in base atoms down to the nearest multiple of a market's base lot size.
+pub fn round_base_atoms_to_lot(base_atoms: u64, base_lot_size: u64) -> Result<u64, OrderInfoError> {
+    if base_lot_size == 0 {
+        return Err(OrderInfoError::AmountCannotBeZero);
+    }
+    Ok(base_atoms - base_atoms % base_lot_size)
+}
+
+/// Like [`to_order_info_args`], but first snaps the price and order size to a market's tick size
+/// and base lot size by rounding both down.
+///
+/// This doesn't check the market's minimum order sizes, since the quote atoms are only known after
+/// the order info args are converted on-chain.
+pub fn to_order_info_args_snapped(
+    price: Decimal,
+    order_size_base_atoms: u64,
+    tick_size: u32,
+    base_lot_size: u64,
+) -> Result<OrderInfoArgs, OrderInfoError> {
+    let (validated_mantissa, price_exponent) = ValidatedPriceMantissa::try_into_with_scale(price)?;
+    let snapped_mantissa = round_price_mantissa_to_tick(validated_mantissa.as_u32(), tick_size)?;
+    let snapped_price = decimal_pow10_i16(Decimal::from(snapped_mantissa.as_u32()), price_exponent);
+    let snapped_size = round_base_atoms_to_lot(order_size_base_atoms, base_lot_size)?;
+
+    to_order_info_args(snapped_price, snapped_size)
+}
+
 pub fn decimal_pow10_i16(value: Decimal, pow: i16) -> Decimal {
     const TEN: Decimal = dec!(10);
     let is_negative = pow.is_negative();
@@ -155,6 +196,40 @@ mod tests {
         assert_eq!(res.unwrap(), expected);
     }
 
+    #[test]
+    fn test_round_to_tick_and_lot() {
+        assert_eq!(
+            round_price_mantissa_to_tick(12_345_678, 1_000)
+                .unwrap()
+                .as_u32(),
+            12_345_000
+        );
+        assert_eq!(
+            round_price_mantissa_to_tick(12_345_678, 1)
+                .unwrap()
+                .as_u32(),
+            12_345_678
+        );
+        assert!(round_price_mantissa_to_tick(12_345_678, 0).is_err());
+        // 10_000_001 rounded down to a multiple of 3 is below the valid price mantissa range.
+        assert!(round_price_mantissa_to_tick(10_000_001, 3).is_err());
+
+        assert_eq!(round_base_atoms_to_lot(1_234, 100).unwrap(), 1_200);
+        assert_eq!(round_base_atoms_to_lot(99, 100).unwrap(), 0);
+        assert!(round_base_atoms_to_lot(1_234, 0).is_err());
+    }
+
+    #[test]
+    fn test_to_order_info_args_snapped() {
+        let base_atoms = 500 * 10u64.pow(6);
+        let res = to_order_info_args_snapped(dec!(1.2567), base_atoms + 1_234, 100_000, 1_000);
+        let expected = to_order_info_args(dec!(1.25), base_atoms + 1_000).unwrap();
+        assert_eq!(res.unwrap(), expected);
+
+        // Snapping the order size below the lot size leaves nothing to order.
+        assert!(to_order_info_args_snapped(dec!(1.25), 999, 1, 1_000).is_err());
+    }
+
     #[test]
     fn test_pow10_i16() {
         assert_eq!(decimal_pow10_i16(dec!(1.23), 2), dec!(123));
@@ -28,6 +28,18 @@ pub struct EncodedPrice(u32);
 pub const ENCODED_PRICE_INFINITY: u32 = u32::MAX;
 pub const ENCODED_PRICE_ZERO: u32 = 0;
 
+/// Splits a raw encoded price into its biased price exponent and price mantissa, in that order.
+///
+/// The price mantissa isn't validated, so this is only meaningful for encoded prices that aren't
+/// [`ENCODED_PRICE_ZERO`] or [`ENCODED_PRICE_INFINITY`].
+#[inline(always)]
+pub const fn split_encoded_price(encoded_price: u32) -> (u8, u32) {
+    (
+        (encoded_price >> PRICE_MANTISSA_BITS) as u8,
+        encoded_price & PRICE_MANTISSA_MASK,
+    )
+}
+
 impl EncodedPrice {
     /// Creates a new [`EncodedPrice`] from a validated price mantissa and a biased price exponent.
     #[inline(always)]
@@ -47,6 +59,12 @@ impl EncodedPrice {
         self.0
     }
 
+    /// Returns the price mantissa stored in the lower bits of the encoded price.
+    #[inline(always)]
+    pub fn price_mantissa(&self) -> u32 {
+        self.0 & PRICE_MANTISSA_MASK
+    }
+
     /// The encoded price representation of a market buy/taker order with no constraints on the
     /// maximum filled ask price.
     #[inline(always)]
@@ -130,6 +148,7 @@ const_assert_eq!(size_of::<LeEncodedPrice>(), U32_SIZE);
 mod tests {
     use crate::{
         encoded_price,
+        split_encoded_price,
         EncodedPrice,
         LeEncodedPrice,
         BIAS,
@@ -147,6 +166,18 @@ mod tests {
             (EXPONENT + BIAS) as u32
         );
         assert_eq!(encoded_price.0 & PRICE_MANTISSA_MASK, PRICE_MANTISSA);
+        assert_eq!(encoded_price.price_mantissa(), PRICE_MANTISSA);
+    }
+
+    #[test]
+    fn split_round_trip() {
+        const EXPONENT: u8 = 3;
+        const PRICE_MANTISSA: u32 = 12_345_678;
+        let encoded = encoded_price!(PRICE_MANTISSA, EXPONENT);
+        assert_eq!(
+            split_encoded_price(encoded.as_u32()),
+            (EXPONENT + BIAS, PRICE_MANTISSA)
+        );
     }
 
     #[test]
@@ -97,7 +97,7 @@ pub struct OrderInfo {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Pack, Unpack, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Pack, Unpack, PartialEq, Eq)]
 pub struct OrderInfoArgs {
     /// The price mantissa.
     pub price_mantissa: u32,
@@ -0,0 +1,113 @@
+//! See [`CloseMarketContext`].
+
+use dropset_interface::{
+    error::DropsetError,
+    instructions::generated_program::CloseMarket,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use solana_address::address_eq;
+
+use crate::validation::{
+    market_account_view::MarketAccountView,
+    mint_account_view::MintAccountView,
+    token_account_view::TokenAccountView,
+};
+
+/// The account context for the [`CloseMarket`] instruction, verifying the closer is permitted to
+/// close the market and that the market's and closer's token accounts are valid.
+#[derive(Clone)]
+pub struct CloseMarketContext<'a> {
+    // The event authority is validated by the inevitable `FlushEvents` self-CPI.
+    pub event_authority: &'a AccountView,
+    pub closer: &'a AccountView,
+    pub market_account: MarketAccountView<'a>,
+    pub base_market_ata: TokenAccountView<'a>,
+    pub quote_market_ata: TokenAccountView<'a>,
+    pub closer_base_ata: TokenAccountView<'a>,
+    pub closer_quote_ata: TokenAccountView<'a>,
+    pub base_mint: MintAccountView<'a>,
+    pub quote_mint: MintAccountView<'a>,
+}
+
+impl<'a> CloseMarketContext<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees no accounts passed have their data borrowed in any capacity. This is a
+    /// more restrictive safety contract than is necessary for soundness but is much simpler.
+    pub unsafe fn load(
+        accounts: &'a [AccountView],
+    ) -> Result<CloseMarketContext<'a>, ProgramError> {
+        let CloseMarket {
+            event_authority,
+            closer,
+            market_account,
+            base_market_ata,
+            quote_market_ata,
+            closer_base_ata,
+            closer_quote_ata,
+            base_mint,
+            quote_mint,
+            base_token_program: _,
+            quote_token_program: _,
+            dropset_program: _,
+        } = CloseMarket::load_accounts(accounts)?;
+
+        if !closer.is_signer() {
+            return Err(DropsetError::AuthorityMustBeSigner.into());
+        }
+
+        // Safety: Scoped borrow of market account data.
+        let (market_account, base_mint, quote_mint) = unsafe {
+            let market_account = MarketAccountView::new(market_account)?;
+            let market = market_account.load_unchecked();
+            // A market with an authority can only be closed by that authority. Otherwise, closing
+            // the market is permissionless.
+            if market.header.has_authority()
+                && !address_eq(closer.address(), &market.header.authority)
+            {
+                return Err(DropsetError::IncorrectMarketAuthority.into());
+            }
+            // Check the base and quote mints against the mints in the market header.
+            let (base_mint, quote_mint) =
+                MintAccountView::new_base_and_quote(base_mint, quote_mint, market)?;
+            (market_account, base_mint, quote_mint)
+        };
+
+        // Safety: Scoped borrows of the market and closer base/quote token accounts.
+        let base_market_ata = TokenAccountView::new(
+            base_market_ata,
+            base_mint.account.address(),
+            market_account.account().address(),
+        )?;
+        let quote_market_ata = TokenAccountView::new(
+            quote_market_ata,
+            quote_mint.account.address(),
+            market_account.account().address(),
+        )?;
+        let closer_base_ata = TokenAccountView::new(
+            closer_base_ata,
+            base_mint.account.address(),
+            closer.address(),
+        )?;
+        let closer_quote_ata = TokenAccountView::new(
+            closer_quote_ata,
+            quote_mint.account.address(),
+            closer.address(),
+        )?;
+
+        Ok(Self {
+            event_authority,
+            closer,
+            market_account,
+            base_market_ata,
+            quote_market_ata,
+            closer_base_ata,
+            closer_quote_ata,
+            base_mint,
+            quote_mint,
+        })
+    }
+}
@@ -0,0 +1,209 @@
+//! See [`MarketAuthorityContext`].
+
+use dropset_interface::{
+    error::DropsetError,
+    instructions::generated_program::UpdateMarketParams,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use solana_address::address_eq;
+
+use crate::validation::market_account_view::MarketAccountView;
+
+/// The account context for any instruction restricted to the market authority (e.g. updating the
+/// market parameters or status, transferring the authority, managing the trader allowlist or
+/// setting the market's oracle),
+/// verifying the authority signed.
+#[derive(Clone)]
+pub struct MarketAuthorityContext<'a> {
+    // The event authority is validated by the inevitable `FlushEvents` self-CPI.
+    pub event_authority: &'a AccountView,
+    pub authority: &'a AccountView,
+    pub market_account: MarketAccountView<'a>,
+}
+
+impl<'a> MarketAuthorityContext<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees no accounts passed have their data borrowed in any capacity. This is a
+    /// more restrictive safety contract than is necessary for soundness but is much simpler.
+    pub unsafe fn load(
+        accounts: &'a [AccountView],
+    ) -> Result<MarketAuthorityContext<'a>, ProgramError> {
+        let UpdateMarketParams {
+            event_authority,
+            authority,
+            market_account,
+            dropset_program: _,
+        } = UpdateMarketParams::load_accounts(accounts)?;
+
+        if !authority.is_signer() {
+            return Err(DropsetError::AuthorityMustBeSigner.into());
+        }
+
+        // Safety: Scoped borrow of market account data.
+        let market_account = unsafe {
+            let market_account = MarketAccountView::new(market_account)?;
+            // A market without an authority stores the system program address, which can't sign.
+            let market = market_account.load_unchecked();
+            if !address_eq(authority.address(), &market.header.authority) {
+                return Err(DropsetError::IncorrectMarketAuthority.into());
+            }
+            market_account
+        };
+
+        Ok(Self {
+            event_authority,
+            authority,
+            market_account,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use dropset_interface::instructions::generated_program::{
+        AddAllowlistedTrader,
+        CompactMarket,
+        RemoveAllowlistedTrader,
+        RenounceMarketAuthority,
+        SetMarketOracle,
+        TransferMarketAuthority,
+        UpdateMarketParams,
+        UpdateMarketStatus,
+    };
+    use pinocchio::{
+        account::AccountView,
+        Address,
+    };
+    use solana_account_view::RuntimeAccount;
+
+    use crate::context::deposit_withdraw_context::tests::{
+        assert_address_eq,
+        create_zeroed_mock_runtime_account,
+    };
+
+    #[test]
+    fn market_authority_account_order_invariant() {
+        let mut runtime_accounts = [
+            create_zeroed_mock_runtime_account(Address::new_from_array([0u8; 32])),
+            create_zeroed_mock_runtime_account(Address::new_from_array([1u8; 32])),
+            create_zeroed_mock_runtime_account(Address::new_from_array([2u8; 32])),
+            create_zeroed_mock_runtime_account(Address::new_from_array([3u8; 32])),
+        ];
+
+        let accounts_ptr: *mut RuntimeAccount = runtime_accounts.as_mut_ptr();
+
+        let account_views = unsafe {
+            [
+                AccountView::new_unchecked(accounts_ptr.add(0)),
+                AccountView::new_unchecked(accounts_ptr.add(1)),
+                AccountView::new_unchecked(accounts_ptr.add(2)),
+                AccountView::new_unchecked(accounts_ptr.add(3)),
+            ]
+        };
+
+        let update_params = UpdateMarketParams::load_accounts(&account_views).unwrap();
+        let transfer = TransferMarketAuthority::load_accounts(&account_views).unwrap();
+        let renounce = RenounceMarketAuthority::load_accounts(&account_views).unwrap();
+        let update_status = UpdateMarketStatus::load_accounts(&account_views).unwrap();
+        let compact = CompactMarket::load_accounts(&account_views).unwrap();
+        let add_trader = AddAllowlistedTrader::load_accounts(&account_views).unwrap();
+        let remove_trader = RemoveAllowlistedTrader::load_accounts(&account_views).unwrap();
+        let set_oracle = SetMarketOracle::load_accounts(&account_views).unwrap();
+
+        let UpdateMarketParams {
+            event_authority: up_event_authority,
+            authority: up_authority,
+            market_account: up_market_account,
+            dropset_program: up_dropset_program,
+        } = update_params;
+
+        let TransferMarketAuthority {
+            event_authority: tr_event_authority,
+            authority: tr_authority,
+            market_account: tr_market_account,
+            dropset_program: tr_dropset_program,
+        } = transfer;
+
+        let RenounceMarketAuthority {
+            event_authority: re_event_authority,
+            authority: re_authority,
+            market_account: re_market_account,
+            dropset_program: re_dropset_program,
+        } = renounce;
+
+        let UpdateMarketStatus {
+            event_authority: us_event_authority,
+            authority: us_authority,
+            market_account: us_market_account,
+            dropset_program: us_dropset_program,
+        } = update_status;
+
+        let CompactMarket {
+            event_authority: cm_event_authority,
+            authority: cm_authority,
+            market_account: cm_market_account,
+            dropset_program: cm_dropset_program,
+        } = compact;
+
+        let AddAllowlistedTrader {
+            event_authority: at_event_authority,
+            authority: at_authority,
+            market_account: at_market_account,
+            dropset_program: at_dropset_program,
+        } = add_trader;
+
+        let RemoveAllowlistedTrader {
+            event_authority: rt_event_authority,
+            authority: rt_authority,
+            market_account: rt_market_account,
+            dropset_program: rt_dropset_program,
+        } = remove_trader;
+
+        let SetMarketOracle {
+            event_authority: so_event_authority,
+            authority: so_authority,
+            market_account: so_market_account,
+            dropset_program: so_dropset_program,
+        } = set_oracle;
+
+        // Ensure the accounts are loaded in the same exact order by comparing each unique address.
+        assert_address_eq(tr_event_authority, up_event_authority);
+        assert_address_eq(tr_authority, up_authority);
+        assert_address_eq(tr_market_account, up_market_account);
+        assert_address_eq(tr_dropset_program, up_dropset_program);
+
+        assert_address_eq(re_event_authority, up_event_authority);
+        assert_address_eq(re_authority, up_authority);
+        assert_address_eq(re_market_account, up_market_account);
+        assert_address_eq(re_dropset_program, up_dropset_program);
+
+        assert_address_eq(us_event_authority, up_event_authority);
+        assert_address_eq(us_authority, up_authority);
+        assert_address_eq(us_market_account, up_market_account);
+        assert_address_eq(us_dropset_program, up_dropset_program);
+
+        assert_address_eq(cm_event_authority, up_event_authority);
+        assert_address_eq(cm_authority, up_authority);
+        assert_address_eq(cm_market_account, up_market_account);
+        assert_address_eq(cm_dropset_program, up_dropset_program);
+
+        assert_address_eq(at_event_authority, up_event_authority);
+        assert_address_eq(at_authority, up_authority);
+        assert_address_eq(at_market_account, up_market_account);
+        assert_address_eq(at_dropset_program, up_dropset_program);
+
+        assert_address_eq(rt_event_authority, up_event_authority);
+        assert_address_eq(rt_authority, up_authority);
+        assert_address_eq(rt_market_account, up_market_account);
+        assert_address_eq(rt_dropset_program, up_dropset_program);
+
+        assert_address_eq(so_event_authority, up_event_authority);
+        assert_address_eq(so_authority, up_authority);
+        assert_address_eq(so_market_account, up_market_account);
+        assert_address_eq(so_dropset_program, up_dropset_program);
+    }
+}
@@ -12,7 +12,14 @@ use crate::validation::{
     token_account_view::TokenAccountView,
 };
 
-/// The contextual, validated account infos required for a market order.
+/// The contextual, validated account infos required for a [`MarketOrder`] or a
+/// [`dropset_interface::instructions::generated_program::Swap`].
+///
+/// A swap keeps the event authority and the dropset program accounts even though it's meant to be
+/// called by other programs: its fills must be emitted through the `FlushEvents` self-CPI like any
+/// other fill, since indexers rebuild the order book from those events and program logs can be
+/// truncated. The self-CPI invokes the dropset program signed by the event authority PDA, so both
+/// accounts must be in the instruction's accounts.
 #[derive(Clone)]
 pub struct MarketOrderContext<'a> {
     // The event authority is validated by the inevitable `FlushEvents` self-CPI.
@@ -35,6 +42,8 @@ impl<'a> MarketOrderContext<'a> {
     pub unsafe fn load(
         accounts: &'a [AccountView],
     ) -> Result<MarketOrderContext<'a>, ProgramError> {
+        // `Swap`'s account info fields are in the same exact order as `MarketOrder`'s, so just use
+        // `MarketOrder::load_accounts` for both. This invariant is checked below in unit tests.
         let MarketOrder {
             event_authority,
             user,
@@ -47,6 +56,7 @@ impl<'a> MarketOrderContext<'a> {
             quote_mint,
             base_token_program: _,
             quote_token_program: _,
+            // Only needed as the program account for the `FlushEvents` self-CPI.
             dropset_program: _,
         } = MarketOrder::load_accounts(accounts)?;
 
@@ -99,3 +109,80 @@ impl<'a> MarketOrderContext<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dropset_interface::instructions::generated_program::{
+        MarketOrder,
+        Swap,
+    };
+    use pinocchio::{
+        account::AccountView,
+        Address,
+    };
+    use solana_account_view::RuntimeAccount;
+
+    use crate::context::deposit_withdraw_context::tests::{
+        assert_address_eq,
+        create_zeroed_mock_runtime_account,
+    };
+
+    #[test]
+    fn market_order_swap_account_order_invariant() {
+        let mut runtime_accounts: [RuntimeAccount; 12] = core::array::from_fn(|i| {
+            create_zeroed_mock_runtime_account(Address::new_from_array([i as u8; 32]))
+        });
+
+        let accounts_ptr: *mut RuntimeAccount = runtime_accounts.as_mut_ptr();
+
+        let account_views: [AccountView; 12] =
+            core::array::from_fn(|i| unsafe { AccountView::new_unchecked(accounts_ptr.add(i)) });
+
+        let market_order = MarketOrder::load_accounts(&account_views).unwrap();
+        let swap = Swap::load_accounts(&account_views).unwrap();
+
+        let MarketOrder {
+            event_authority: mo_event_authority,
+            user: mo_user,
+            market_account: mo_market_account,
+            base_user_ata: mo_base_user_ata,
+            quote_user_ata: mo_quote_user_ata,
+            base_market_ata: mo_base_market_ata,
+            quote_market_ata: mo_quote_market_ata,
+            base_mint: mo_base_mint,
+            quote_mint: mo_quote_mint,
+            base_token_program: mo_base_token_program,
+            quote_token_program: mo_quote_token_program,
+            dropset_program: mo_dropset_program,
+        } = market_order;
+
+        let Swap {
+            event_authority: sw_event_authority,
+            user: sw_user,
+            market_account: sw_market_account,
+            base_user_ata: sw_base_user_ata,
+            quote_user_ata: sw_quote_user_ata,
+            base_market_ata: sw_base_market_ata,
+            quote_market_ata: sw_quote_market_ata,
+            base_mint: sw_base_mint,
+            quote_mint: sw_quote_mint,
+            base_token_program: sw_base_token_program,
+            quote_token_program: sw_quote_token_program,
+            dropset_program: sw_dropset_program,
+        } = swap;
+
+        // Ensure the accounts are loaded in the same exact order by comparing each unique address.
+        assert_address_eq(mo_event_authority, sw_event_authority);
+        assert_address_eq(mo_user, sw_user);
+        assert_address_eq(mo_market_account, sw_market_account);
+        assert_address_eq(mo_base_user_ata, sw_base_user_ata);
+        assert_address_eq(mo_quote_user_ata, sw_quote_user_ata);
+        assert_address_eq(mo_base_market_ata, sw_base_market_ata);
+        assert_address_eq(mo_quote_market_ata, sw_quote_market_ata);
+        assert_address_eq(mo_base_mint, sw_base_mint);
+        assert_address_eq(mo_quote_mint, sw_quote_mint);
+        assert_address_eq(mo_base_token_program, sw_base_token_program);
+        assert_address_eq(mo_quote_token_program, sw_quote_token_program);
+        assert_address_eq(mo_dropset_program, sw_dropset_program);
+    }
+}
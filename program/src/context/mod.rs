@@ -3,12 +3,18 @@
 //! Each context groups and validates the accounts required by its corresponding instruction before
 //! execution.
 
+pub mod close_market_context;
 pub mod close_seat_context;
 pub mod deposit_withdraw_context;
+pub mod expand_market_context;
 pub mod flush_events_context;
+pub mod market_authority_context;
 pub mod market_order_context;
 pub mod mutate_orders_context;
+pub mod prune_expired_orders_context;
 pub mod register_market_context;
+pub mod sweep_fees_context;
+pub mod update_oracle_price_context;
 
 /// The account infos necessary to emit events with the event buffer.
 pub struct EventBufferContext<'a> {
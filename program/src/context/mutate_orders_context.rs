@@ -1,6 +1,9 @@
 //! See [`MutateOrdersContext`].
 
-use dropset_interface::instructions::generated_program::PostOrder;
+use dropset_interface::{
+    error::DropsetError,
+    instructions::generated_program::PostOrder,
+};
 use pinocchio::{
     account::AccountView,
     error::ProgramError,
@@ -8,12 +11,14 @@ use pinocchio::{
 
 use crate::validation::market_account_view::MarketAccountView;
 
-/// The account context for any instruction that mutates a user's orders (e.g. post or cancel),
-/// validating the market account passed in.
+/// The account context for any instruction that mutates a user's orders (e.g. post or cancel) or
+/// seat settings, validating the market account passed in and that the user signed.
 #[derive(Clone)]
 pub struct MutateOrdersContext<'a> {
     // The event authority is validated by the inevitable `FlushEvents` self-CPI.
     pub event_authority: &'a AccountView,
+    /// The signer, which is either the seat's user or, for instructions that allow it, the seat's
+    /// delegate. Handlers verify which against the seat itself.
     pub user: &'a AccountView,
     pub market_account: MarketAccountView<'a>,
 }
@@ -33,6 +38,10 @@ impl<'a> MutateOrdersContext<'a> {
             dropset_program: _,
         } = PostOrder::load_accounts(accounts)?;
 
+        if !user.is_signer() {
+            return Err(DropsetError::UserMustBeSigner.into());
+        }
+
         // Safety: Scoped borrow of market account data.
         let market_account = unsafe { MarketAccountView::new(market_account) }?;
 
@@ -47,9 +56,15 @@ impl<'a> MutateOrdersContext<'a> {
 #[cfg(test)]
 pub(crate) mod tests {
     use dropset_interface::instructions::generated_program::{
+        AmendOrder,
         BatchReplace,
+        CancelAllOrders,
         CancelOrder,
+        LimitOrder,
         PostOrder,
+        RevokeSeatDelegate,
+        SeatMarketOrder,
+        SetSeatDelegate,
     };
     use pinocchio::{
         account::AccountView,
@@ -85,6 +100,12 @@ pub(crate) mod tests {
         let post_order = PostOrder::load_accounts(&account_views).unwrap();
         let cancel_order = CancelOrder::load_accounts(&account_views).unwrap();
         let batch_replace = BatchReplace::load_accounts(&account_views).unwrap();
+        let limit_order = LimitOrder::load_accounts(&account_views).unwrap();
+        let cancel_all_orders = CancelAllOrders::load_accounts(&account_views).unwrap();
+        let amend_order = AmendOrder::load_accounts(&account_views).unwrap();
+        let seat_market_order = SeatMarketOrder::load_accounts(&account_views).unwrap();
+        let set_seat_delegate = SetSeatDelegate::load_accounts(&account_views).unwrap();
+        let revoke_seat_delegate = RevokeSeatDelegate::load_accounts(&account_views).unwrap();
 
         let PostOrder {
             event_authority: po_event_authority,
@@ -107,6 +128,48 @@ pub(crate) mod tests {
             dropset_program: br_dropset_program,
         } = batch_replace;
 
+        let LimitOrder {
+            event_authority: lo_event_authority,
+            user: lo_user,
+            market_account: lo_market_account,
+            dropset_program: lo_dropset_program,
+        } = limit_order;
+
+        let CancelAllOrders {
+            event_authority: ca_event_authority,
+            user: ca_user,
+            market_account: ca_market_account,
+            dropset_program: ca_dropset_program,
+        } = cancel_all_orders;
+
+        let AmendOrder {
+            event_authority: ao_event_authority,
+            user: ao_user,
+            market_account: ao_market_account,
+            dropset_program: ao_dropset_program,
+        } = amend_order;
+
+        let SeatMarketOrder {
+            event_authority: sm_event_authority,
+            user: sm_user,
+            market_account: sm_market_account,
+            dropset_program: sm_dropset_program,
+        } = seat_market_order;
+
+        let SetSeatDelegate {
+            event_authority: sd_event_authority,
+            user: sd_user,
+            market_account: sd_market_account,
+            dropset_program: sd_dropset_program,
+        } = set_seat_delegate;
+
+        let RevokeSeatDelegate {
+            event_authority: rd_event_authority,
+            user: rd_user,
+            market_account: rd_market_account,
+            dropset_program: rd_dropset_program,
+        } = revoke_seat_delegate;
+
         // Ensure the accounts are loaded in the same exact order by comparing each unique address.
         assert_address_eq(co_event_authority, po_event_authority);
         assert_address_eq(co_user, po_user);
@@ -117,5 +180,35 @@ pub(crate) mod tests {
         assert_address_eq(br_user, po_user);
         assert_address_eq(br_market_account, po_market_account);
         assert_address_eq(br_dropset_program, po_dropset_program);
+
+        assert_address_eq(lo_event_authority, po_event_authority);
+        assert_address_eq(lo_user, po_user);
+        assert_address_eq(lo_market_account, po_market_account);
+        assert_address_eq(lo_dropset_program, po_dropset_program);
+
+        assert_address_eq(ca_event_authority, po_event_authority);
+        assert_address_eq(ca_user, po_user);
+        assert_address_eq(ca_market_account, po_market_account);
+        assert_address_eq(ca_dropset_program, po_dropset_program);
+
+        assert_address_eq(ao_event_authority, po_event_authority);
+        assert_address_eq(ao_user, po_user);
+        assert_address_eq(ao_market_account, po_market_account);
+        assert_address_eq(ao_dropset_program, po_dropset_program);
+
+        assert_address_eq(sm_event_authority, po_event_authority);
+        assert_address_eq(sm_user, po_user);
+        assert_address_eq(sm_market_account, po_market_account);
+        assert_address_eq(sm_dropset_program, po_dropset_program);
+
+        assert_address_eq(sd_event_authority, po_event_authority);
+        assert_address_eq(sd_user, po_user);
+        assert_address_eq(sd_market_account, po_market_account);
+        assert_address_eq(sd_dropset_program, po_dropset_program);
+
+        assert_address_eq(rd_event_authority, po_event_authority);
+        assert_address_eq(rd_user, po_user);
+        assert_address_eq(rd_market_account, po_market_account);
+        assert_address_eq(rd_dropset_program, po_dropset_program);
     }
 }
@@ -0,0 +1,42 @@
+//! See [`PruneExpiredOrdersContext`].
+
+use dropset_interface::instructions::generated_program::PruneExpiredOrders;
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::validation::market_account_view::MarketAccountView;
+
+/// The account context for the permissionless [`PruneExpiredOrders`] crank, validating the market
+/// account passed in.
+#[derive(Clone)]
+pub struct PruneExpiredOrdersContext<'a> {
+    // The event authority is validated by the inevitable `FlushEvents` self-CPI.
+    pub event_authority: &'a AccountView,
+    pub market_account: MarketAccountView<'a>,
+}
+
+impl<'a> PruneExpiredOrdersContext<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees no accounts passed have their data borrowed in any capacity. This is a
+    /// more restrictive safety contract than is necessary for soundness but is much simpler.
+    pub unsafe fn load(
+        accounts: &'a [AccountView],
+    ) -> Result<PruneExpiredOrdersContext<'a>, ProgramError> {
+        let PruneExpiredOrders {
+            event_authority,
+            market_account,
+            dropset_program: _,
+        } = PruneExpiredOrders::load_accounts(accounts)?;
+
+        // Safety: Scoped borrow of market account data.
+        let market_account = unsafe { MarketAccountView::new(market_account) }?;
+
+        Ok(Self {
+            event_authority,
+            market_account,
+        })
+    }
+}
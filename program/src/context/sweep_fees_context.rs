@@ -0,0 +1,92 @@
+//! See [`SweepFeesContext`].
+
+use dropset_interface::{
+    error::DropsetError,
+    instructions::generated_program::SweepFees,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use solana_address::address_eq;
+
+use crate::validation::{
+    market_account_view::MarketAccountView,
+    mint_account_view::MintAccountView,
+    token_account_view::TokenAccountView,
+};
+
+/// The account context for the [`SweepFees`] instruction, verifying the fee authority, the quote
+/// mint, and the fee recipient and market token accounts.
+#[derive(Clone)]
+pub struct SweepFeesContext<'a> {
+    // The event authority is validated by the inevitable `FlushEvents` self-CPI.
+    pub event_authority: &'a AccountView,
+    pub fee_authority: &'a AccountView,
+    pub market_account: MarketAccountView<'a>,
+    pub fee_recipient_ata: TokenAccountView<'a>,
+    pub quote_market_ata: TokenAccountView<'a>,
+    pub quote_mint: MintAccountView<'a>,
+}
+
+impl<'a> SweepFeesContext<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees no accounts passed have their data borrowed in any capacity. This is a
+    /// more restrictive safety contract than is necessary for soundness but is much simpler.
+    pub unsafe fn load(accounts: &'a [AccountView]) -> Result<SweepFeesContext<'a>, ProgramError> {
+        let SweepFees {
+            event_authority,
+            fee_authority,
+            market_account,
+            fee_recipient_ata,
+            quote_market_ata,
+            quote_mint,
+            quote_token_program: _,
+            dropset_program: _,
+        } = SweepFees::load_accounts(accounts)?;
+
+        if !fee_authority.is_signer() {
+            return Err(DropsetError::AuthorityMustBeSigner.into());
+        }
+
+        // Safety: Scoped borrow of market account data.
+        let (market_account, quote_mint) = unsafe {
+            let market_account = MarketAccountView::new(market_account)?;
+            let market = market_account.load_unchecked();
+            if !address_eq(fee_authority.address(), &market.header.fee_authority) {
+                return Err(DropsetError::IncorrectFeeAuthority.into());
+            }
+            // Protocol fees are only ever accrued in quote.
+            let quote_mint = MintAccountView::new(quote_mint, market)?;
+            if quote_mint.is_base_mint {
+                return Err(DropsetError::InvalidMintAccount.into());
+            }
+            (market_account, quote_mint)
+        };
+
+        // Safety: Scoped borrows of the fee recipient token account and market token account.
+        let (fee_recipient_ata, quote_market_ata) = unsafe {
+            let fee_recipient_ata = TokenAccountView::new(
+                fee_recipient_ata,
+                quote_mint.account.address(),
+                fee_authority.address(),
+            )?;
+            let quote_market_ata = TokenAccountView::new(
+                quote_market_ata,
+                quote_mint.account.address(),
+                market_account.account().address(),
+            )?;
+            (fee_recipient_ata, quote_market_ata)
+        };
+
+        Ok(Self {
+            event_authority,
+            fee_authority,
+            market_account,
+            fee_recipient_ata,
+            quote_market_ata,
+            quote_mint,
+        })
+    }
+}
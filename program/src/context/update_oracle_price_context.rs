@@ -0,0 +1,56 @@
+//! See [`UpdateOraclePriceContext`].
+
+use dropset_interface::{
+    error::DropsetError,
+    instructions::generated_program::UpdateOraclePrice,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use solana_address::address_eq;
+
+use crate::validation::market_account_view::MarketAccountView;
+
+/// The account context for the permissionless [`UpdateOraclePrice`] crank, validating the market
+/// account and that the oracle account passed in is the market's oracle.
+#[derive(Clone)]
+pub struct UpdateOraclePriceContext<'a> {
+    // The event authority is validated by the inevitable `FlushEvents` self-CPI.
+    pub event_authority: &'a AccountView,
+    pub market_account: MarketAccountView<'a>,
+    pub oracle: &'a AccountView,
+}
+
+impl<'a> UpdateOraclePriceContext<'a> {
+    /// # Safety
+    ///
+    /// Caller guarantees no accounts passed have their data borrowed in any capacity. This is a
+    /// more restrictive safety contract than is necessary for soundness but is much simpler.
+    pub unsafe fn load(
+        accounts: &'a [AccountView],
+    ) -> Result<UpdateOraclePriceContext<'a>, ProgramError> {
+        let UpdateOraclePrice {
+            event_authority,
+            market_account,
+            oracle,
+            dropset_program: _,
+        } = UpdateOraclePrice::load_accounts(accounts)?;
+
+        // Safety: Scoped borrow of market account data.
+        let market_account = unsafe {
+            let market_account = MarketAccountView::new(market_account)?;
+            let market = market_account.load_unchecked();
+            if !market.header.has_oracle() || !address_eq(oracle.address(), &market.header.oracle) {
+                return Err(DropsetError::IncorrectOracle.into());
+            }
+            market_account
+        };
+
+        Ok(Self {
+            event_authority,
+            market_account,
+            oracle,
+        })
+    }
+}
@@ -64,15 +64,73 @@ pub fn process_instruction(
             DropsetInstruction::CancelOrder => {
                 process_cancel_order(accounts, instruction_data, event_buffer)
             }
+            DropsetInstruction::BatchReplace => {
+                process_batch_replace(accounts, instruction_data, event_buffer)
+            }
             DropsetInstruction::MarketOrder => {
                 process_market_order(accounts, instruction_data, event_buffer)
             }
+            DropsetInstruction::LimitOrder => {
+                process_limit_order(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::SweepFees => {
+                process_sweep_fees(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::UpdateMarketParams => {
+                process_update_market_params(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::TransferMarketAuthority => {
+                process_transfer_market_authority(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::RenounceMarketAuthority => {
+                process_renounce_market_authority(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::UpdateMarketStatus => {
+                process_update_market_status(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::CancelAllOrders => {
+                process_cancel_all_orders(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::PruneExpiredOrders => {
+                process_prune_expired_orders(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::AmendOrder => {
+                process_amend_order(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::SeatMarketOrder => {
+                process_seat_market_order(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::CompactMarket => {
+                process_compact_market(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::ExpandMarket => {
+                process_expand_market(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::CloseMarket => {
+                process_close_market(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::Swap => process_swap(accounts, instruction_data, event_buffer),
+            DropsetInstruction::SetSeatDelegate => {
+                process_set_seat_delegate(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::RevokeSeatDelegate => {
+                process_revoke_seat_delegate(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::AddAllowlistedTrader => {
+                process_add_allowlisted_trader(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::RemoveAllowlistedTrader => {
+                process_remove_allowlisted_trader(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::SetMarketOracle => {
+                process_set_market_oracle(accounts, instruction_data, event_buffer)
+            }
+            DropsetInstruction::UpdateOraclePrice => {
+                process_update_oracle_price(accounts, instruction_data, event_buffer)
+            }
             DropsetInstruction::FlushEvents => {
                 return process_flush_events(accounts, instruction_data)
             }
-            DropsetInstruction::BatchReplace => {
-                return process_batch_replace(accounts, instruction_data)
-            }
         }
     }?;
 
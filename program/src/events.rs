@@ -191,6 +191,33 @@ impl EventBuffer {
     }
 }
 
+/// A destination for the events emitted while matching an order against the book.
+pub trait EventSink<'a> {
+    fn emit<T: Tagged>(
+        &mut self,
+        packable_event: T,
+        market_account: MarketAccountView<'a>,
+    ) -> ProgramResult;
+}
+
+/// Emits events to an [`EventBuffer`], flushing through the event authority as needed.
+pub struct BufferedEvents<'a, 'b> {
+    pub event_buffer: &'b mut EventBuffer,
+    pub event_authority: &'a AccountView,
+}
+
+impl<'a> EventSink<'a> for BufferedEvents<'a, '_> {
+    #[inline(always)]
+    fn emit<T: Tagged>(
+        &mut self,
+        packable_event: T,
+        market_account: MarketAccountView<'a>,
+    ) -> ProgramResult {
+        self.event_buffer
+            .add_to_buffer(packable_event, self.event_authority, market_account)
+    }
+}
+
 #[test]
 fn test_max_cpi_len() {
     pub const MAX_CPI_INSTRUCTION_DATA_LEN: usize = 10 * 1024;
@@ -0,0 +1,97 @@
+//! See [`process_add_allowlisted_trader`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::AddAllowlistedTraderEventInstructionData,
+    instructions::AddAllowlistedTraderInstructionData,
+    state::market_seat::MarketSeat,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::seat_operations::{
+        find_mut_seat_with_hint,
+        find_seat_by_address,
+        try_insert_market_seat,
+    },
+};
+
+/// Instruction handler logic for the market authority adding a trader to a permissioned market's
+/// allowlist.
+///
+/// A trader's allowlisting is stored on their market seat. If the trader already has a seat it's
+/// marked as allowlisted, otherwise an empty, allowlisted seat is registered for them, resizing the
+/// market account at the authority's expense if there are no free sectors. The trader's existing
+/// seat is looked up before resizing, so the authority only pays for a sector that's used by a new
+/// seat. The trader can then deposit into it as usual.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::AddAllowlistedTrader`].
+#[inline(never)]
+pub unsafe fn process_add_allowlisted_trader<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let AddAllowlistedTraderInstructionData { trader } =
+        AddAllowlistedTraderInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    let (existing_seat_index, needs_resize) = {
+        // Safety: Scoped borrow of the market account data to check that it's permissioned and
+        // isn't being compacted, find the trader's seat and check the number of free sectors.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        if !market.header.is_permissioned() {
+            return Err(DropsetError::MarketNotPermissioned.into());
+        }
+        market.header.check_not_compacting()?;
+        let existing_seat_index = find_seat_by_address(&market, &trader);
+        let needs_resize = existing_seat_index.is_none() && market.header.num_free_sectors() == 0;
+        (existing_seat_index, needs_resize)
+    };
+
+    let (sector_index, registered_seat) = if let Some(sector_index) = existing_seat_index {
+        // Safety: Scoped mutable borrow of the market account to update the trader's seat.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        // Safety: The seat index was just found by iterating the seats list, so it's in-bounds.
+        unsafe { find_mut_seat_with_hint(&mut market, sector_index, &trader) }?
+            .set_allowlisted(true);
+        (sector_index, false)
+    } else {
+        // The trader has no seat, so inserting the new seat below can't fail as a duplicate.
+        if needs_resize {
+            // Safety: Scoped mutable borrow to resize the market account and add a new sector.
+            unsafe { ctx.market_account.resize(ctx.authority, 1) }?;
+        }
+
+        // Safety: Scoped mutable borrow of market account data to insert the new seat.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+
+        let mut seat = MarketSeat::new(trader, 0, 0);
+        seat.set_allowlisted(true);
+        (try_insert_market_seat(&mut market.seats(), seat)?, true)
+    };
+
+    event_buffer.add_to_buffer(
+        AddAllowlistedTraderEventInstructionData::new(trader, sector_index, registered_seat),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,108 @@
+//! See [`process_amend_order`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::AmendOrderEventInstructionData,
+    instructions::AmendOrderInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use price::to_order_info;
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::{
+        order_operations::{
+            amend_user_order,
+            current_unix_timestamp,
+        },
+        seat_operations::find_authorized_seat,
+    },
+};
+
+/// Instruction handler logic for amending the size of a user's bid or ask order on the market's
+/// order book without cancelling and re-posting it.
+///
+/// Reducing an order's size keeps its queue position, while increasing it moves the order to the
+/// back of its price level. Expired orders can't be amended. Increasing an order's size requires an
+/// active market, but reducing it is allowed whenever users can exit the market.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::AmendOrder`].
+#[inline(never)]
+pub unsafe fn process_amend_order<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let AmendOrderInstructionData {
+        order_sector_index,
+        is_bid,
+        order_info_args,
+        user_sector_index_hint,
+    } = AmendOrderInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+    let unix_timestamp = current_unix_timestamp()?;
+
+    let order_info = to_order_info(order_info_args).map_err(DropsetError::from)?;
+    if order_info.base_atoms == 0 || order_info.quote_atoms == 0 {
+        return Err(DropsetError::AmountCannotBeZero.into());
+    }
+
+    let amended = {
+        // Safety: Scoped mutable borrow of the market account data to amend the order.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+
+        market.header.check_allows_exits()?;
+        market.header.check_order_info(&order_info)?;
+        let (user_sector_index, user) =
+            find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), false)?;
+
+        let amended = amend_user_order(
+            &mut market,
+            &user,
+            order_sector_index,
+            is_bid,
+            user_sector_index,
+            order_info,
+            unix_timestamp,
+        )?;
+
+        // Only size increases add liquidity to the book, so only they require an active market.
+        if amended.requeued {
+            market.header.check_is_active()?;
+        }
+
+        amended
+    };
+
+    event_buffer.add_to_buffer(
+        AmendOrderEventInstructionData::new(
+            is_bid,
+            user_sector_index,
+            order_sector_index,
+            amended.order_sector_index,
+            order_info.base_atoms,
+            order_info.quote_atoms,
+            amended.requeued,
+            amended.client_order_id,
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -1,18 +1,143 @@
 //! See [`process_batch_replace`].
 
+#[cfg(feature = "debug")]
+use dropset_interface::events::{
+    CancelOrderEventInstructionData,
+    PostOrderEventInstructionData,
+};
+use dropset_interface::{
+    error::DropsetError,
+    instructions::BatchReplaceInstructionData,
+};
 use pinocchio::{
     account::AccountView,
-    ProgramResult,
+    error::ProgramError,
+};
+use price::to_order_info;
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::{
+        order_operations::{
+            cancel_user_order,
+            current_unix_timestamp,
+            post_user_order,
+            PostOrderOptions,
+        },
+        seat_operations::find_authorized_seat,
+    },
 };
 
-/// Handler logic for executing multiple instructions in a single atomic batch.
+/// Instruction handler logic for atomically cancelling and then posting multiple of a user's bid
+/// and ask orders on the market's order book.
+///
+/// All cancels are applied before any posts, so that the collateral returned by each cancel can be
+/// used to post the new orders. If any single cancel or post fails, the entire batch fails.
 ///
 /// # Safety
 ///
-/// Since the accounts borrowed depend on the inner batch instructions, the most straightforward
-/// safety contract is simply ensuring that **no Solana account data is currently borrowed** prior
-/// to calling this instruction.
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::BatchReplace`].
 #[inline(never)]
-pub fn process_batch_replace(_accounts: &[AccountView], _instruction_data: &[u8]) -> ProgramResult {
-    Ok(())
+pub unsafe fn process_batch_replace<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    _event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let BatchReplaceInstructionData {
+        cancels,
+        posts,
+        user_sector_index_hint,
+    } = BatchReplaceInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    // Cancels are allowed whenever users can exit the market, but posts require an active market.
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status and find the
+        // user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_allows_exits()?;
+        if !posts.as_slice().is_empty() {
+            market.header.check_is_active()?;
+        }
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), false)?
+    };
+
+    for cancel in cancels.as_slice() {
+        let _client_order_id = {
+            // Safety: Scoped mutable borrow of the market account data, dropped before the event
+            // buffer borrows it.
+            let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+            cancel_user_order(
+                &mut market,
+                &user,
+                cancel.order_sector_index,
+                cancel.is_bid,
+                user_sector_index,
+            )?
+        };
+
+        #[cfg(feature = "debug")]
+        _event_buffer.add_to_buffer(
+            CancelOrderEventInstructionData::new(
+                cancel.is_bid,
+                user_sector_index,
+                _client_order_id,
+            ),
+            ctx.event_authority,
+            ctx.market_account.clone(),
+        )?;
+    }
+
+    let unix_timestamp = current_unix_timestamp()?;
+
+    for post in posts.as_slice() {
+        let order_info = to_order_info(post.order_info_args.clone()).map_err(DropsetError::from)?;
+        #[cfg(feature = "debug")]
+        let (base_atoms, quote_atoms) = (order_info.base_atoms, order_info.quote_atoms);
+
+        let _order_sector_index = {
+            // Safety: Scoped mutable borrow of the market account data, dropped before the event
+            // buffer borrows it.
+            let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+            market.header.check_order_info(&order_info)?;
+            post_user_order(
+                &mut market,
+                &user,
+                order_info,
+                post.is_bid,
+                user_sector_index,
+                PostOrderOptions {
+                    expiry: post.expiry,
+                    client_order_id: post.client_order_id,
+                },
+                unix_timestamp,
+            )?
+        };
+
+        #[cfg(feature = "debug")]
+        _event_buffer.add_to_buffer(
+            PostOrderEventInstructionData::new(
+                post.is_bid,
+                user_sector_index,
+                _order_sector_index,
+                base_atoms,
+                quote_atoms,
+                post.client_order_id,
+            ),
+            ctx.event_authority,
+            ctx.market_account.clone(),
+        )?;
+    }
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
 }
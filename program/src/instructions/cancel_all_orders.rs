@@ -0,0 +1,110 @@
+//! See [`process_cancel_all_orders`].
+
+use dropset_interface::{
+    events::CancelOrderEventInstructionData,
+    instructions::CancelAllOrdersInstructionData,
+    state::{
+        market_seat::MarketSeat,
+        sector::{
+            Sector,
+            NIL,
+        },
+    },
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::{
+        order_operations::cancel_user_order,
+        seat_operations::find_authorized_seat,
+    },
+};
+
+/// Instruction handler logic for cancelling all of a user's bids and/or asks on the market's order
+/// book in a single instruction.
+///
+/// Each order's remaining collateral is returned to the user's seat and a
+/// [`dropset_interface::events::DropsetEventTag::CancelOrderEvent`] is emitted per cancelled order.
+/// A side with no orders is simply a no-op.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::CancelAllOrders`].
+#[inline(never)]
+pub unsafe fn process_cancel_all_orders<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let CancelAllOrdersInstructionData {
+        cancel_bids,
+        cancel_asks,
+        user_sector_index_hint,
+    } = CancelAllOrdersInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status and find the
+        // user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_allows_exits()?;
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), false)?
+    };
+
+    for (is_bid, should_cancel) in [(true, cancel_bids), (false, cancel_asks)] {
+        if !should_cancel {
+            continue;
+        }
+        // Cancel the first of the user's orders on this side until there are none left.
+        loop {
+            let client_order_id = {
+                // Safety: The market account is not borrowed anywhere else. The event buffer only
+                // borrows it after this scoped borrow is dropped.
+                let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+
+                // Safety: The user's seat index was just verified as in-bounds.
+                let user_seat =
+                    unsafe { Sector::from_sector_index(market.sectors, user_sector_index) }
+                        .load_payload::<MarketSeat>();
+                let order_sector_index = if is_bid {
+                    user_seat.user_order_sectors.bids.head()
+                } else {
+                    user_seat.user_order_sectors.asks.head()
+                };
+                if order_sector_index == NIL {
+                    break;
+                }
+
+                cancel_user_order(
+                    &mut market,
+                    &user,
+                    order_sector_index,
+                    is_bid,
+                    user_sector_index,
+                )?
+            };
+
+            event_buffer.add_to_buffer(
+                CancelOrderEventInstructionData::new(is_bid, user_sector_index, client_order_id),
+                ctx.event_authority,
+                ctx.market_account.clone(),
+            )?;
+        }
+    }
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -5,11 +5,8 @@ use dropset_interface::events::CancelOrderEventInstructionData;
 use dropset_interface::{
     instructions::CancelOrderInstructionData,
     state::{
-        market_seat::MarketSeat,
-        sector::{
-            Sector,
-            SectorIndex,
-        },
+        order::NO_CLIENT_ORDER_ID,
+        sector::Sector,
     },
 };
 use pinocchio::{
@@ -24,13 +21,20 @@ use crate::{
     },
     events::EventBuffer,
     shared::{
-        order_operations::load_order_from_sector_index,
-        seat_operations::find_mut_seat_with_hint,
+        order_operations::{
+            cancel_user_order,
+            find_user_order_by_client_order_id,
+        },
+        seat_operations::find_authorized_seat,
     },
 };
 
 /// Instruction handler logic for cancelling a user's bid or ask order on the market's order book.
 ///
+/// The order is identified by its sector index, unless a non-zero client order id is passed, in
+/// which case the order with that client order id is cancelled instead. Either way, the order must
+/// belong to the user's seat.
+///
 /// # Safety
 ///
 /// Caller upholds the safety contract detailed in
@@ -42,76 +46,53 @@ pub unsafe fn process_cancel_order<'a>(
     _event_buffer: &mut EventBuffer,
 ) -> Result<EventBufferContext<'a>, ProgramError> {
     let CancelOrderInstructionData {
-        encoded_price,
+        order_sector_index,
         is_bid,
         user_sector_index_hint,
+        client_order_id,
     } = CancelOrderInstructionData::unpack_untagged(instruction_data)?;
 
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
 
-    // Safety: The market account is currently not borrowed in any capacity.
-    let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
-
-    // Remove the order from the user seat's order sectors mapping.
-    let order_sector_index = {
-        Sector::check_in_bounds(market.sectors, user_sector_index_hint)?;
-        // Safety: The user sector index hint was just verified in-bounds.
-        let user_seat = unsafe {
-            find_mut_seat_with_hint(&mut market, user_sector_index_hint, ctx.user.address())
-        }?;
-        if is_bid {
-            SectorIndex::from_le_bytes(user_seat.user_order_sectors.bids.remove(encoded_price)?)
-        } else {
-            SectorIndex::from_le_bytes(user_seat.user_order_sectors.asks.remove(encoded_price)?)
-        }
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status and find the
+        // user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_allows_exits()?;
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), false)?
     };
 
-    // The safety comment below explains why this isn't explicitly necessary.
-    debug_assert!(Sector::check_in_bounds(market.sectors, order_sector_index).is_ok());
-
-    // Load the order given the order sector index.
-    let order = {
-        // Safety: The order sector index returned from the `remove` method still points to a
-        // sector with a valid order. All order sector indices in a user seat are thus in-bounds and
-        // don't need to be explicitly verified as in-bounds.
-        load_order_from_sector_index(&market, order_sector_index)
-    };
+    // Safety: The market account is currently not borrowed in any capacity.
+    let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
 
-    // Increment the user's collateral in their market seat by the amount remaining in the order.
-    if is_bid {
-        // If the user placed a bid, they provided quote as collateral.
-        let order_size_remaining = order.quote_remaining();
-        // Safety: The seat hint was already validated as in-bounds. It could only possibly be out
-        // of bounds now if the account data size was just reduced, which it was not.
-        let sector =
-            unsafe { Sector::from_sector_index_mut(market.sectors, user_sector_index_hint) };
-        let user_seat = sector.load_payload_mut::<MarketSeat>();
-        user_seat.try_increment_quote_available(order_size_remaining)?;
+    let order_sector_index = if client_order_id == NO_CLIENT_ORDER_ID {
+        order_sector_index
     } else {
-        // If the user placed an ask, they provided base as collateral.
-        let order_size_remaining = order.base_remaining();
-        // Safety: The seat hint was already validated as in-bounds. It could only possibly be out
-        // of bounds now if the account data size was just reduced, which it was not.
-        let sector =
-            unsafe { Sector::from_sector_index_mut(market.sectors, user_sector_index_hint) };
-        let user_seat = sector.load_payload_mut::<MarketSeat>();
-        user_seat.try_increment_base_available(order_size_remaining)?;
-    }
+        Sector::check_in_bounds(market.sectors, user_sector_index)?;
+        // Safety: The user's seat index was just verified as in-bounds.
+        unsafe {
+            find_user_order_by_client_order_id(
+                &market,
+                &user,
+                is_bid,
+                user_sector_index,
+                client_order_id,
+            )
+        }?
+    };
 
-    // Remove the order at the order sector index from the appropriate orders collection.
-    unsafe {
-        // Safety: The order sector index from the `remove` method is still in-bounds.
-        if is_bid {
-            market.bids().remove_at(order_sector_index);
-        } else {
-            market.asks().remove_at(order_sector_index);
-        }
-    }
+    let _client_order_id = cancel_user_order(
+        &mut market,
+        &user,
+        order_sector_index,
+        is_bid,
+        user_sector_index,
+    )?;
 
     #[cfg(feature = "debug")]
     _event_buffer.add_to_buffer(
-        CancelOrderEventInstructionData::new(is_bid, user_sector_index_hint),
+        CancelOrderEventInstructionData::new(is_bid, user_sector_index, _client_order_id),
         ctx.event_authority,
         ctx.market_account.clone(),
     )?;
@@ -0,0 +1,150 @@
+//! See [`process_close_market`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::CloseMarketEventInstructionData,
+    utils::is_owned_by_spl_token,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        close_market_context::CloseMarketContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    market_signer,
+    shared::token_utils::market_transfers::withdraw_non_zero_from_market,
+};
+
+/// Instruction handler logic for tearing down an empty market.
+///
+/// The market must have no seats, bids or asks, and its protocol fees must have been swept. Any
+/// tokens left in the market token accounts after that, e.g. tokens sent to them directly, aren't
+/// owed to anyone and are sent to the closer's token accounts. Both market token accounts are then
+/// closed and all rent, including the market account's, is refunded to the closer. The market's
+/// discriminant is zeroed so the account is no longer recognized as a market.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::CloseMarket`].
+#[inline(never)]
+pub unsafe fn process_close_market<'a>(
+    accounts: &'a [AccountView],
+    _instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    // Safety: No account data in `accounts` is currently borrowed.
+    let ctx = unsafe { CloseMarketContext::load(accounts) }?;
+
+    let market_bump = {
+        // Safety: Scoped borrow of the market account data to check that the market is empty.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        let header = market.header;
+        if header.num_seats() != 0 || header.num_bids() != 0 || header.num_asks() != 0 {
+            return Err(DropsetError::MarketNotEmpty.into());
+        }
+        if header.protocol_fees() != 0 {
+            return Err(DropsetError::ProtocolFeesNotSwept.into());
+        }
+        header.market_bump
+    };
+
+    // Sweep any remaining tokens to the closer, since a token account can only be closed once it's
+    // empty, then close both market token accounts, refunding their rent to the closer.
+    let mut amounts_swept = [0; 2];
+    for ((mint, market_ata, closer_ata), amount_swept) in [
+        (&ctx.base_mint, &ctx.base_market_ata, &ctx.closer_base_ata),
+        (
+            &ctx.quote_mint,
+            &ctx.quote_market_ata,
+            &ctx.closer_quote_ata,
+        ),
+    ]
+    .into_iter()
+    .zip(amounts_swept.iter_mut())
+    {
+        // Safety: Scoped immutable borrow of the market token account data to get its balance.
+        *amount_swept = unsafe { market_ata.get_balance() }?;
+        if *amount_swept != 0 {
+            // Safety: The market account data isn't currently borrowed.
+            unsafe {
+                withdraw_non_zero_from_market(
+                    closer_ata,
+                    market_ata,
+                    &ctx.market_account,
+                    mint,
+                    *amount_swept,
+                )
+            }?;
+        }
+
+        if is_owned_by_spl_token(mint.account) {
+            pinocchio_token::instructions::CloseAccount {
+                account: market_ata.account,             // WRITE
+                destination: ctx.closer,                 // WRITE
+                authority: ctx.market_account.account(), // READ
+            }
+            .invoke_signed(&[market_signer!(
+                ctx.base_mint.account.address(),
+                ctx.quote_mint.account.address(),
+                market_bump
+            )])?;
+        } else {
+            pinocchio_token_2022::instructions::CloseAccount {
+                account: market_ata.account,             // WRITE
+                destination: ctx.closer,                 // WRITE
+                authority: ctx.market_account.account(), // READ
+                token_program: &pinocchio_token_2022::ID,
+            }
+            .invoke_signed(&[market_signer!(
+                ctx.base_mint.account.address(),
+                ctx.quote_mint.account.address(),
+                market_bump
+            )])?;
+        }
+    }
+
+    let market_account = ctx.market_account.account();
+    let lamports_refunded = market_account.lamports();
+    let closer_lamports = ctx
+        .closer
+        .lamports()
+        .checked_add(lamports_refunded)
+        .ok_or(DropsetError::ArithmeticOverflow)?;
+
+    event_buffer.add_to_buffer(
+        CloseMarketEventInstructionData::new(
+            *ctx.closer.address(),
+            lamports_refunded,
+            amounts_swept[0],
+            amounts_swept[1],
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    // Flush the events now, since flushing writes to the market header and the market account can't
+    // be loaded once its discriminant is zeroed. The final flush in the entrypoint is then a no-op.
+    // Safety: The market account is not currently borrowed in any capacity.
+    unsafe { event_buffer.flush_events(ctx.event_authority, ctx.market_account.clone()) }?;
+
+    {
+        // Safety: Scoped mutable borrow of the market account data to zero the discriminant.
+        let mut market_account = ctx.market_account.clone();
+        let market = unsafe { market_account.load_unchecked_mut() };
+        market.header.clear_discriminant();
+    }
+
+    market_account.set_lamports(0);
+    ctx.closer.set_lamports(closer_lamports);
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
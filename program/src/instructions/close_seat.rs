@@ -3,7 +3,6 @@
 use dropset_interface::{
     events::CloseSeatEventInstructionData,
     instructions::CloseSeatInstructionData,
-    state::sector::Sector,
     utils::is_owned_by_spl_token,
 };
 use pinocchio::{
@@ -18,7 +17,11 @@ use crate::{
     },
     events::EventBuffer,
     market_signer,
-    shared::seat_operations::find_seat_with_hint,
+    shared::seat_operations::{
+        find_seat_sector_index,
+        find_seat_with_hint,
+        remove_seat_delegate,
+    },
 };
 
 /// Instruction handler logic for closing an existing market seat and reclaiming associated funds.
@@ -39,27 +42,42 @@ pub unsafe fn process_close_seat<'a>(
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { CloseSeatContext::load(accounts) }?;
 
-    // Remove the seat after copying the market bump and the seat's base and quote available.
-    let (market_bump, base_available, quote_available) = unsafe {
+    // Safety: Scoped borrow of the market account data to check the market status.
+    unsafe { ctx.market_account.load_unchecked() }
+        .header
+        .check_allows_exits()?;
+
+    // Remove the seat after copying its sector index, the market bump and the seat's base and quote
+    // available.
+    let (sector_index, market_bump, base_available, quote_available) = unsafe {
         // Safety: Scoped mutable borrow of market account data.
         let mut market = ctx.market_account.load_unchecked_mut();
 
         // --- read market data ---
         // Copy the market bump and the seat's base and quote amounts available to the user.
         let market_bump = market.header.market_bump;
-        Sector::check_in_bounds(market.sectors, sector_index_hint)?;
-        // Safety: The index hint was just verified as in-bounds.
-        let seat = find_seat_with_hint(&market, sector_index_hint, ctx.user.address())?;
+        let sector_index = find_seat_sector_index(&market, sector_index_hint, ctx.user.address())?;
+        // Safety: The seat index was just verified as in-bounds.
+        let seat = find_seat_with_hint(&market, sector_index, ctx.user.address())?;
         // NOTE: The base/quote available and deposited do not need to be zeroed here because
         // they're zeroed out in the `push_free_sector` call in the `remove_at` method below.
-        let copied_values = (market_bump, seat.base_available(), seat.quote_available());
+        let copied_values = (
+            sector_index,
+            market_bump,
+            seat.base_available(),
+            seat.quote_available(),
+        );
 
         // --- write market data ---
+        // Free the seat's delegate sector, if it has one.
+        // Safety: The seat index was verified as the user's seat.
+        remove_seat_delegate(&mut market, sector_index);
+
         // Remove the seat, push it to the free stack, and zero it out.
         market
             .seats()
-            // Safety: The index hint was verified as in-bounds.
-            .remove_at(sector_index_hint);
+            // Safety: The seat index was verified as in-bounds.
+            .remove_at(sector_index);
 
         copied_values
     };
@@ -133,7 +151,7 @@ pub unsafe fn process_close_seat<'a>(
     }
 
     event_buffer.add_to_buffer(
-        CloseSeatEventInstructionData::new(sector_index_hint),
+        CloseSeatEventInstructionData::new(sector_index),
         ctx.event_authority,
         ctx.market_account.clone(),
     )?;
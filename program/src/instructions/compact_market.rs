@@ -0,0 +1,96 @@
+//! See [`process_compact_market`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::CompactMarketEventInstructionData,
+    instructions::CompactMarketInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::market_operations::compact_market_sectors,
+};
+
+/// Instruction handler logic for the market authority compacting the market account's sectors to
+/// reclaim rent.
+///
+/// Live seats and orders are relocated toward the front of the sectors region, visiting at most
+/// `max_sectors` live sectors per instruction. Large markets are compacted by sending the
+/// instruction until its event reports the compaction as complete, at which point all free sectors
+/// are truncated from the end of the account and the lamports no longer required for rent are
+/// refunded to the market authority.
+///
+/// The market must be paused for the whole compaction, so that no sectors are allocated or freed
+/// while it's in progress.
+///
+/// Relocated seats and orders get new sector indices and no per-sector events are emitted, so
+/// clients holding stale seat index hints should pass `NIL` to find their seat by address, and
+/// clients tracking order sector indices should refetch the market's orders afterwards.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::CompactMarket`].
+#[inline(never)]
+pub unsafe fn process_compact_market<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let CompactMarketInstructionData { max_sectors } =
+        CompactMarketInstructionData::unpack_untagged(instruction_data)?;
+    if max_sectors == 0 {
+        return Err(DropsetError::InvalidNonZeroInteger.into());
+    }
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    let (num_sectors_relocated, num_sectors_removed) = {
+        // Safety: Scoped mutable borrow of the market account data to compact its sectors.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        market.header.check_is_paused()?;
+        let num_sectors = market.get_capacity();
+        let progress = compact_market_sectors(&mut market, max_sectors)?;
+        (
+            progress.num_sectors_relocated,
+            progress
+                .num_live_sectors
+                .map(|num_live_sectors| num_sectors - num_live_sectors),
+        )
+    };
+
+    let lamports_refunded = match num_sectors_removed {
+        // Safety: The market account data is no longer borrowed, and the trailing free sectors
+        // were removed from the free stack when the compaction started.
+        Some(num_sectors_removed) => unsafe {
+            ctx.market_account
+                .truncate(ctx.authority, num_sectors_removed)
+        }?,
+        None => 0,
+    };
+
+    event_buffer.add_to_buffer(
+        CompactMarketEventInstructionData::new(
+            num_sectors_relocated,
+            num_sectors_removed.is_some(),
+            num_sectors_removed.unwrap_or(0),
+            lamports_refunded,
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -5,10 +5,7 @@ use dropset_interface::{
     instructions::DepositInstructionData,
     state::{
         market_seat::MarketSeat,
-        sector::{
-            Sector,
-            NIL,
-        },
+        sector::NIL,
     },
 };
 use pinocchio::{
@@ -25,6 +22,8 @@ use crate::{
     shared::{
         seat_operations::{
             find_mut_seat_with_hint,
+            find_seat_by_address,
+            find_seat_sector_index,
             try_insert_market_seat,
         },
         token_utils::market_transfers::deposit_non_zero_to_market,
@@ -35,14 +34,17 @@ use crate::{
 ///
 /// There are two paths:
 ///
-/// 1) User provided a non-NIL sector index hint: update an existing seat.
-///   - Try to find the seat with the user's sector index hint.
-///   - If invalid return early, otherwise update the seat with the amount deposited.
+/// 1) The user already has a seat: update the existing seat.
+///   - Find the seat with the user's sector index hint, or by the user's address if the hint is
+///     NIL.
+///   - If a non-NIL hint is invalid return early, otherwise update the seat with the amount
+///     deposited.
 ///
-/// 2) The user didn't provide a non-NIL sector index hint: register a new seat.
+/// 2) The user provided a NIL sector index hint and has no seat: register a new seat.
 ///   - Check if the account needs extra storage and resize it if so.
 ///   - Then register the user's new seat at the proper index with the amount deposited data.
-///   - If the user already exists, return an error instead of inserting.
+///   - Permissioned markets only allow the market authority to register seats, so this path always
+///     fails on them.
 ///
 /// # Safety
 ///
@@ -62,19 +64,37 @@ pub unsafe fn process_deposit<'a>(
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { DepositWithdrawContext::load(accounts) }?;
 
+    // Safety: Scoped borrow of the market account data to check the market status.
+    unsafe { ctx.market_account.load_unchecked() }
+        .header
+        .check_is_active()?;
+
     // Safety: No account data is currently borrowed.
     let amount_deposited = unsafe {
         deposit_non_zero_to_market(&ctx.user_ata, &ctx.market_ata, ctx.user, &ctx.mint, amount)
     }?;
 
+    let existing_seat_index = {
+        // Safety: Scoped borrow of the market account data to find the user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        if sector_index_hint != NIL {
+            Some(find_seat_sector_index(
+                &market,
+                sector_index_hint,
+                ctx.user.address(),
+            )?)
+        } else {
+            find_seat_by_address(&market, ctx.user.address())
+        }
+    };
+
     // 1) Update an existing seat.
-    let sector_index = if sector_index_hint != NIL {
+    let sector_index = if let Some(sector_index) = existing_seat_index {
         // Safety: Scoped mutable borrow of the market account to mutate the user's seat.
         let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
-        Sector::check_in_bounds(market.sectors, sector_index_hint)?;
-        // Safety: The index hint was just verified as in-bounds.
+        // Safety: The seat index was just verified as in-bounds.
         let seat =
-            unsafe { find_mut_seat_with_hint(&mut market, sector_index_hint, ctx.user.address()) }?;
+            unsafe { find_mut_seat_with_hint(&mut market, sector_index, ctx.user.address()) }?;
 
         if ctx.mint.is_base_mint {
             seat.set_base_available(
@@ -90,14 +110,23 @@ pub unsafe fn process_deposit<'a>(
             );
         }
 
-        sector_index_hint
+        sector_index
     } else {
         // 2) Register a new seat.
-        // Safety: Scoped immutable borrow of the market account, checks the number of free sectors.
-        let needs_resize = unsafe { ctx.market_account.load_unchecked() }
-            .header
-            .num_free_sectors()
-            == 0;
+        let seat = if ctx.mint.is_base_mint {
+            MarketSeat::new(*ctx.user.address(), amount_deposited, 0)
+        } else {
+            MarketSeat::new(*ctx.user.address(), 0, amount_deposited)
+        };
+
+        // Safety: Scoped immutable borrow of the market account, checks that the user may register
+        // a seat before checking the number of free sectors, so that a rejected user never pays
+        // to resize the market.
+        let needs_resize = {
+            let market = unsafe { ctx.market_account.load_unchecked() };
+            market.header.check_seat_allowlisted(&seat)?;
+            market.header.num_free_sectors() == 0
+        };
 
         if needs_resize {
             // Safety: Scoped mutable borrow to resize the market account and add a new sector.
@@ -107,12 +136,6 @@ pub unsafe fn process_deposit<'a>(
         // Safety: Scoped mutable borrow of market account data to insert the new seat.
         let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
 
-        let seat = if ctx.mint.is_base_mint {
-            MarketSeat::new(*ctx.user.address(), amount_deposited, 0)
-        } else {
-            MarketSeat::new(*ctx.user.address(), 0, amount_deposited)
-        };
-
         // Attempts to insert the user into the linked list. If the user already exists, this fails.
         try_insert_market_seat(&mut market.seats(), seat)?
     };
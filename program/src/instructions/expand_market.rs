@@ -0,0 +1,70 @@
+//! See [`process_expand_market`].
+
+use dropset_interface::{
+    events::ExpandMarketEventInstructionData,
+    instructions::ExpandMarketInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        expand_market_context::ExpandMarketContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for permissionlessly adding free sectors to a market account, e.g. to
+/// pre-allocate capacity for a busy market ahead of time.
+///
+/// The payer funds the additional rent, minus any lamports the market account already holds in
+/// excess of its rent-exempt minimum. Closed markets and markets being compacted can't be expanded.
+///
+/// Since the runtime limits how much an account can grow in a single instruction, large expansions
+/// should be split across multiple instructions.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::ExpandMarket`].
+#[inline(never)]
+pub unsafe fn process_expand_market<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let ExpandMarketInstructionData { num_sectors } =
+        ExpandMarketInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { ExpandMarketContext::load(accounts) }?;
+
+    {
+        // Safety: Scoped borrow of the market account data to check its status.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        // A closed market never allocates sectors again.
+        market.header.check_not_closed()?;
+        // A compaction fixes the number of sectors it truncates the market to when it starts.
+        market.header.check_not_compacting()?;
+    }
+
+    // Safety: No account data is currently borrowed. This fails if `num_sectors` is zero.
+    unsafe { ctx.market_account.resize(ctx.payer, num_sectors) }?;
+
+    // Safety: Scoped borrow of the market account data to get the market's new capacity.
+    let capacity = unsafe { ctx.market_account.load_unchecked() }.get_capacity();
+
+    event_buffer.add_to_buffer(
+        ExpandMarketEventInstructionData::new(num_sectors, capacity),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
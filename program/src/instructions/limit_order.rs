@@ -0,0 +1,205 @@
+//! See [`process_limit_order`].
+
+use core::num::NonZeroU64;
+
+use dropset_interface::{
+    error::DropsetError,
+    events::LimitOrderEventInstructionData,
+    instructions::{
+        LimitOrderInstructionData,
+        TimeInForce,
+    },
+    matching::{
+        mul_div_checked,
+        swap_amounts,
+        AmountsFilled,
+        TakerOrder,
+    },
+    state::sector::{
+        Sector,
+        NIL,
+    },
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+use price::{
+    to_order_info,
+    OrderInfo,
+};
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::{
+        BufferedEvents,
+        EventBuffer,
+    },
+    instructions::market_order::fill_market_order::fill_market_order,
+    shared::{
+        order_operations::{
+            current_unix_timestamp,
+            post_user_order,
+            PostOrderOptions,
+        },
+        seat_operations::{
+            find_authorized_seat,
+            find_mut_seat_with_hint,
+            settle_taker_fill_with_seat,
+        },
+    },
+};
+
+/// Instruction handler logic for placing a user's limit order.
+///
+/// The order first matches against resting liquidity on the opposite side of the book up to its
+/// limit price, exactly like a market order denominated in base. Any unfilled remainder is then
+/// posted on the book at the limit price.
+///
+/// Unlike a market order, a limit order is settled entirely with the user's market seat balances.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::LimitOrder`].
+#[inline(never)]
+pub unsafe fn process_limit_order<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let LimitOrderInstructionData {
+        order_info_args,
+        is_bid,
+        user_sector_index_hint,
+        self_trade_prevention,
+        expiry,
+        client_order_id,
+    } = LimitOrderInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    let order_info = to_order_info(order_info_args).map_err(DropsetError::from)?;
+
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status, the order
+        // against the market's tick size, lot size and minimum order sizes, and find the user's
+        // seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_is_active()?;
+        market.header.check_order_info(&order_info)?;
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), true)?
+    };
+    let limit_price = order_info.encoded_price.as_u32();
+
+    let taker_order = TakerOrder {
+        taker: &user,
+        order_size: order_info.base_atoms,
+        limit_price,
+        time_in_force: TimeInForce::ImmediateOrCancel,
+        self_trade_prevention,
+    };
+
+    let mut events = BufferedEvents {
+        event_buffer,
+        event_authority: ctx.event_authority,
+    };
+
+    // Match against the opposite side of the book. A bid buys base and an ask sells base, and the
+    // order size is always denominated in base.
+    // Safety: The market account data is not currently borrowed.
+    let AmountsFilled {
+        base: base_filled,
+        quote: quote_filled,
+        remaining: base_remaining,
+        taker_fee,
+        ..
+    } = unsafe {
+        fill_market_order(
+            &mut ctx.market_account,
+            &mut events,
+            taker_order,
+            is_bid,
+            true,
+        )
+    }?;
+
+    // Safety: The market account is currently not borrowed in any capacity.
+    let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+
+    // Settle the filled amounts with the user's seat.
+    {
+        Sector::check_in_bounds(market.sectors, user_sector_index)?;
+        // Safety: The user's seat index was just verified as in-bounds.
+        let user_seat = unsafe { find_mut_seat_with_hint(&mut market, user_sector_index, &user) }?;
+        let (amount_in, amount_out) = swap_amounts(is_bid, base_filled, quote_filled, taker_fee)?;
+        settle_taker_fill_with_seat(user_seat, is_bid, amount_in, amount_out)?;
+    }
+
+    // The quote remaining is the remaining base at the limit price, rounded down. Any base removed
+    // by self-trade prevention is neither filled nor posted.
+    let quote_remaining = match base_remaining {
+        0 => 0,
+        _ => {
+            let base_atoms =
+                NonZeroU64::new(order_info.base_atoms).ok_or(DropsetError::AmountCannotBeZero)?;
+            mul_div_checked(base_remaining, order_info.quote_atoms, base_atoms)?
+        }
+    };
+
+    // Post the remainder on the book if there's a non-zero amount of both base and quote left that
+    // still meets the market's minimum order sizes. Anything else is dust that can't be
+    // represented as a valid order.
+    let is_postable = base_remaining != 0
+        && quote_remaining != 0
+        && market.header.check_base_order_size(base_remaining).is_ok()
+        && market
+            .header
+            .check_quote_order_size(quote_remaining)
+            .is_ok();
+    let (order_sector_index, base_posted, quote_posted) = if is_postable {
+        let remainder = OrderInfo {
+            encoded_price: order_info.encoded_price,
+            base_atoms: base_remaining,
+            quote_atoms: quote_remaining,
+        };
+        let order_sector_index = post_user_order(
+            &mut market,
+            &user,
+            remainder,
+            is_bid,
+            user_sector_index,
+            PostOrderOptions {
+                expiry,
+                client_order_id,
+            },
+            current_unix_timestamp()?,
+        )?;
+        (order_sector_index, base_remaining, quote_remaining)
+    } else {
+        (NIL, 0, 0)
+    };
+
+    event_buffer.add_to_buffer(
+        LimitOrderEventInstructionData::new(
+            is_bid,
+            user_sector_index,
+            base_filled,
+            quote_filled,
+            order_sector_index,
+            base_posted,
+            quote_posted,
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -1,360 +1,407 @@
-use core::num::NonZeroU64;
-
 use dropset_interface::{
-    error::{
-        DropsetError,
-        DropsetResult,
+    error::DropsetError,
+    events::{
+        ExpireOrderEventInstructionData,
+        FillEventInstructionData,
+        RemoveOutOfBandOrderEventInstructionData,
+        SelfTradeCancelRestingEventInstructionData,
+        SelfTradeCancelTakingEventInstructionData,
+        SelfTradeDecrementBothEventInstructionData,
+    },
+    matching::{
+        match_taker_order,
+        AmountsFilled,
+        BookOrder,
+        OrderBook,
+        TakerOrder,
     },
     state::{
-        asks_dll::AskOrders,
-        bids_dll::BidOrders,
-        linked_list::LinkedListHeaderOperations,
+        market::MarketRef,
         market_seat::MarketSeat,
         sector::{
             Sector,
             SectorIndex,
-            NIL,
         },
+        user_order_sectors::UserOrders,
     },
 };
-use pinocchio::hint;
-use price::EncodedPrice;
+use pinocchio::{
+    error::ProgramError,
+    ProgramResult,
+};
+use solana_address::Address;
 
 use crate::{
-    context::market_order_context::MarketOrderContext,
-    instructions::market_order::mul_div_checked,
+    events::EventSink,
     shared::order_operations::{
+        cancel_user_order,
+        current_unix_timestamp,
+        expire_order,
         load_mut_order_from_sector_index,
-        load_order_from_sector_index,
     },
+    validation::market_account_view::MarketAccountView,
 };
 
-struct OrderSnapshot {
-    base_remaining: u64,
-    quote_remaining: u64,
-    encoded_price: u32,
-    maker_seat_sector: SectorIndex,
-    order_sector: SectorIndex,
-}
-
-impl OrderSnapshot {
-    #[inline(always)]
-    const fn get_constrained_remaining<const BASE_DENOM: bool>(&self) -> u64 {
-        if BASE_DENOM {
-            self.base_remaining
-        } else {
-            self.quote_remaining
-        }
-    }
-
-    #[inline(always)]
-    const fn get_counter_asset_remaining<const BASE_DENOM: bool>(&self) -> u64 {
-        if BASE_DENOM {
-            self.quote_remaining
-        } else {
-            self.base_remaining
-        }
-    }
-}
-
-pub struct AmountsFilled {
-    pub base: u64,
-    pub quote: u64,
-}
-
-/// `IS_BUY` determines whether or not it's a market buy or a market sell.
-///
-/// `BASE_DENOM` determines which asset the constraint input amount is in.
-/// That is, if the user specifies they want to market buy 1000 quote atoms worth:
+/// Fills a taker order against the market account's order book with
+/// [`dropset_interface::matching::match_taker_order`], which documents how the book is walked and
+/// what `is_buy` and `is_base` mean.
 ///
-/// `IS_BUY == true && BASE_DENOM == false && amount == 1000`
+/// A [`dropset_interface::events::DropsetEventTag::FillEvent`] is emitted to `events` for each
+/// posted order that's matched against. Expired orders are removed from the book, returning their
+/// collateral to the maker's seat and emitting a
+/// [`dropset_interface::events::DropsetEventTag::ExpireOrderEvent`]. Orders outside the near side
+/// of the market's oracle band are removed the same way, emitting a
+/// [`dropset_interface::events::DropsetEventTag::RemoveOutOfBandOrderEvent`]. Each self-trade
+/// prevention outcome other than [`dropset_interface::instructions::SelfTradePrevention::Abort`]
+/// emits its own self-trade event.
 ///
-/// This function returns the amounts filled denominated in both base and quote. The ratio of these
-/// two values is effectively the average fill price.
+/// The market's taker fee is charged on the quote filled against each posted order. The maker's
+/// rebate is credited to their seat and the remainder accrues to the market's protocol fees. The
+/// total taker fee is returned separately from the amounts filled, and it's up to the caller to
+/// settle it with the taker.
 ///
 /// # Safety
 ///
 /// The market account data must not be currently borrowed.
 #[inline(always)]
-pub unsafe fn fill_market_order<const IS_BUY: bool, const BASE_DENOM: bool>(
-    ctx: &'_ mut MarketOrderContext<'_>,
-    order_size: u64,
-) -> Result<AmountsFilled, DropsetError> {
-    // All amounts in this function are in atoms.
-    let mut constraint_asset_remaining = order_size;
-    let mut counter_asset_filled: u64 = 0;
-
-    // Iterate over each order on the book, filling each posted order in whole as long as the
-    // market order has any remaining size.
-    // That is, as long as the amount not filled yet exceeds the amount in the next posted order,
-    // simply close the order and decrement the remaining amount by the amount used to fill the
-    // order. This skips muldiv operations until the very last partial fill.
-    while let Some(top_order) = top_of_book_snapshot::<IS_BUY>(ctx) {
-        // If there's nothing left to fill, break from the loop. The last order filled cleanly with
-        // no remainder so there's no partial order to fill.
-        if hint::unlikely(constraint_asset_remaining == 0) {
-            break;
-        } else {
-            // Safety:
-            // 1. Market account data isn't currently borrowed per this function's safety contract.
-            // 2. The head/top of book order sector index is valid.
-            // 3. The market maker's seat order sector index is valid.
-            unsafe {
-                // Otherwise, check if this should be a partial fill or a full fill.
-                // If the constrained asset amount remaining in the top order is <= to the amount
-                // that should be filled for the taker, it's a full fill. That is, the maker order
-                // can be completely filled and thus removed from the books.
-                if top_order.get_constrained_remaining::<BASE_DENOM>() <= constraint_asset_remaining
-                {
-                    // Safety: The order's constrained amount remaining is <= the constraint asset
-                    // remaining.
-                    full_fill::<IS_BUY, BASE_DENOM>(
-                        ctx,
-                        &mut constraint_asset_remaining,
-                        &mut counter_asset_filled,
-                        &top_order,
-                    )?;
+pub unsafe fn fill_market_order<'a>(
+    market_account: &'_ mut MarketAccountView<'a>,
+    events: &mut impl EventSink<'a>,
+    taker_order: TakerOrder<'_>,
+    is_buy: bool,
+    is_base: bool,
+) -> Result<AmountsFilled, ProgramError> {
+    let unix_timestamp = current_unix_timestamp()?;
+    // Safety: The market account data isn't currently borrowed per this function's safety contract.
+    let mut book = unsafe { MarketBook::new(market_account, events) };
+    match_taker_order(&mut book, taker_order, is_buy, is_base, unix_timestamp)
+}
 
-                    // Safety: The market account data isn't currently borrowed and the top order's
-                    // maker seat sector index still points to a valid seat in memory.
-                    #[cfg(debug_assertions)]
-                    ensure_order_has_been_removed::<IS_BUY>(ctx, &top_order);
-                } else {
-                    // Otherwise, it's a partial fill. That is, the maker order *cannot* be
-                    // completely filled and must be mutated to reflect the new amounts remaining.
-                    partial_fill::<IS_BUY, BASE_DENOM>(
-                        ctx,
-                        &mut constraint_asset_remaining,
-                        &mut counter_asset_filled,
-                        &top_order,
-                    )?;
+/// The [`OrderBook`] in a market account, which applies each match to the market account data and
+/// emits an event for it.
+struct MarketBook<'m, 'a, E: EventSink<'a>> {
+    market_account: &'m mut MarketAccountView<'a>,
+    events: &'m mut E,
+}
 
-                    // The taker order amount should be completely filled now.
-                    debug_assert_eq!(constraint_asset_remaining, 0);
-                    break;
-                }
-            }
+impl<'m, 'a, E: EventSink<'a>> MarketBook<'m, 'a, E> {
+    /// # Safety
+    ///
+    /// The market account data must not be borrowed for as long as the book is in use.
+    #[inline(always)]
+    unsafe fn new(market_account: &'m mut MarketAccountView<'a>, events: &'m mut E) -> Self {
+        MarketBook {
+            market_account,
+            events,
         }
     }
 
-    // Safety: The constraint asset remaining never increments, so it's always <= the order size.
-    let constrained_asset_filled = order_size.unchecked_sub(constraint_asset_remaining);
-
-    if BASE_DENOM {
-        Ok(AmountsFilled {
-            base: constrained_asset_filled,
-            quote: counter_asset_filled,
-        })
-    } else {
-        Ok(AmountsFilled {
-            base: counter_asset_filled,
-            quote: constrained_asset_filled,
-        })
+    /// Removes an order from the book on behalf of its maker, returning its collateral to the
+    /// maker's seat.
+    #[inline(always)]
+    fn remove_for_maker<const IS_BUY: bool>(&mut self, order: &BookOrder) -> ProgramResult {
+        // Safety: The market account data isn't borrowed per `MarketBook::new`'s safety contract,
+        // and the order sector index is valid and on the opposite side of the book, since a market
+        // buy matches against asks and a market sell against bids.
+        unsafe {
+            expire_order(
+                &mut self.market_account.load_unchecked_mut(),
+                order.order_sector,
+                !IS_BUY,
+            )
+        }?;
+        Ok(())
     }
 }
 
-#[inline(always)]
-fn top_of_book_snapshot<const IS_BUY: bool>(ctx: &'_ MarketOrderContext) -> Option<OrderSnapshot> {
-    // Safety: Scoped borrow of the market account data to check the top of book.
-    let market = unsafe { ctx.market_account.load_unchecked() };
+// Safety: Every method below borrows the market account data only within its own scope, which is
+// sound since the market account data isn't otherwise borrowed per `MarketBook::new`'s safety
+// contract. The order passed to each method was just read from the book by `match_taker_order`, so
+// its order sector and maker seat sector indices still point to valid, properly typed sectors.
+impl<'a, E: EventSink<'a>> OrderBook for MarketBook<'_, 'a, E> {
+    type Error = ProgramError;
 
-    let head_index = if IS_BUY {
-        AskOrders::head(market.header)
-    } else {
-        BidOrders::head(market.header)
-    };
-
-    if head_index == NIL {
-        None
-    } else {
-        // Safety: The head index is a non-NIL sector index pointing to a valid order sector.
-        let order = unsafe { load_order_from_sector_index(&market, head_index) };
-        Some(OrderSnapshot {
-            base_remaining: order.base_remaining(),
-            quote_remaining: order.quote_remaining(),
-            encoded_price: order.encoded_price(),
-            maker_seat_sector: order.user_seat(),
-            order_sector: head_index,
-        })
+    #[inline(always)]
+    fn market(&self) -> MarketRef<'_> {
+        // Safety: Scoped borrow of the market account data.
+        unsafe { self.market_account.load_unchecked() }
     }
-}
 
-/// Fully fill the order, by doing the following:
-/// 1. Remove the order from the orders collection.
-/// 2. Update the filled maker seat's balance and remove the order from the maker seat's price to
-///    order map.
-/// 3. Update the constraint asset remaining and the counter asset filled.
-///
-/// # Safety
-///
-/// The market account data must not be currently borrowed and the top order sector index and the
-/// user seat sector index must both still point to valid, properly typed sectors in memory.
-///
-/// The constraint asset remaining must be <= the top order's constraint asset remaining.
-#[inline(always)]
-unsafe fn full_fill<const IS_BUY: bool, const BASE_DENOM: bool>(
-    ctx: &'_ mut MarketOrderContext<'_>,
-    constraint_asset_remaining: &mut u64,
-    counter_asset_filled: &mut u64,
-    top_order: &OrderSnapshot,
-) -> DropsetResult {
-    // 1. Close/remove the order from the orders collection.
-    if IS_BUY {
-        ctx.market_account
-            .load_unchecked_mut()
-            .asks()
-            .remove_at(top_order.order_sector);
-    } else {
-        ctx.market_account
-            .load_unchecked_mut()
-            .bids()
-            .remove_at(top_order.order_sector);
+    #[inline(always)]
+    fn remove_expired<const IS_BUY: bool>(
+        &mut self,
+        order: &BookOrder,
+    ) -> Result<(), ProgramError> {
+        self.remove_for_maker::<IS_BUY>(order)?;
+        self.events.emit(
+            ExpireOrderEventInstructionData::new(
+                !IS_BUY,
+                order.maker_seat_sector,
+                order.order_sector,
+                order.encoded_price,
+                order.base_remaining,
+                order.quote_remaining,
+            ),
+            self.market_account.clone(),
+        )
     }
 
-    // 2. Update the filled maker seat's balance and remove the order from their price to order
-    // sector map.
-    // Safety: The safety contract is essentially a subset of the calling function.
-    unsafe {
-        update_maker_seat_after_fill::<IS_BUY, false>(
-            ctx,
-            top_order.maker_seat_sector,
-            // The base/quote amount filled is simply the (now previously) top order's amounts
-            // remaining, since this was a full fill.
-            top_order.base_remaining,
-            top_order.quote_remaining,
-            top_order.encoded_price,
+    #[inline(always)]
+    fn remove_out_of_band<const IS_BUY: bool>(
+        &mut self,
+        order: &BookOrder,
+    ) -> Result<(), ProgramError> {
+        self.remove_for_maker::<IS_BUY>(order)?;
+        self.events.emit(
+            RemoveOutOfBandOrderEventInstructionData::new(
+                !IS_BUY,
+                order.maker_seat_sector,
+                order.order_sector,
+                order.encoded_price,
+                order.base_remaining,
+                order.quote_remaining,
+            ),
+            self.market_account.clone(),
         )
-    }?;
-
-    // 3. Update the constrained amount not filled yet and the counter asset total filled.
-    // Safety: The amount of constraint asset remaining must be >= the denominated constrained
-    // amount in the top order or this would not be a full fill.
-    *constraint_asset_remaining = constraint_asset_remaining
-        .unchecked_sub(top_order.get_constrained_remaining::<BASE_DENOM>());
-
-    *counter_asset_filled = counter_asset_filled
-        .checked_add(top_order.get_counter_asset_remaining::<BASE_DENOM>())
-        .ok_or(DropsetError::ArithmeticOverflow)?;
-
-    Ok(())
-}
+    }
 
-#[inline(always)]
-fn partial_fill<const IS_BUY: bool, const BASE_DENOM: bool>(
-    ctx: &'_ mut MarketOrderContext<'_>,
-    constraint_asset_remaining: &mut u64,
-    counter_asset_filled: &mut u64,
-    top_order: &OrderSnapshot,
-) -> DropsetResult {
-    let remaining_constrained_asset_in_top_order =
-        dropset_non_zero_u64(top_order.get_constrained_remaining::<BASE_DENOM>())?;
-    let remaining_counter_asset_in_top_order =
-        top_order.get_counter_asset_remaining::<BASE_DENOM>();
+    /// Fills the order by doing the following:
+    /// 1. Remove the order from the maker's orders and the orders collection if it's closed, and
+    ///    otherwise update it to reflect the new remaining amounts.
+    /// 2. Update the filled maker seat's balance and charge the fees.
+    /// 3. Emit a fill event for the order.
+    #[inline(always)]
+    fn fill<const IS_BUY: bool>(
+        &mut self,
+        order: &BookOrder,
+        base_filled: u64,
+        quote_filled: u64,
+        is_order_closed: bool,
+    ) -> Result<u64, ProgramError> {
+        {
+            // Safety: Scoped mutable borrow of the market account data.
+            let mut market = unsafe { self.market_account.load_unchecked_mut() };
+            if is_order_closed {
+                // Freeing the order's sector zeroes out its links in the maker's orders, so they
+                // must be unlinked first. A market buy fills a maker's ask and a market sell fills
+                // a maker's bid.
+                // Safety: The user seat sector index is in-bounds and points to a seat, as it came
+                // from the order.
+                unsafe { UserOrders::new(market.sectors, order.maker_seat_sector, !IS_BUY) }
+                    .remove(order.order_sector)?;
+                if IS_BUY {
+                    market.asks().remove_at(order.order_sector);
+                } else {
+                    market.bids().remove_at(order.order_sector);
+                }
+            } else {
+                // Safety: The order sector index is non-NIL and pointing to a valid order sector.
+                let posted =
+                    unsafe { load_mut_order_from_sector_index(&mut market, order.order_sector) };
+                // Safety: The amounts filled for both sides are always <= the amounts in the order,
+                // otherwise it would've been closed.
+                unsafe {
+                    posted.set_base_remaining(order.base_remaining.unchecked_sub(base_filled));
+                    posted.set_quote_remaining(order.quote_remaining.unchecked_sub(quote_filled));
+                }
+            }
+        }
 
-    let partial_counter_asset_fill_amount = mul_div_checked(
-        *constraint_asset_remaining,
-        remaining_counter_asset_in_top_order,
-        remaining_constrained_asset_in_top_order,
-    )?;
+        // Safety: The market account data isn't borrowed and the maker seat sector index is valid.
+        let (taker_fee, maker_rebate) = unsafe {
+            update_maker_seat_after_fill::<IS_BUY>(
+                self.market_account,
+                order.maker_seat_sector,
+                base_filled,
+                quote_filled,
+            )
+        }?;
+
+        #[cfg(debug_assertions)]
+        if is_order_closed {
+            // Safety: The market account data isn't borrowed and the maker seat sector index is
+            // valid.
+            unsafe { ensure_order_has_been_removed::<IS_BUY>(self.market_account, order) };
+        }
 
-    // Add the partial fill amount to the total counter asset filled.
-    *counter_asset_filled = counter_asset_filled
-        .checked_add(partial_counter_asset_fill_amount)
-        .ok_or(DropsetError::ArithmeticOverflow)?;
+        self.events.emit(
+            FillEventInstructionData::new(
+                order.maker_seat_sector,
+                order.order_sector,
+                order.encoded_price,
+                base_filled,
+                quote_filled,
+                is_order_closed,
+                taker_fee,
+                maker_rebate,
+                order.client_order_id,
+            ),
+            self.market_account.clone(),
+        )?;
+
+        Ok(taker_fee)
+    }
 
-    let (base_filled, quote_filled) = {
-        // Now update the order to reflect the new remaining amounts after the partial fill.
+    /// Cancels the taker's own resting order, returning its collateral to the taker's seat, and
+    /// emits a self-trade cancel resting event.
+    #[inline(always)]
+    fn cancel_resting<const IS_BUY: bool>(
+        &mut self,
+        taker: &Address,
+        order: &BookOrder,
+    ) -> Result<(), ProgramError> {
+        // A market buy matches against asks and a market sell matches against bids.
         // Safety: Scoped mutable borrow of the market account data.
-        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
-
-        // Safety: The order sector index is non-NIL and pointing to a valid order sector.
-        let order =
-            unsafe { load_mut_order_from_sector_index(&mut market, top_order.order_sector) };
-
-        #[rustfmt::skip]
-        let (base_filled, quote_filled) = if BASE_DENOM {
-            (*constraint_asset_remaining, partial_counter_asset_fill_amount)
-        } else {
-            (partial_counter_asset_fill_amount, *constraint_asset_remaining)
-        };
+        cancel_user_order(
+            &mut unsafe { self.market_account.load_unchecked_mut() },
+            taker,
+            order.order_sector,
+            !IS_BUY,
+            order.maker_seat_sector,
+        )?;
+
+        self.events.emit(
+            SelfTradeCancelRestingEventInstructionData::new(
+                order.maker_seat_sector,
+                order.order_sector,
+                order.encoded_price,
+                order.base_remaining,
+                order.quote_remaining,
+            ),
+            self.market_account.clone(),
+        )
+    }
 
-        // Safety: The amount not filled yet for both sides is always <= the amount in the top
-        // order, otherwise this would not be a partial fill.
-        unsafe {
-            let new_base = top_order.base_remaining.unchecked_sub(base_filled);
-            let new_quote = top_order.quote_remaining.unchecked_sub(quote_filled);
-            order.set_base_remaining(new_base);
-            order.set_quote_remaining(new_quote);
-        };
+    #[inline(always)]
+    fn cancel_taking(
+        &mut self,
+        order: &BookOrder,
+        constraint_asset_remaining: u64,
+    ) -> Result<(), ProgramError> {
+        self.events.emit(
+            SelfTradeCancelTakingEventInstructionData::new(
+                order.order_sector,
+                order.encoded_price,
+                constraint_asset_remaining,
+            ),
+            self.market_account.clone(),
+        )
+    }
 
-        (base_filled, quote_filled)
-    };
+    /// Decrements the taker's own resting order without trading, and emits a self-trade decrement
+    /// both event.
+    ///
+    /// If the resting order is decremented entirely, it's cancelled. Otherwise, the resting order's
+    /// released collateral is returned to the taker's seat.
+    #[inline(always)]
+    fn decrement_both<const IS_BUY: bool>(
+        &mut self,
+        taker: &Address,
+        order: &BookOrder,
+        base_decremented: u64,
+        quote_decremented: u64,
+        is_order_closed: bool,
+    ) -> Result<(), ProgramError> {
+        {
+            // Safety: Scoped mutable borrow of the market account data.
+            let mut market = unsafe { self.market_account.load_unchecked_mut() };
+            if is_order_closed {
+                // A market buy matches against asks and a market sell matches against bids.
+                cancel_user_order(
+                    &mut market,
+                    taker,
+                    order.order_sector,
+                    !IS_BUY,
+                    order.maker_seat_sector,
+                )?;
+            } else {
+                // Safety: The order sector index is non-NIL and pointing to a valid order sector.
+                let posted =
+                    unsafe { load_mut_order_from_sector_index(&mut market, order.order_sector) };
+                // Safety: The amounts decremented for both sides are always <= the amounts in the
+                // order, otherwise it would've been closed.
+                unsafe {
+                    posted.set_base_remaining(order.base_remaining.unchecked_sub(base_decremented));
+                    posted.set_quote_remaining(
+                        order.quote_remaining.unchecked_sub(quote_decremented),
+                    );
+                }
 
-    // Set the remaining amount not yet filled to zero.
-    *constraint_asset_remaining = 0;
+                // Return the decremented collateral to the taker's seat. A resting ask holds base
+                // and a resting bid holds quote.
+                // Safety: The user seat sector index is in-bounds, as it came from the order.
+                let sector = unsafe {
+                    Sector::from_sector_index_mut(market.sectors, order.maker_seat_sector)
+                };
+                let seat = sector.load_payload_mut::<MarketSeat>();
+                if IS_BUY {
+                    seat.try_increment_base_available(base_decremented)?;
+                } else {
+                    seat.try_increment_quote_available(quote_decremented)?;
+                }
+            }
+        }
 
-    // Update the maker's seat to reflect the partial fill.
-    // Safety: The market account data is not currently borrowed and the maker's user seat inside
-    // the top order still points to a valid user.
-    unsafe {
-        update_maker_seat_after_fill::<IS_BUY, true>(
-            ctx,
-            top_order.maker_seat_sector,
-            base_filled,
-            quote_filled,
-            top_order.encoded_price,
+        self.events.emit(
+            SelfTradeDecrementBothEventInstructionData::new(
+                order.maker_seat_sector,
+                order.order_sector,
+                order.encoded_price,
+                base_decremented,
+                quote_decremented,
+                is_order_closed,
+            ),
+            self.market_account.clone(),
         )
-    }?;
-
-    Ok(())
-}
-
-#[inline(always)]
-fn dropset_non_zero_u64(value: u64) -> Result<NonZeroU64, DropsetError> {
-    if value == 0 {
-        Err(DropsetError::AmountCannotBeZero)
-    } else {
-        // Safety: The value was just verified as non-zero.
-        Ok(unsafe { NonZeroU64::new_unchecked(value) })
     }
 }
 
+/// Credits the maker's seat for the fill and charges the market's fees on the quote filled. The
+/// maker rebate is credited to the maker's seat and the rest of the taker fee accrues to the
+/// market's protocol fees.
+///
+/// Returns the taker fee and the maker rebate, in that order.
+///
 /// # Safety
 ///
 /// The market account data must not be currently borrowed and the passed order's maker seat sector
 /// index must still point to a valid seat in memory.
 #[inline(always)]
-unsafe fn update_maker_seat_after_fill<const IS_BUY: bool, const PARTIAL_FILL: bool>(
-    ctx: &'_ mut MarketOrderContext<'_>,
+unsafe fn update_maker_seat_after_fill<const IS_BUY: bool>(
+    market_account: &'_ mut MarketAccountView<'_>,
     maker_seat_sector: SectorIndex,
     base_filled: u64,
     quote_filled: u64,
-    encoded_price: u32,
-) -> DropsetResult {
+) -> Result<(u64, u64), DropsetError> {
     // Safety: Single, scoped mutable borrow of the market account data.
-    let market = ctx.market_account.load_unchecked_mut();
+    let market = market_account.load_unchecked_mut();
+
+    let taker_fee = market.header.taker_fee(quote_filled);
+    let maker_rebate = market.header.maker_rebate(quote_filled);
+    // Safety: The maker rebate never exceeds the taker fee.
+    let protocol_fee = taker_fee.unchecked_sub(maker_rebate);
+    market.header.set_protocol_fees(
+        market
+            .header
+            .protocol_fees()
+            .checked_add(protocol_fee)
+            .ok_or(DropsetError::ArithmeticOverflow)?,
+    );
+
     // Safety: The user seat sector index is in-bounds, as it came from the order.
     let sector = unsafe { Sector::from_sector_index_mut(market.sectors, maker_seat_sector) };
     let maker_seat = sector.load_payload_mut::<MarketSeat>();
+    // The maker rebate is always paid in quote, regardless of the side filled.
+    maker_seat.try_increment_quote_available(maker_rebate)?;
     if IS_BUY {
         // Market buy means a maker's ask got filled, so they receive quote.
         maker_seat.try_increment_quote_available(quote_filled)?;
-
-        // If it's a complete/full fill, remove the order sector index from the price to index map.
-        if !PARTIAL_FILL {
-            maker_seat.user_order_sectors.asks.remove(encoded_price)?;
-        }
     } else {
         // Market sell means a maker's bid got filled, so they receive base.
         maker_seat.try_increment_base_available(base_filled)?;
-
-        // If it's a complete/full fill, remove the order sector index from the price to index map.
-        if !PARTIAL_FILL {
-            maker_seat.user_order_sectors.bids.remove(encoded_price)?;
-        }
     }
 
-    Ok(())
+    Ok((taker_fee, maker_rebate))
 }
 
 /// # Safety
@@ -363,27 +410,19 @@ unsafe fn update_maker_seat_after_fill<const IS_BUY: bool, const PARTIAL_FILL: b
 /// must still point to a valid seat in memory.
 #[cfg(debug_assertions)]
 unsafe fn ensure_order_has_been_removed<const IS_BUY: bool>(
-    ctx: &'_ MarketOrderContext,
-    top_order: &OrderSnapshot,
+    market_account: &'_ MarketAccountView,
+    top_order: &BookOrder,
 ) {
-    use price::LeEncodedPrice;
-
     // Safety: Single, scoped mutable borrow of the market account data.
-    let market = ctx.market_account.load_unchecked();
+    let market = market_account.load_unchecked();
     // Safety: The user seat sector index is in-bounds, as it came from the order.
     let sector = unsafe { Sector::from_sector_index(market.sectors, top_order.maker_seat_sector) };
     let maker_seat = sector.load_payload::<MarketSeat>();
-    let encoded_price: EncodedPrice = top_order
-        .encoded_price
-        .try_into()
-        .expect("Should be a valid encoded price");
-    let le_encoded_price: &LeEncodedPrice = &encoded_price.into();
-
     let orders = if IS_BUY {
         &maker_seat.user_order_sectors.asks
     } else {
         &maker_seat.user_order_sectors.bids
     };
 
-    debug_assert!({ orders.get(le_encoded_price).is_none() });
+    debug_assert!(!orders.contains(market.sectors, top_order.order_sector));
 }
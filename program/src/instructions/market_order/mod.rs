@@ -1,15 +1,17 @@
 //! See [`process_market_order`].
 
-mod fill_market_order;
-mod mul_div_checked;
+pub(crate) mod fill_market_order;
 
 // #[cfg(feature = "debug")]
 use dropset_interface::{
-    error::DropsetError,
     events::MarketOrderEventInstructionData,
     instructions::MarketOrderInstructionData,
+    matching::{
+        checked_swap_amounts,
+        AmountsFilled,
+        TakerOrder,
+    },
 };
-use mul_div_checked::mul_div_checked;
 use pinocchio::{
     account::AccountView,
     error::ProgramError,
@@ -20,15 +22,12 @@ use crate::{
         market_order_context::MarketOrderContext,
         EventBufferContext,
     },
-    events::EventBuffer,
-    instructions::market_order::fill_market_order::{
-        fill_market_order,
-        AmountsFilled,
-    },
-    shared::token_utils::market_transfers::{
-        deposit_non_zero_to_market,
-        withdraw_non_zero_from_market,
+    events::{
+        BufferedEvents,
+        EventBuffer,
     },
+    instructions::market_order::fill_market_order::fill_market_order,
+    shared::token_utils::market_transfers::settle_taker_fill_with_token_accounts,
 };
 
 /// Instruction handler logic for processing a market order.
@@ -41,86 +40,80 @@ use crate::{
 pub unsafe fn process_market_order<'a>(
     accounts: &'a [AccountView],
     instruction_data: &[u8],
-    _event_buffer: &mut EventBuffer,
+    event_buffer: &mut EventBuffer,
 ) -> Result<EventBufferContext<'a>, ProgramError> {
     let MarketOrderInstructionData {
         order_size,
         is_buy,
         is_base,
+        limit_price,
+        min_output,
+        time_in_force,
+        self_trade_prevention,
     } = MarketOrderInstructionData::unpack_untagged(instruction_data)?;
 
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { MarketOrderContext::load(accounts) }?;
 
-    let AmountsFilled {
-        base: base_filled,
-        quote: quote_filled,
-    } = match (is_buy, is_base) {
-        (false, false) => fill_market_order::<false, false>(&mut ctx, order_size),
-        (true, false) => fill_market_order::<true, false>(&mut ctx, order_size),
-        (false, true) => fill_market_order::<false, true>(&mut ctx, order_size),
-        (true, true) => fill_market_order::<true, true>(&mut ctx, order_size),
-    }?;
-
-    // Try to transfer the taker side's tokens to the market account.
-    // Safety: No account data is currently borrowed.
-    let (taker_amount_filled, taker_amount_deposited) = unsafe {
-        // A buy means taker transfers quote to the market.
-        if is_buy {
-            let quote_transferred = deposit_non_zero_to_market(
-                &ctx.quote_user_ata,
-                &ctx.quote_market_ata,
-                ctx.user,
-                &ctx.quote_mint,
-                quote_filled,
-            )?;
+    // Safety: Scoped borrow of the market account data to check the market status, the user's
+    // allowlisting and the order size against the market's lot size and minimum order sizes.
+    unsafe { ctx.market_account.load_unchecked() }.check_taker_order(
+        ctx.user.address(),
+        is_base,
+        order_size,
+    )?;
 
-            // And receives base.
-            withdraw_non_zero_from_market(
-                &ctx.base_user_ata,
-                &ctx.base_market_ata,
-                &ctx.market_account,
-                &ctx.base_mint,
-                base_filled,
-            )?;
+    let taker_order = TakerOrder {
+        taker: ctx.user.address(),
+        order_size,
+        limit_price,
+        time_in_force,
+        self_trade_prevention,
+    };
 
-            (quote_filled, quote_transferred)
-        // A sell means taker transfers base to the market.
-        } else {
-            let base_transferred = deposit_non_zero_to_market(
-                &ctx.base_user_ata,
-                &ctx.base_market_ata,
-                ctx.user,
-                &ctx.base_mint,
-                base_filled,
-            )?;
+    let mut events = BufferedEvents {
+        event_buffer,
+        event_authority: ctx.event_authority,
+    };
 
-            // And receives quote.
-            withdraw_non_zero_from_market(
-                &ctx.quote_user_ata,
-                &ctx.quote_market_ata,
-                &ctx.market_account,
-                &ctx.quote_mint,
-                quote_filled,
-            )?;
+    let AmountsFilled {
+        base: base_filled,
+        quote: quote_filled,
+        time_in_force,
+        is_fully_filled,
+        taker_fee,
+        ..
+    } = fill_market_order(
+        &mut ctx.market_account,
+        &mut events,
+        taker_order,
+        is_buy,
+        is_base,
+    )?;
 
-            (base_filled, base_transferred)
-        }
-    };
+    // Ensure the taker receives at least the minimum output amount after the taker fee.
+    let (amount_in, amount_out) = checked_swap_amounts(
+        is_buy,
+        base_filled,
+        quote_filled,
+        taker_fee,
+        min_output,
+        u64::MAX,
+    )?;
 
-    // Ensure that the order size matches the exact amount transferred.
-    if taker_amount_filled != taker_amount_deposited {
-        return Err(DropsetError::AmountFilledVsTransferredMismatch.into());
-    }
+    // Safety: No account data is currently borrowed.
+    unsafe { settle_taker_fill_with_token_accounts(&ctx, is_buy, amount_in, amount_out) }?;
 
     // #[cfg(feature = "debug")]
-    _event_buffer.add_to_buffer(
+    event_buffer.add_to_buffer(
         MarketOrderEventInstructionData::new(
             order_size,
             is_buy,
             is_base,
             base_filled,
             quote_filled,
+            time_in_force,
+            is_fully_filled,
         ),
         ctx.event_authority,
         ctx.market_account.clone(),
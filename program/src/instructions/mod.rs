@@ -3,22 +3,62 @@
 //! Routes decoded instructions to their corresponding handlers and encapsulates all
 //! on-chain logic for each supported operation.
 
+pub mod add_allowlisted_trader;
+pub mod amend_order;
 pub mod batch_replace;
+pub mod cancel_all_orders;
 pub mod cancel_order;
+pub mod close_market;
 pub mod close_seat;
+pub mod compact_market;
 pub mod deposit;
+pub mod expand_market;
 pub mod flush_events;
+pub mod limit_order;
 pub mod market_order;
 pub mod post_order;
+pub mod prune_expired_orders;
 pub mod register_market;
+pub mod remove_allowlisted_trader;
+pub mod renounce_market_authority;
+pub mod revoke_seat_delegate;
+pub mod seat_market_order;
+pub mod set_market_oracle;
+pub mod set_seat_delegate;
+pub mod swap;
+pub mod sweep_fees;
+pub mod transfer_market_authority;
+pub mod update_market_params;
+pub mod update_market_status;
+pub mod update_oracle_price;
 pub mod withdraw;
 
+pub use add_allowlisted_trader::process_add_allowlisted_trader;
+pub use amend_order::process_amend_order;
 pub use batch_replace::process_batch_replace;
+pub use cancel_all_orders::process_cancel_all_orders;
 pub use cancel_order::process_cancel_order;
+pub use close_market::process_close_market;
 pub use close_seat::process_close_seat;
+pub use compact_market::process_compact_market;
 pub use deposit::process_deposit;
+pub use expand_market::process_expand_market;
 pub use flush_events::process_flush_events;
+pub use limit_order::process_limit_order;
 pub use market_order::process_market_order;
 pub use post_order::process_post_order;
+pub use prune_expired_orders::process_prune_expired_orders;
 pub use register_market::process_register_market;
+pub use remove_allowlisted_trader::process_remove_allowlisted_trader;
+pub use renounce_market_authority::process_renounce_market_authority;
+pub use revoke_seat_delegate::process_revoke_seat_delegate;
+pub use seat_market_order::process_seat_market_order;
+pub use set_market_oracle::process_set_market_oracle;
+pub use set_seat_delegate::process_set_seat_delegate;
+pub use swap::process_swap;
+pub use sweep_fees::process_sweep_fees;
+pub use transfer_market_authority::process_transfer_market_authority;
+pub use update_market_params::process_update_market_params;
+pub use update_market_status::process_update_market_status;
+pub use update_oracle_price::process_update_oracle_price;
 pub use withdraw::process_withdraw;
@@ -5,15 +5,6 @@ use dropset_interface::events::PostOrderEventInstructionData;
 use dropset_interface::{
     error::DropsetError,
     instructions::PostOrderInstructionData,
-    state::{
-        asks_dll::AskOrders,
-        bids_dll::BidOrders,
-        order::{
-            Order,
-            OrdersCollection,
-        },
-        sector::Sector,
-    },
 };
 use pinocchio::{
     account::AccountView,
@@ -28,8 +19,12 @@ use crate::{
     },
     events::EventBuffer,
     shared::{
-        order_operations::insert_order,
-        seat_operations::find_mut_seat_with_hint,
+        order_operations::{
+            current_unix_timestamp,
+            post_user_order,
+            PostOrderOptions,
+        },
+        seat_operations::find_authorized_seat,
     },
 };
 
@@ -49,71 +44,54 @@ pub unsafe fn process_post_order<'a>(
         order_info_args,
         is_bid,
         user_sector_index_hint,
+        expiry,
+        client_order_id,
     } = PostOrderInstructionData::unpack_untagged(instruction_data)?;
 
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
 
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status and find the
+        // user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_is_active()?;
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), false)?
+    };
+
     let order_info = to_order_info(order_info_args).map_err(DropsetError::from)?;
+    let unix_timestamp = current_unix_timestamp()?;
 
+    #[cfg(feature = "debug")]
     let (base_atoms, quote_atoms) = (order_info.base_atoms, order_info.quote_atoms);
 
-    // To avoid convoluted borrow checking rules, optimistically insert the order with the index
-    // hint passed in, assuming it's valid. It's verified later when mutating the market seat.
-    let order = Order::new(order_info, user_sector_index_hint);
-    let le_encoded_price = *order.le_encoded_price();
-
     // Safety: The market account is currently not borrowed in any capacity.
     let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
 
-    let order_sector_index = {
-        if is_bid {
-            BidOrders::post_only_crossing_check(&order, &market)?;
-            insert_order(&mut market.bids(), order)
-        } else {
-            AskOrders::post_only_crossing_check(&order, &market)?;
-            insert_order(&mut market.asks(), order)
-        }
-    }?;
+    market.header.check_order_info(&order_info)?;
 
-    Sector::check_in_bounds(market.sectors, user_sector_index_hint)?;
-    // Find and verify the user's seat with the given index hint.
-    // Safety: The index hint was just verified as in-bounds.
-    let user_seat =
-        find_mut_seat_with_hint(&mut market, user_sector_index_hint, ctx.user.address())?;
-
-    let order_sector_index_bytes = order_sector_index.to_le_bytes();
-
-    // 1. Check that the user has enough collateral to place the order and update their seat with
-    //    the resulting decremented amount.
-    // 2. Update the user seat's mapped order sectors. This also checks for duplicate prices so that
-    //    all of a user's orders have a unique price.
-    if is_bid {
-        // 1. If the user is posting a bid, they intend to provide quote and receive base.
-        user_seat.try_decrement_quote_available(quote_atoms)?;
-        // 2. Add the order to the user's bids.
-        user_seat
-            .user_order_sectors
-            .bids
-            .add(&le_encoded_price, &order_sector_index_bytes)?;
-    } else {
-        // 1. If the user is posting an ask, they intend to provide base and receive quote.
-        user_seat.try_decrement_base_available(base_atoms)?;
-        // 2. Add the order to the user's asks.
-        user_seat
-            .user_order_sectors
-            .asks
-            .add(&le_encoded_price, &order_sector_index_bytes)?;
-    }
+    let _order_sector_index = post_user_order(
+        &mut market,
+        &user,
+        order_info,
+        is_bid,
+        user_sector_index,
+        PostOrderOptions {
+            expiry,
+            client_order_id,
+        },
+        unix_timestamp,
+    )?;
 
     #[cfg(feature = "debug")]
     _event_buffer.add_to_buffer(
         PostOrderEventInstructionData::new(
             is_bid,
-            user_sector_index_hint,
-            order_sector_index,
+            user_sector_index,
+            _order_sector_index,
             base_atoms,
             quote_atoms,
+            client_order_id,
         ),
         ctx.event_authority,
         ctx.market_account.clone(),
@@ -0,0 +1,116 @@
+//! See [`process_prune_expired_orders`].
+
+use dropset_interface::{
+    events::ExpireOrderEventInstructionData,
+    instructions::PruneExpiredOrdersInstructionData,
+    state::{
+        order::Order,
+        sector::{
+            Sector,
+            NIL,
+        },
+    },
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        prune_expired_orders_context::PruneExpiredOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::order_operations::{
+        current_unix_timestamp,
+        expire_order,
+    },
+};
+
+/// Instruction handler logic for the permissionless crank that removes expired orders from the
+/// market's order book.
+///
+/// Bids are pruned before asks, and at most `max_orders` orders are removed in total. Each expired
+/// order's remaining collateral is returned to its maker's seat and a
+/// [`dropset_interface::events::DropsetEventTag::ExpireOrderEvent`] is emitted per removed order.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::PruneExpiredOrders`].
+#[inline(never)]
+pub unsafe fn process_prune_expired_orders<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let PruneExpiredOrdersInstructionData { max_orders } =
+        PruneExpiredOrdersInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { PruneExpiredOrdersContext::load(accounts) }?;
+
+    // Safety: Scoped borrow of the market account data to check the market status.
+    unsafe { ctx.market_account.load_unchecked() }
+        .header
+        .check_allows_exits()?;
+
+    let unix_timestamp = current_unix_timestamp()?;
+    let mut num_pruned: u16 = 0;
+
+    for is_bid in [true, false] {
+        let mut curr = {
+            // Safety: Scoped borrow of the market account data to get the head of the orders list.
+            let market = unsafe { ctx.market_account.load_unchecked() };
+            if is_bid {
+                market.header.bids_dll_head()
+            } else {
+                market.header.asks_dll_head()
+            }
+        };
+
+        while curr != NIL && num_pruned < max_orders {
+            // Copy the next index before the current order is possibly removed.
+            let (next, is_expired) = {
+                // Safety: Scoped borrow of the market account data to check the current order.
+                let market = unsafe { ctx.market_account.load_unchecked() };
+                // Safety: `curr` is a non-NIL sector index from the orders list, so it's in-bounds.
+                let sector = unsafe { Sector::from_sector_index(market.sectors, curr) };
+                let order = sector.load_payload::<Order>();
+                (sector.next(), order.is_expired(unix_timestamp))
+            };
+
+            if is_expired {
+                let order = {
+                    // Safety: Scoped mutable borrow of the market account data to remove the
+                    // order. `curr` points to a valid order on the side of the book being pruned.
+                    let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+                    unsafe { expire_order(&mut market, curr, is_bid) }?
+                };
+
+                event_buffer.add_to_buffer(
+                    ExpireOrderEventInstructionData::new(
+                        is_bid,
+                        order.user_seat(),
+                        curr,
+                        order.encoded_price(),
+                        order.base_remaining(),
+                        order.quote_remaining(),
+                    ),
+                    ctx.event_authority,
+                    ctx.market_account.clone(),
+                )?;
+
+                num_pruned += 1;
+            }
+
+            curr = next;
+        }
+    }
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -5,7 +5,10 @@ use dropset_interface::{
     events::RegisterMarketEventInstructionData,
     instructions::RegisterMarketInstructionData,
     state::{
-        market_header::MarketHeader,
+        market_header::{
+            MarketHeader,
+            NO_MARKET_AUTHORITY,
+        },
         sector::SECTOR_SIZE,
         transmutable::Transmutable,
     },
@@ -44,9 +47,19 @@ pub unsafe fn process_register_market<'a>(
     instruction_data: &[u8],
     event_buffer: &mut EventBuffer,
 ) -> Result<EventBufferContext<'a>, ProgramError> {
-    let num_sectors = RegisterMarketInstructionData::unpack_untagged(instruction_data)?.num_sectors;
+    let RegisterMarketInstructionData {
+        num_sectors,
+        authority,
+        is_permissioned,
+        params,
+    } = RegisterMarketInstructionData::unpack_untagged(instruction_data)?;
     let ctx = RegisterMarketContext::load(accounts)?;
 
+    // Only the market authority can allowlist traders, so a permissioned market needs one.
+    if is_permissioned && authority == NO_MARKET_AUTHORITY {
+        return Err(DropsetError::PermissionedMarketRequiresAuthority.into());
+    }
+
     // It's not necessary to check the returned PDA here because `CreateAccount` will fail if the
     // market account info's address doesn't match.
     let (_pda, market_bump) = Address::try_find_program_address(
@@ -101,7 +114,11 @@ pub unsafe fn process_register_market<'a>(
         ctx.base_mint.address(),
         ctx.quote_mint.address(),
         market_bump,
-    )?;
+        &authority,
+        &params,
+    )?
+    .header
+    .set_permissioned(is_permissioned);
 
     // Safety: `ctx.market_account.account` was just initialized as a market account.
     let market_account = unsafe { MarketAccountView::new_unchecked(ctx.market_account.account) };
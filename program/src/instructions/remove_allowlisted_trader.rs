@@ -0,0 +1,73 @@
+//! See [`process_remove_allowlisted_trader`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::RemoveAllowlistedTraderEventInstructionData,
+    instructions::RemoveAllowlistedTraderInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::seat_operations::{
+        find_mut_seat_with_hint,
+        find_seat_sector_index,
+    },
+};
+
+/// Instruction handler logic for the market authority removing a trader from a permissioned
+/// market's allowlist.
+///
+/// The trader keeps their seat, so they can still cancel their orders, withdraw and close it, but
+/// they can no longer take liquidity. Closing the seat drops the trader from the allowlist for
+/// good, since re-registering a seat requires the market authority to add them again.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::RemoveAllowlistedTrader`].
+#[inline(never)]
+pub unsafe fn process_remove_allowlisted_trader<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let RemoveAllowlistedTraderInstructionData {
+        trader,
+        sector_index_hint,
+    } = RemoveAllowlistedTraderInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    let sector_index = {
+        // Safety: Scoped mutable borrow of the market account data to update the trader's seat.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        if !market.header.is_permissioned() {
+            return Err(DropsetError::MarketNotPermissioned.into());
+        }
+        let sector_index = find_seat_sector_index(&market, sector_index_hint, &trader)?;
+        // Safety: The seat index was just verified as in-bounds.
+        unsafe { find_mut_seat_with_hint(&mut market, sector_index, &trader) }?
+            .set_allowlisted(false);
+        sector_index
+    };
+
+    event_buffer.add_to_buffer(
+        RemoveAllowlistedTraderEventInstructionData::new(trader, sector_index),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,51 @@
+//! See [`process_renounce_market_authority`].
+
+use dropset_interface::{
+    events::RenounceMarketAuthorityEventInstructionData,
+    state::market_header::NO_MARKET_AUTHORITY,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for the market authority permanently giving up its role. The market's
+/// parameters can't be changed afterwards.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::RenounceMarketAuthority`].
+#[inline(never)]
+pub unsafe fn process_renounce_market_authority<'a>(
+    accounts: &'a [AccountView],
+    _instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    // Safety: Scoped mutable borrow of the market account data to update the header.
+    unsafe { ctx.market_account.load_unchecked_mut() }
+        .header
+        .authority = NO_MARKET_AUTHORITY;
+
+    event_buffer.add_to_buffer(
+        RenounceMarketAuthorityEventInstructionData::new(*ctx.authority.address()),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
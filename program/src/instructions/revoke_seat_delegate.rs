@@ -0,0 +1,71 @@
+//! See [`process_revoke_seat_delegate`].
+
+use dropset_interface::{
+    events::RevokeSeatDelegateEventInstructionData,
+    instructions::RevokeSeatDelegateInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::seat_operations::{
+        find_seat_sector_index,
+        find_seat_with_hint,
+        remove_seat_delegate,
+    },
+};
+
+/// Instruction handler logic for a seat's user revoking their delegate. The delegate's resting
+/// orders stay on the book, since they belong to the seat. The delegate's sector is freed, so it
+/// can't be revoked while the market is being compacted.
+///
+/// Only the seat's user can revoke its delegate, since the seat is found by the signer's address.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::RevokeSeatDelegate`].
+#[inline(never)]
+pub unsafe fn process_revoke_seat_delegate<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let user_sector_index_hint =
+        RevokeSeatDelegateInstructionData::unpack_untagged(instruction_data)?
+            .user_sector_index_hint;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    let (user_sector_index, previous_delegate) = {
+        // Safety: Scoped mutable borrow of the market account data to update the user's seat.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        market.header.check_not_compacting()?;
+        let user_sector_index =
+            find_seat_sector_index(&market, user_sector_index_hint, ctx.user.address())?;
+        // Safety: The seat index was just verified as in-bounds.
+        unsafe { find_seat_with_hint(&market, user_sector_index, ctx.user.address()) }?;
+        // Safety: The seat index was just verified as the user's seat.
+        let previous_delegate = unsafe { remove_seat_delegate(&mut market, user_sector_index) };
+        (user_sector_index, previous_delegate)
+    };
+
+    event_buffer.add_to_buffer(
+        RevokeSeatDelegateEventInstructionData::new(user_sector_index, previous_delegate),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,142 @@
+//! See [`process_seat_market_order`].
+
+use dropset_interface::{
+    events::MarketOrderEventInstructionData,
+    instructions::SeatMarketOrderInstructionData,
+    matching::{
+        checked_swap_amounts,
+        AmountsFilled,
+        TakerOrder,
+    },
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::{
+        BufferedEvents,
+        EventBuffer,
+    },
+    instructions::market_order::fill_market_order::fill_market_order,
+    shared::seat_operations::{
+        find_authorized_seat,
+        find_mut_seat_with_hint,
+        settle_taker_fill_with_seat,
+    },
+};
+
+/// Instruction handler logic for processing a market order settled with the taker's market seat.
+///
+/// The order matches exactly like a regular market order, but instead of transferring tokens
+/// between the taker's and the market's token accounts, the amounts filled are debited from and
+/// credited to the taker's seat balances. It therefore requires neither token accounts nor token
+/// program CPIs.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::SeatMarketOrder`].
+#[inline(never)]
+pub unsafe fn process_seat_market_order<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let SeatMarketOrderInstructionData {
+        order_size,
+        is_buy,
+        is_base,
+        limit_price,
+        min_output,
+        time_in_force,
+        self_trade_prevention,
+        user_sector_index_hint,
+    } = SeatMarketOrderInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    let (user_sector_index, user) = {
+        // Safety: Scoped borrow of the market account data to check the market status, the order
+        // size against the market's lot size and minimum order sizes, and find the user's seat.
+        let market = unsafe { ctx.market_account.load_unchecked() };
+        market.header.check_is_active()?;
+        market.header.check_order_size(is_base, order_size)?;
+        find_authorized_seat(&market, user_sector_index_hint, ctx.user.address(), true)?
+    };
+
+    let taker_order = TakerOrder {
+        taker: &user,
+        order_size,
+        limit_price,
+        time_in_force,
+        self_trade_prevention,
+    };
+
+    let mut events = BufferedEvents {
+        event_buffer,
+        event_authority: ctx.event_authority,
+    };
+
+    // Safety: The market account data is not currently borrowed.
+    let AmountsFilled {
+        base: base_filled,
+        quote: quote_filled,
+        time_in_force,
+        is_fully_filled,
+        taker_fee,
+        ..
+    } = unsafe {
+        fill_market_order(
+            &mut ctx.market_account,
+            &mut events,
+            taker_order,
+            is_buy,
+            is_base,
+        )
+    }?;
+
+    // Ensure the taker receives at least the minimum output amount after the taker fee.
+    let (amount_in, amount_out) = checked_swap_amounts(
+        is_buy,
+        base_filled,
+        quote_filled,
+        taker_fee,
+        min_output,
+        u64::MAX,
+    )?;
+
+    // Settle the filled amounts with the user's seat.
+    {
+        // Safety: The market account is currently not borrowed in any capacity.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        // Safety: The user's seat index was already verified as in-bounds.
+        let user_seat = unsafe { find_mut_seat_with_hint(&mut market, user_sector_index, &user) }?;
+        settle_taker_fill_with_seat(user_seat, is_buy, amount_in, amount_out)?;
+    }
+
+    event_buffer.add_to_buffer(
+        MarketOrderEventInstructionData::new(
+            order_size,
+            is_buy,
+            is_base,
+            base_filled,
+            quote_filled,
+            time_in_force,
+            is_fully_filled,
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,60 @@
+//! See [`process_set_market_oracle`].
+
+use dropset_interface::{
+    events::SetMarketOracleEventInstructionData,
+    instructions::SetMarketOracleInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for the market authority setting or removing the oracle that the
+/// market's price bands are centered on.
+///
+/// The cached oracle price is cleared, so posts and fills are rejected until the new oracle's price
+/// is cached with [`dropset_interface::instructions::generated_program::UpdateOraclePrice`].
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::SetMarketOracle`].
+#[inline(never)]
+pub unsafe fn process_set_market_oracle<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let SetMarketOracleInstructionData {
+        oracle,
+        band_bps,
+        max_staleness_secs,
+    } = SetMarketOracleInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    // Safety: Scoped mutable borrow of the market account data to update the header.
+    unsafe { ctx.market_account.load_unchecked_mut() }
+        .header
+        .set_oracle(&oracle, band_bps, max_staleness_secs)?;
+
+    event_buffer.add_to_buffer(
+        SetMarketOracleEventInstructionData::new(oracle, band_bps, max_staleness_secs),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
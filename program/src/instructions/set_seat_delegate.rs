@@ -0,0 +1,82 @@
+//! See [`process_set_seat_delegate`].
+
+use dropset_interface::{
+    events::SetSeatDelegateEventInstructionData,
+    instructions::SetSeatDelegateInstructionData,
+    state::seat_delegate::SeatDelegate,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        mutate_orders_context::MutateOrdersContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::seat_operations::{
+        find_seat_sector_index,
+        find_seat_with_hint,
+        set_seat_delegate,
+    },
+};
+
+/// Instruction handler logic for a seat's user setting the delegate allowed to post, amend and
+/// cancel orders on their behalf, replacing any existing delegate.
+///
+/// The delegate is stored in its own sector, so a seat without a delegate needs a free sector to
+/// set one, like posting an order. Sectors can't be allocated while the market is being compacted.
+///
+/// Only the seat's user can set its delegate, since the seat is found by the signer's address.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::SetSeatDelegate`].
+#[inline(never)]
+pub unsafe fn process_set_seat_delegate<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let SetSeatDelegateInstructionData {
+        delegate,
+        can_take,
+        user_sector_index_hint,
+    } = SetSeatDelegateInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MutateOrdersContext::load(accounts) }?;
+
+    let user_sector_index = {
+        // Safety: Scoped mutable borrow of the market account data to update the user's seat.
+        let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
+        market.header.check_not_compacting()?;
+        let user_sector_index =
+            find_seat_sector_index(&market, user_sector_index_hint, ctx.user.address())?;
+        // Safety: The seat index was just verified as in-bounds.
+        unsafe { find_seat_with_hint(&market, user_sector_index, ctx.user.address()) }?;
+        // Safety: The seat index was just verified as the user's seat.
+        unsafe {
+            set_seat_delegate(
+                &mut market,
+                user_sector_index,
+                SeatDelegate::new(delegate, can_take),
+            )
+        }?;
+        user_sector_index
+    };
+
+    event_buffer.add_to_buffer(
+        SetSeatDelegateEventInstructionData::new(user_sector_index, delegate, can_take),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,123 @@
+//! See [`process_swap`].
+
+use dropset_interface::{
+    events::MarketOrderEventInstructionData,
+    instructions::SwapInstructionData,
+    matching::{
+        checked_swap_amounts,
+        is_swap_base_denominated,
+        swap_taker_order,
+        AmountsFilled,
+    },
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_order_context::MarketOrderContext,
+        EventBufferContext,
+    },
+    events::{
+        BufferedEvents,
+        EventBuffer,
+    },
+    instructions::market_order::fill_market_order::fill_market_order,
+    shared::token_utils::market_transfers::settle_taker_fill_with_token_accounts,
+};
+
+/// Instruction handler logic for a swap, the composable counterpart to a market order meant for
+/// cross-program invocations from other programs, e.g. aggregators.
+///
+/// A swap matches exactly like an immediate-or-cancel market order without a limit price that
+/// cancels the rest of the order when it would match against the taker's own resting order, and
+/// emits the same events through the same self-CPI (see [`MarketOrderContext`]). The amounts it
+/// fills can be quoted off-chain with [`dropset_interface::matching::simulate_swap`].
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::Swap`].
+#[inline(never)]
+pub unsafe fn process_swap<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let SwapInstructionData {
+        amount,
+        is_buy,
+        is_exact_out,
+        min_output,
+        max_input,
+    } = SwapInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketOrderContext::load(accounts) }?;
+
+    let is_base = is_swap_base_denominated(is_buy, is_exact_out);
+
+    // Safety: Scoped borrow of the market account data to check the market status, the user's
+    // allowlisting and the swap amount against the market's lot size and minimum order sizes.
+    unsafe { ctx.market_account.load_unchecked() }.check_taker_order(
+        ctx.user.address(),
+        is_base,
+        amount,
+    )?;
+
+    let mut events = BufferedEvents {
+        event_buffer,
+        event_authority: ctx.event_authority,
+    };
+
+    // Safety: The market account data is not currently borrowed.
+    let AmountsFilled {
+        base: base_filled,
+        quote: quote_filled,
+        time_in_force,
+        is_fully_filled,
+        taker_fee,
+        ..
+    } = unsafe {
+        fill_market_order(
+            &mut ctx.market_account,
+            &mut events,
+            swap_taker_order(ctx.user.address(), amount, is_buy),
+            is_buy,
+            is_base,
+        )
+    }?;
+
+    let (amount_in, amount_out) = checked_swap_amounts(
+        is_buy,
+        base_filled,
+        quote_filled,
+        taker_fee,
+        min_output,
+        max_input,
+    )?;
+
+    // Safety: No account data is currently borrowed.
+    unsafe { settle_taker_fill_with_token_accounts(&ctx, is_buy, amount_in, amount_out) }?;
+
+    event_buffer.add_to_buffer(
+        MarketOrderEventInstructionData::new(
+            amount,
+            is_buy,
+            is_base,
+            base_filled,
+            quote_filled,
+            time_in_force,
+            is_fully_filled,
+        ),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
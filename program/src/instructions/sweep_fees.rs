@@ -0,0 +1,64 @@
+//! See [`process_sweep_fees`].
+
+use dropset_interface::events::SweepFeesEventInstructionData;
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        sweep_fees_context::SweepFeesContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::token_utils::market_transfers::withdraw_non_zero_from_market,
+};
+
+/// Instruction handler logic for sweeping a market's accrued protocol fees to the fee authority's
+/// quote token account.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::SweepFees`].
+#[inline(never)]
+pub unsafe fn process_sweep_fees<'a>(
+    accounts: &'a [AccountView],
+    _instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { SweepFeesContext::load(accounts) }?;
+
+    // Take the entire accrued fee balance.
+    let amount = {
+        // Safety: Scoped mutable borrow of the market account data to zero out the accrued fees.
+        let market = unsafe { ctx.market_account.load_unchecked_mut() };
+        let amount = market.header.protocol_fees();
+        market.header.set_protocol_fees(0);
+        amount
+    };
+
+    // Safety: The market account data is no longer borrowed.
+    unsafe {
+        withdraw_non_zero_from_market(
+            &ctx.fee_recipient_ata,
+            &ctx.quote_market_ata,
+            &ctx.market_account,
+            &ctx.quote_mint,
+            amount,
+        )
+    }?;
+
+    event_buffer.add_to_buffer(
+        SweepFeesEventInstructionData::new(amount),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
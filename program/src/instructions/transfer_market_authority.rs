@@ -0,0 +1,53 @@
+//! See [`process_transfer_market_authority`].
+
+use dropset_interface::{
+    events::TransferMarketAuthorityEventInstructionData,
+    instructions::TransferMarketAuthorityInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for the market authority transferring its role to a new address.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::TransferMarketAuthority`].
+#[inline(never)]
+pub unsafe fn process_transfer_market_authority<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let TransferMarketAuthorityInstructionData { new_authority } =
+        TransferMarketAuthorityInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    // Safety: Scoped mutable borrow of the market account data to update the header.
+    unsafe { ctx.market_account.load_unchecked_mut() }
+        .header
+        .authority = new_authority;
+
+    event_buffer.add_to_buffer(
+        TransferMarketAuthorityEventInstructionData::new(*ctx.authority.address(), new_authority),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,56 @@
+//! See [`process_update_market_params`].
+
+use dropset_interface::{
+    events::UpdateMarketParamsEventInstructionData,
+    instructions::UpdateMarketParamsInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for the market authority updating the market's parameters.
+///
+/// The new fee rates only apply to fills after this instruction. Protocol fees already accrued are
+/// unaffected and can be swept by the new fee authority.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::UpdateMarketParams`].
+#[inline(never)]
+pub unsafe fn process_update_market_params<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let UpdateMarketParamsInstructionData { params } =
+        UpdateMarketParamsInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    // Safety: Scoped mutable borrow of the market account data to update the header.
+    unsafe { ctx.market_account.load_unchecked_mut() }
+        .header
+        .set_params(&params)?;
+
+    event_buffer.add_to_buffer(
+        UpdateMarketParamsEventInstructionData::new(params),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,56 @@
+//! See [`process_update_market_status`].
+
+use dropset_interface::{
+    events::UpdateMarketStatusEventInstructionData,
+    instructions::UpdateMarketStatusInstructionData,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        market_authority_context::MarketAuthorityContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+};
+
+/// Instruction handler logic for the market authority updating the market's trading status.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::UpdateMarketStatus`].
+#[inline(never)]
+pub unsafe fn process_update_market_status<'a>(
+    accounts: &'a [AccountView],
+    instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    let UpdateMarketStatusInstructionData { status } =
+        UpdateMarketStatusInstructionData::unpack_untagged(instruction_data)?;
+
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { MarketAuthorityContext::load(accounts) }?;
+
+    let previous_status = {
+        // Safety: Scoped mutable borrow of the market account data to update the header.
+        let market = unsafe { ctx.market_account.load_unchecked_mut() };
+        let previous_status = market.header.status();
+        market.header.set_status(status)?;
+        previous_status
+    };
+
+    event_buffer.add_to_buffer(
+        UpdateMarketStatusEventInstructionData::new(previous_status, status),
+        ctx.event_authority,
+        ctx.market_account.clone(),
+    )?;
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
@@ -0,0 +1,75 @@
+//! See [`process_update_oracle_price`].
+
+use dropset_interface::{
+    error::DropsetError,
+    events::UpdateOraclePriceEventInstructionData,
+    state::oracle::OraclePrice,
+};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+};
+
+use crate::{
+    context::{
+        update_oracle_price_context::UpdateOraclePriceContext,
+        EventBufferContext,
+    },
+    events::EventBuffer,
+    shared::order_operations::current_unix_timestamp,
+};
+
+/// Instruction handler logic for the permissionless crank that caches the market oracle's price in
+/// the market header.
+///
+/// The cached price is only updated if the oracle's price was published after it, so replaying an
+/// older oracle state can never roll the cached price back. An oracle price published after the
+/// current clock time is rejected, since it would keep the cached price fresh indefinitely. Posts
+/// and fills are checked against the cached price, which keeps their account lists unchanged.
+///
+/// # Safety
+///
+/// Caller upholds the safety contract detailed in
+/// [`dropset_interface::instructions::generated_program::UpdateOraclePrice`].
+#[inline(never)]
+pub unsafe fn process_update_oracle_price<'a>(
+    accounts: &'a [AccountView],
+    _instruction_data: &[u8],
+    event_buffer: &mut EventBuffer,
+) -> Result<EventBufferContext<'a>, ProgramError> {
+    // Safety: No account data in `accounts` is currently borrowed.
+    let mut ctx = unsafe { UpdateOraclePriceContext::load(accounts) }?;
+
+    let (encoded_price, publish_time) = {
+        // Safety: Scoped borrow of the oracle account data, which is never mutably borrowed.
+        let oracle_price = OraclePrice::load_checked(unsafe { ctx.oracle.borrow_unchecked() })?;
+        (oracle_price.encoded_price(), oracle_price.publish_time())
+    };
+
+    if publish_time > current_unix_timestamp()? {
+        return Err(DropsetError::InvalidOracleAccount.into());
+    }
+
+    let updated = {
+        // Safety: Scoped mutable borrow of the market account data to update the header.
+        let market = unsafe { ctx.market_account.load_unchecked_mut() };
+        let updated = publish_time > market.header.oracle_publish_time();
+        if updated {
+            market.header.set_oracle_price(encoded_price, publish_time);
+        }
+        updated
+    };
+
+    if updated {
+        event_buffer.add_to_buffer(
+            UpdateOraclePriceEventInstructionData::new(encoded_price, publish_time),
+            ctx.event_authority,
+            ctx.market_account.clone(),
+        )?;
+    }
+
+    Ok(EventBufferContext {
+        event_authority: ctx.event_authority,
+        market_account: ctx.market_account,
+    })
+}
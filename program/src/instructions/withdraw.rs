@@ -4,7 +4,6 @@ use dropset_interface::{
     error::DropsetError,
     events::WithdrawEventInstructionData,
     instructions::WithdrawInstructionData,
-    state::sector::Sector,
 };
 use pinocchio::{
     account::AccountView,
@@ -18,7 +17,10 @@ use crate::{
     },
     events::EventBuffer,
     shared::{
-        seat_operations::find_mut_seat_with_hint,
+        seat_operations::{
+            find_mut_seat_with_hint,
+            find_seat_sector_index,
+        },
         token_utils::market_transfers::withdraw_non_zero_from_market,
     },
 };
@@ -42,6 +44,11 @@ pub unsafe fn process_withdraw<'a>(
 
     // Safety: No account data in `accounts` is currently borrowed.
     let mut ctx = unsafe { DepositWithdrawContext::load(accounts) }?;
+
+    // Safety: Scoped borrow of the market account data to check the market status.
+    unsafe { ctx.market_account.load_unchecked() }
+        .header
+        .check_allows_exits()?;
     unsafe {
         withdraw_non_zero_from_market(
             &ctx.user_ata,
@@ -55,11 +62,11 @@ pub unsafe fn process_withdraw<'a>(
     // Safety: Scoped mutable borrow of market account data to update the user's seat.
     let mut market = unsafe { ctx.market_account.load_unchecked_mut() };
 
-    // Find the seat with the index hint or fail and return early.
-    Sector::check_in_bounds(market.sectors, sector_index_hint)?;
-    // Safety: The hint was just verified as in-bounds.
-    let seat =
-        unsafe { find_mut_seat_with_hint(&mut market, sector_index_hint, ctx.user.address()) }?;
+    // Find the seat with the index hint, or by address if the hint is NIL, or fail and return
+    // early.
+    let sector_index = find_seat_sector_index(&market, sector_index_hint, ctx.user.address())?;
+    // Safety: The seat index was just verified as in-bounds.
+    let seat = unsafe { find_mut_seat_with_hint(&mut market, sector_index, ctx.user.address()) }?;
 
     // Update the market seat available/deposited, checking for underflow, as that means the user
     // tried to withdraw more than they have available.
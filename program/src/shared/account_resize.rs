@@ -1,8 +1,10 @@
 //! Handles resizing Solana accounts in-place with rent and lamport adjustments, ensuring safety
 //! when reallocating market or seat accounts.
 
+use dropset_interface::error::DropsetError;
 use pinocchio::{
     account::AccountView,
+    error::ProgramError,
     sysvars::{
         rent::Rent,
         Sysvar,
@@ -11,14 +13,16 @@ use pinocchio::{
 };
 
 /// Transfers `lamports_diff` lamports from `payer` to `account`, where `lamports_diff` is the
-/// calculated difference in lamports required for the account given the requested additional space.
+/// calculated difference in lamports required for the account given the requested additional space,
+/// and then resizes `account` to its new size.
 ///
-/// Typically this call should be followed by an account resize. It isn't provided here so the
-/// caller can decide on a case by case basis which version of the resize invocation to call.
-///
-/// - If the lamport diff is zero, the transfer CPI isn't invoked.
+/// - If the lamport diff is zero, e.g. because the account was already sent enough lamports, the
+///   transfer CPI isn't invoked.
 /// - Otherwise, the `payer` transfers the necessary lamports.
 ///
+/// The account is always resized, since callers initialize the bytes past the current data length
+/// right after this call.
+///
 /// # Safety
 ///
 /// Caller guarantees:
@@ -39,17 +43,56 @@ pub unsafe fn fund_then_resize_unchecked(
     let new_lamports_required = Rent::get()?.try_minimum_balance(new_size)?;
     let lamports_diff = new_lamports_required.saturating_sub(current_lamports);
 
-    if lamports_diff == 0 {
-        return Ok(());
-    }
-
-    pinocchio_system::instructions::Transfer {
-        from: payer, // WRITE
-        to: account, // WRITE
-        lamports: lamports_diff,
+    if lamports_diff != 0 {
+        pinocchio_system::instructions::Transfer {
+            from: payer, // WRITE
+            to: account, // WRITE
+            lamports: lamports_diff,
+        }
+        .invoke()?;
     }
-    .invoke()?;
 
     // Safety: Scoped mutable borrow of the account data.
     unsafe { account.resize_unchecked(new_size) }
 }
+
+/// Shrinks `account` to `new_size` bytes and then transfers the lamports it holds in excess of the
+/// rent-exempt minimum for its new size to `recipient`.
+///
+/// The lamports are moved by directly debiting and crediting the accounts' balances, so `account`
+/// must be owned by this program. Returns the number of lamports refunded.
+///
+/// # Safety
+///
+/// Caller guarantees:
+/// - WRITE accounts are not currently borrowed in *any* capacity.
+/// - `new_size` is not larger than the account's current data length.
+///
+/// ### Accounts
+///   0. `[WRITE]` Account to be resized
+///   1. `[WRITE]` Recipient
+pub unsafe fn resize_then_refund_unchecked(
+    account: &AccountView,
+    recipient: &AccountView,
+    new_size: usize,
+) -> Result<u64, ProgramError> {
+    // Safety: Scoped mutable borrow of the account data.
+    unsafe { account.resize_unchecked(new_size) }?;
+
+    let current_lamports = account.lamports();
+    let new_lamports_required = Rent::get()?.try_minimum_balance(new_size)?;
+    let lamports_refunded = current_lamports.saturating_sub(new_lamports_required);
+
+    if lamports_refunded == 0 {
+        return Ok(0);
+    }
+
+    let recipient_lamports = recipient
+        .lamports()
+        .checked_add(lamports_refunded)
+        .ok_or(DropsetError::ArithmeticOverflow)?;
+    account.set_lamports(new_lamports_required);
+    recipient.set_lamports(recipient_lamports);
+
+    Ok(lamports_refunded)
+}
@@ -2,18 +2,34 @@
 
 use dropset_interface::{
     error::DropsetError,
+    instructions::MarketParams,
     state::{
+        compaction_phase::CompactionPhase,
         market::{
             Market,
             MarketRefMut,
         },
         market_header::MarketHeader,
-        sector::SECTOR_SIZE,
+        market_seat::MarketSeat,
+        seat_delegate::SeatDelegate,
+        sector::{
+            Sector,
+            SectorIndex,
+            NIL,
+            SECTOR_SIZE,
+        },
+        sector_kind::SectorKind,
         transmutable::Transmutable,
+        user_order_sectors::UserOrders,
     },
 };
 use solana_address::Address;
 
+use crate::shared::order_operations::{
+    load_mut_order_from_sector_index,
+    load_order_from_sector_index,
+};
+
 /// Initializes a freshly created market account. This function skips checks based on the assumption
 /// that the market has just been created on-chain.
 ///
@@ -24,12 +40,16 @@ pub fn initialize_market_account_data<'a>(
     base_mint: &Address,
     quote_mint: &Address,
     market_bump: u8,
+    authority: &Address,
+    params: &MarketParams,
 ) -> Result<MarketRefMut<'a>, DropsetError> {
     let account_data_len = zeroed_market_account_data.len();
     if account_data_len < MarketHeader::LEN {
         return Err(DropsetError::UnallocatedAccountData);
     }
 
+    MarketHeader::check_params(params)?;
+
     let sector_bytes = account_data_len - MarketHeader::LEN;
 
     if sector_bytes % SECTOR_SIZE != 0 {
@@ -47,6 +67,8 @@ pub fn initialize_market_account_data<'a>(
             market_bump,
             base_mint,
             quote_mint,
+            authority,
+            params,
         );
     }
 
@@ -62,20 +84,246 @@ pub fn initialize_market_account_data<'a>(
     Ok(market)
 }
 
+/// The progress made by a single [`compact_market_sectors`] call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompactionProgress {
+    /// The number of live sectors relocated in this call.
+    pub num_sectors_relocated: u32,
+    /// The number of live sectors, which is the number of sectors the sectors region can be
+    /// truncated to, if the compaction is complete. `None` if it's still in progress.
+    pub num_live_sectors: Option<u32>,
+}
+
+/// Compacts a market's sectors so that every free sector ends up at the end of the sectors region,
+/// where it can be truncated.
+///
+/// Every live sector at or past the market's number of live sectors is relocated into a free sector
+/// before it. Relocating a seat updates the `user_seat` back-pointer of each of its orders, and
+/// relocating an order re-links its neighbors in its user's orders to the order's new sector. A
+/// seat's delegate sector isn't in any list, so it's relocated when its seat is visited. The
+/// relocated seats, delegates and orders therefore get new sector indices.
+///
+/// A compaction walks the seats, then the bids, then the asks, visiting at most `max_sectors` live
+/// sectors per call and storing its cursor in the market header, so large markets can be compacted
+/// over multiple calls. The truncation point is fixed when the compaction starts, and the free
+/// sectors past it are removed from the free stack right away, so the market must not allocate or
+/// free any sectors until the compaction is complete.
+///
+/// Once the walk completes, the free stack is empty and the returned progress has the number of
+/// live sectors.
+pub fn compact_market_sectors(
+    market: &mut MarketRefMut<'_>,
+    max_sectors: u16,
+) -> Result<CompactionProgress, DropsetError> {
+    if !market.header.is_compacting() {
+        let num_live_sectors = market.get_capacity() - market.header.num_free_sectors();
+
+        // Only the free sectors before the truncation point can hold relocated sectors. There are
+        // exactly as many of them as there are live sectors at or past the truncation point.
+        market.free_stack().retain_below(num_live_sectors);
+
+        // Relocate seats first, so that relocated orders always point to their seat's final index.
+        market.header.set_compaction_end(num_live_sectors);
+        market.header.set_compaction_phase(CompactionPhase::Seats);
+        market
+            .header
+            .set_compaction_cursor(market.header.seats_dll_head());
+    }
+
+    let num_live_sectors = market.header.compaction_end();
+    let mut num_sectors_visited = 0;
+    let mut num_sectors_relocated = 0;
+
+    loop {
+        let phase = market.header.compaction_phase();
+        let mut curr = market.header.compaction_cursor();
+
+        while curr != NIL && num_sectors_visited < max_sectors {
+            // Safety: `curr` is non-NIL and per the linked list impl, must be in-bounds.
+            let next = unsafe { Sector::from_sector_index(market.sectors, curr) }.next();
+
+            let mut index = curr;
+            if curr >= num_live_sectors {
+                let new_index = market.free_stack().pop_free_sector()?;
+                // Safety: `curr` came from the current phase's list and `new_index` from the free
+                // stack. The phase is never idle while compacting, so the remaining phase is asks.
+                unsafe {
+                    match phase {
+                        CompactionPhase::Seats => relocate_seat(market, curr, new_index),
+                        CompactionPhase::Bids => relocate_order(market, curr, new_index, true),
+                        _ => relocate_order(market, curr, new_index, false),
+                    }
+                };
+                index = new_index;
+                num_sectors_relocated += 1;
+            }
+
+            if phase == CompactionPhase::Seats {
+                // Safety: `index` is the visited seat's sector index after any relocation.
+                let delegate_sector = unsafe { Sector::from_sector_index(market.sectors, index) }
+                    .load_payload::<MarketSeat>()
+                    .delegate_sector();
+                if delegate_sector != NIL && delegate_sector >= num_live_sectors {
+                    let new_index = market.free_stack().pop_free_sector()?;
+                    // Safety: `index` is a seat, `delegate_sector` is its delegate sector and
+                    // `new_index` was just popped from the free stack.
+                    unsafe { relocate_seat_delegate(market, index, delegate_sector, new_index) };
+                    num_sectors_relocated += 1;
+                }
+            }
+
+            num_sectors_visited += 1;
+            curr = next;
+        }
+
+        if curr != NIL {
+            market.header.set_compaction_cursor(curr);
+            return Ok(CompactionProgress {
+                num_sectors_relocated,
+                num_live_sectors: None,
+            });
+        }
+
+        let (next_phase, next_cursor) = match phase {
+            CompactionPhase::Seats => (CompactionPhase::Bids, market.header.bids_dll_head()),
+            CompactionPhase::Bids => (CompactionPhase::Asks, market.header.asks_dll_head()),
+            _ => (CompactionPhase::Idle, NIL),
+        };
+        market.header.set_compaction_phase(next_phase);
+        market.header.set_compaction_cursor(next_cursor);
+
+        if next_phase == CompactionPhase::Idle {
+            market.header.set_compaction_end(NIL);
+            debug_assert_eq!(market.header.num_free_sectors(), 0);
+
+            return Ok(CompactionProgress {
+                num_sectors_relocated,
+                num_live_sectors: Some(num_live_sectors),
+            });
+        }
+    }
+}
+
+/// Relocates the seat at `index` to the free sector at `new_index` and updates the `user_seat`
+/// back-pointer of each of the seat's orders.
+///
+/// # Safety
+///
+/// Caller guarantees `index` is in the seats list and `new_index` was just popped from the free
+/// stack.
+unsafe fn relocate_seat(market: &mut MarketRefMut<'_>, index: SectorIndex, new_index: SectorIndex) {
+    // Safety: Caller guarantees both indices are valid for relocating a seat.
+    unsafe { market.seats().relocate(index, new_index) };
+
+    for is_bid in [true, false] {
+        // Safety: The seat was just relocated to `new_index`.
+        let mut curr_order = {
+            let seat = unsafe { Sector::from_sector_index(market.sectors, new_index) }
+                .load_payload::<MarketSeat>();
+            if is_bid {
+                seat.user_order_sectors.bids.head()
+            } else {
+                seat.user_order_sectors.asks.head()
+            }
+        };
+        while curr_order != NIL {
+            // Safety: The sector indices in a user's orders always point to valid orders.
+            let order = unsafe { load_mut_order_from_sector_index(market, curr_order) };
+            order.set_user_seat(new_index);
+            curr_order = order.next_user_order();
+        }
+    }
+}
+
+/// Relocates the delegate sector at `index` of the seat at `seat_index` to the free sector at
+/// `new_index` and points the seat to it.
+///
+/// # Safety
+///
+/// Caller guarantees `seat_index` is in the seats list, `index` is its delegate sector and
+/// `new_index` was just popped from the free stack.
+unsafe fn relocate_seat_delegate(
+    market: &mut MarketRefMut<'_>,
+    seat_index: SectorIndex,
+    index: SectorIndex,
+    new_index: SectorIndex,
+) {
+    // Safety: Caller guarantees all indices are in-bounds.
+    unsafe {
+        let delegate = Sector::from_sector_index(market.sectors, index)
+            .load_payload::<SeatDelegate>()
+            .clone();
+        let sector = Sector::from_sector_index_mut(market.sectors, new_index);
+        sector.set_payload(delegate.as_bytes());
+        sector.set_kind(SectorKind::SeatDelegate);
+        Sector::from_sector_index_mut(market.sectors, seat_index)
+            .load_payload_mut::<MarketSeat>()
+            .set_delegate_sector(new_index);
+    }
+}
+
+/// Relocates the order at `index` to the free sector at `new_index` and re-links its neighbors in
+/// its user's orders to the order's new sector.
+///
+/// # Safety
+///
+/// Caller guarantees `index` is in the bids list if `is_bid` and the asks list otherwise, and
+/// `new_index` was just popped from the free stack.
+unsafe fn relocate_order(
+    market: &mut MarketRefMut<'_>,
+    index: SectorIndex,
+    new_index: SectorIndex,
+    is_bid: bool,
+) {
+    // Safety: Caller guarantees both indices are valid for relocating an order.
+    unsafe {
+        if is_bid {
+            market.bids().relocate(index, new_index);
+        } else {
+            market.asks().relocate(index, new_index);
+        }
+    }
+
+    // Safety: The order was just relocated to `new_index`.
+    let user_seat = unsafe { load_order_from_sector_index(market, new_index) }.user_seat();
+    // Safety: An order's user seat always points to a valid seat, and the order at `new_index` is
+    // a copy of the user's order with its links unchanged.
+    unsafe { UserOrders::new(market.sectors, user_seat, is_bid).replace(new_index) };
+}
+
 #[cfg(test)]
 pub mod tests {
-    use dropset_interface::state::{
-        market_seat::MarketSeat,
-        sector::{
-            SectorIndex,
-            SECTOR_SIZE,
+    use dropset_interface::{
+        error::DropsetError,
+        state::{
+            market_seat::MarketSeat,
+            order::Order,
+            sector::{
+                SectorIndex,
+                NIL,
+                SECTOR_SIZE,
+            },
+            transmutable::Transmutable,
+            user_order_sectors::MAX_ORDERS,
         },
-        transmutable::Transmutable,
+    };
+    use price::{
+        biased_exponent,
+        to_order_info,
+        OrderInfoArgs,
+        UNBIASED_MAX,
     };
     use solana_address::Address;
 
     use super::initialize_market_account_data;
-    use crate::shared::seat_operations::try_insert_market_seat;
+    use crate::shared::{
+        order_operations::insert_order,
+        seat_operations::{
+            find_seat_by_address,
+            find_seat_sector_index,
+            try_insert_market_seat,
+        },
+    };
 
     extern crate std;
     use std::{
@@ -94,6 +342,8 @@ pub mod tests {
             &Address::from_str_const("11111111111111111111111111111111111111111111"),
             &Address::from_str_const("22222222222222222222222222222222222222222222"),
             254,
+            &Address::from_str_const("33333333333333333333333333333333333333333333"),
+            &MarketParams::default(),
         )
         .expect("Should initialize market data");
 
@@ -146,4 +396,205 @@ pub mod tests {
             assert_eq!(pk_e, pk_a);
         }
     }
+
+    #[test]
+    fn market_find_seats_without_hint() {
+        const N_SECTORS: usize = 10;
+        let mut bytes = [0u8; MarketHeader::LEN + SECTOR_SIZE * N_SECTORS];
+        let mut market = initialize_market_account_data(
+            bytes.as_mut(),
+            &Address::from_str_const("11111111111111111111111111111111111111111111"),
+            &Address::from_str_const("22222222222222222222222222222222222222222222"),
+            254,
+            &Address::from_str_const("33333333333333333333333333333333333333333333"),
+            &MarketParams::default(),
+        )
+        .expect("Should initialize market data");
+
+        let [one, two, three, four]: [Address; 4] =
+            core::array::from_fn(|i| Address::new_from_array([(i + 1) as u8; 32]));
+
+        // Insert out of order so the sector indices don't match the sorted seat order.
+        let [three_index, one_index, four_index] = [three, one, four].map(|user| {
+            try_insert_market_seat(&mut market.seats(), MarketSeat::new(user, 0, 0))
+                .expect("Should insert seat")
+        });
+
+        assert_eq!(find_seat_by_address(&market, &one), Some(one_index));
+        assert_eq!(find_seat_by_address(&market, &three), Some(three_index));
+        assert_eq!(find_seat_by_address(&market, &four), Some(four_index));
+        assert_eq!(find_seat_by_address(&market, &two), None);
+
+        // A NIL hint falls back to the search by address.
+        assert_eq!(find_seat_sector_index(&market, NIL, &four), Ok(four_index));
+        assert_eq!(
+            find_seat_sector_index(&market, NIL, &two),
+            Err(DropsetError::SeatNotFound)
+        );
+
+        // A non-NIL hint must point directly to the user's seat.
+        assert_eq!(
+            find_seat_sector_index(&market, one_index, &one),
+            Ok(one_index)
+        );
+        assert_eq!(
+            find_seat_sector_index(&market, three_index, &one),
+            Err(DropsetError::InvalidIndexHint)
+        );
+        assert_eq!(
+            find_seat_sector_index(&market, N_SECTORS as SectorIndex, &one),
+            Err(DropsetError::IndexOutOfBounds)
+        );
+    }
+
+    /// Test utility function to insert an order for the seat at `seat_index` and map it in the
+    /// seat, like posting an order without any collateral checks.
+    fn insert_test_order(
+        market: &mut MarketRefMut<'_>,
+        seat_index: SectorIndex,
+        is_bid: bool,
+        price_mantissa: u32,
+    ) -> SectorIndex {
+        let order_info = to_order_info(OrderInfoArgs::new(
+            price_mantissa,
+            1,
+            biased_exponent!(UNBIASED_MAX),
+            biased_exponent!(-1),
+        ))
+        .expect("The unit test should pass a valid price mantissa");
+        let order = Order::new(order_info, seat_index);
+
+        let order_index = if is_bid {
+            insert_order(&mut market.bids(), order)
+        } else {
+            insert_order(&mut market.asks(), order)
+        }
+        .expect("Should insert order");
+
+        unsafe { UserOrders::new(market.sectors, seat_index, is_bid).add(order_index, MAX_ORDERS) }
+            .expect("Should add the order to the user's orders");
+
+        order_index
+    }
+
+    /// Compacts a market with holes at the front of its sectors region, visiting at most
+    /// `max_sectors` sectors per call, and checks the compacted market. Returns the number of calls
+    /// it took to complete the compaction.
+    fn compact_and_check(max_sectors: u16) -> usize {
+        const N_SECTORS: usize = 10;
+        let mut bytes = [0u8; MarketHeader::LEN + SECTOR_SIZE * N_SECTORS];
+        let mut market = initialize_market_account_data(
+            bytes.as_mut(),
+            &Address::from_str_const("11111111111111111111111111111111111111111111"),
+            &Address::from_str_const("22222222222222222222222222222222222222222222"),
+            254,
+            &Address::from_str_const("33333333333333333333333333333333333333333333"),
+            &MarketParams::default(),
+        )
+        .expect("Should initialize market data");
+
+        let [one, two, three, four]: [Address; 4] =
+            core::array::from_fn(|i| Address::new_from_array([(i + 1) as u8; 32]));
+        let insert_seat = |market: &mut MarketRefMut<'_>, user| {
+            try_insert_market_seat(&mut market.seats(), MarketSeat::new(user, 0, 0))
+                .expect("Should insert seat")
+        };
+
+        // Sectors are handed out from the front, so the layout is:
+        // [one, two, three, three's bid, three's ask, four, four's bid, free, free, free]
+        let one_index = insert_seat(&mut market, one);
+        let two_index = insert_seat(&mut market, two);
+        let three_index = insert_seat(&mut market, three);
+        let three_bid = insert_test_order(&mut market, three_index, true, 11_111_111);
+        let three_ask = insert_test_order(&mut market, three_index, false, 33_333_333);
+        let four_index = insert_seat(&mut market, four);
+        let four_bid = insert_test_order(&mut market, four_index, true, 22_222_222);
+        assert_eq!(
+            [
+                one_index,
+                two_index,
+                three_index,
+                three_bid,
+                three_ask,
+                four_index,
+                four_bid
+            ],
+            [0, 1, 2, 3, 4, 5, 6]
+        );
+
+        // Free the first two sectors, leaving holes at the front of the sectors region.
+        unsafe {
+            market.seats().remove_at(one_index);
+            market.seats().remove_at(two_index);
+        }
+
+        let mut num_calls = 0;
+        let mut num_sectors_relocated = 0;
+        let num_live_sectors = loop {
+            let progress =
+                compact_market_sectors(&mut market, max_sectors).expect("Should compact");
+            num_calls += 1;
+            num_sectors_relocated += progress.num_sectors_relocated;
+            match progress.num_live_sectors {
+                Some(num_live_sectors) => break num_live_sectors,
+                None => assert!(market.header.is_compacting()),
+            }
+        };
+        assert!(!market.header.is_compacting());
+        assert_eq!(num_live_sectors, 5);
+        // Only four's seat and bid are past the truncation point.
+        assert_eq!(num_sectors_relocated, 2);
+        assert_eq!(market.header.num_free_sectors(), 0);
+        assert_eq!(market.header.free_stack_top(), NIL);
+
+        // Sectors before the truncation point are left in place.
+        assert_eq!(find_seat_by_address(&market, &three), Some(three_index));
+        // The seat and order past the truncation point are relocated into the freed sectors.
+        let four_index = find_seat_by_address(&market, &four).expect("Should find seat");
+        assert!(four_index < num_live_sectors);
+        assert_eq!(find_seat_by_address(&market, &one), None);
+        assert_eq!(find_seat_by_address(&market, &two), None);
+
+        // Every live sector is before the truncation point, and every seat's orders point back to
+        // the seat and are on the seat's side of the book.
+        let seats: Vec<(SectorIndex, MarketSeat)> = market
+            .iter_seats()
+            .map(|(i, sector)| (i, sector.load_payload::<MarketSeat>().clone()))
+            .collect();
+        assert_eq!(seats.len(), 2);
+        for (seat_index, seat) in seats {
+            assert!(seat_index < num_live_sectors);
+            for (is_bid, order_sectors) in [
+                (true, &seat.user_order_sectors.bids),
+                (false, &seat.user_order_sectors.asks),
+            ] {
+                for (order_index, order) in order_sectors.iter(market.sectors) {
+                    assert!(order_index < num_live_sectors);
+                    assert_eq!(order.user_seat(), seat_index);
+
+                    let mut list = if is_bid { market.iter_bids() } else { market.iter_asks() };
+                    assert!(list.any(|(i, _)| i == order_index));
+                }
+            }
+        }
+
+        let bids: Vec<SectorIndex> = market.iter_bids().map(|(i, _)| i).collect();
+        let asks: Vec<SectorIndex> = market.iter_asks().map(|(i, _)| i).collect();
+        assert_eq!(bids.len(), 2);
+        assert_eq!(asks, vec![three_ask]);
+        assert!(bids.iter().all(|i| *i < num_live_sectors));
+
+        num_calls
+    }
+
+    #[test]
+    fn market_compact_sectors() {
+        assert_eq!(compact_and_check(u16::MAX), 1);
+    }
+
+    #[test]
+    fn market_compact_sectors_over_multiple_calls() {
+        // There are 5 live sectors in 3 lists, so visiting one sector per call takes 5 calls.
+        assert_eq!(compact_and_check(1), 5);
+    }
 }
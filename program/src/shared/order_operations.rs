@@ -3,6 +3,8 @@
 use dropset_interface::{
     error::DropsetError,
     state::{
+        asks_dll::AskOrders,
+        bids_dll::BidOrders,
         linked_list::{
             LinkedList,
             LinkedListHeaderOperations,
@@ -12,6 +14,7 @@ use dropset_interface::{
             MarketRefMut,
         },
         market_header::MarketHeader,
+        market_seat::MarketSeat,
         order::{
             Order,
             OrdersCollection,
@@ -21,13 +24,39 @@ use dropset_interface::{
             SectorIndex,
             NIL,
         },
+        user_order_sectors::UserOrders,
     },
 };
+use pinocchio::{
+    error::ProgramError,
+    sysvars::{
+        clock::Clock,
+        Sysvar,
+    },
+};
+use price::OrderInfo;
+use solana_address::Address;
+
+use crate::shared::seat_operations::{
+    find_mut_seat_with_hint,
+    find_seat_with_hint,
+};
+
+/// The optional, user-supplied fields stored on a newly posted order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostOrderOptions {
+    /// The unix timestamp in seconds at which the order expires, or
+    /// [`dropset_interface::state::order::NO_EXPIRY`].
+    pub expiry: u64,
+    /// The user's own identifier for the order, or
+    /// [`dropset_interface::state::order::NO_CLIENT_ORDER_ID`].
+    pub client_order_id: u64,
+}
 
 /// Insert a new user order into the orders collection.
 ///
 /// NOTE: this function solely inserts the order into the orders collection. It doesn't update the
-/// user's seat nor does it check for duplicate prices posted by the same user.
+/// user's seat.
 pub fn insert_order<T: OrdersCollection + LinkedListHeaderOperations>(
     list: &mut LinkedList<'_, T>,
     order: Order,
@@ -86,6 +115,357 @@ pub unsafe fn load_mut_order_from_sector_index<'m>(
     sector.load_payload_mut::<Order>()
 }
 
+/// Returns the current unix timestamp in seconds from the clock sysvar, used to check order expiry.
+#[inline(always)]
+pub fn current_unix_timestamp() -> Result<u64, ProgramError> {
+    // A negative unix timestamp is treated as zero, which no order expiry can precede.
+    Ok(Clock::get()?.unix_timestamp.max(0) as u64)
+}
+
+/// Posts a user's bid or ask order on the market's order book and updates the user's seat with the
+/// collateral used and adds the new order to the user's orders.
+///
+/// Fails if the order's expiry isn't [`dropset_interface::state::order::NO_EXPIRY`] and is at or
+/// before the current `unix_timestamp`, or if the market has an oracle and the order's price is
+/// outside the market's oracle band.
+///
+/// Returns the new order's sector index.
+pub fn post_user_order(
+    market: &mut MarketRefMut<'_>,
+    user: &Address,
+    order_info: OrderInfo,
+    is_bid: bool,
+    user_sector_index_hint: SectorIndex,
+    options: PostOrderOptions,
+    unix_timestamp: u64,
+) -> Result<SectorIndex, DropsetError> {
+    let (base_atoms, quote_atoms) = (order_info.base_atoms, order_info.quote_atoms);
+    market
+        .header
+        .check_oracle_band(order_info.encoded_price.as_u32(), unix_timestamp)?;
+
+    // To avoid convoluted borrow checking rules, optimistically insert the order with the index
+    // hint passed in, assuming it's valid. It's verified later when mutating the market seat.
+    let mut order = Order::new(order_info, user_sector_index_hint);
+    order.set_expiry(options.expiry);
+    order.set_client_order_id(options.client_order_id);
+    if order.is_expired(unix_timestamp) {
+        return Err(DropsetError::OrderAlreadyExpired);
+    }
+
+    let order_sector_index = {
+        if is_bid {
+            BidOrders::post_only_crossing_check(&order, market)?;
+            insert_order(&mut market.bids(), order)
+        } else {
+            AskOrders::post_only_crossing_check(&order, market)?;
+            insert_order(&mut market.asks(), order)
+        }
+    }?;
+
+    Sector::check_in_bounds(market.sectors, user_sector_index_hint)?;
+    let max_orders = market.header.max_orders();
+
+    // 1. Check that the user has enough collateral to place the order and update their seat with
+    //    the resulting decremented amount.
+    {
+        // Find and verify the user's seat with the given index hint.
+        // Safety: The index hint was just verified as in-bounds.
+        let user_seat = unsafe { find_mut_seat_with_hint(market, user_sector_index_hint, user) }?;
+        if is_bid {
+            // If the user is posting a bid, they intend to provide quote and receive base.
+            user_seat.try_decrement_quote_available(quote_atoms)?;
+        } else {
+            // If the user is posting an ask, they intend to provide base and receive quote.
+            user_seat.try_decrement_base_available(base_atoms)?;
+        }
+    }
+
+    // 2. Add the order to the user's bids or asks. This also checks that the user doesn't exceed
+    //    the market's max number of orders per side.
+    // Safety: The user's seat was just verified at the in-bounds index hint and the order was just
+    // inserted at the order sector index, so it isn't in any user's orders yet.
+    unsafe {
+        UserOrders::new(market.sectors, user_sector_index_hint, is_bid)
+            .add(order_sector_index, max_orders)
+    }?;
+
+    Ok(order_sector_index)
+}
+
+/// Cancels a user's bid or ask order on the market's order book, returning the order's remaining
+/// collateral to the user's seat.
+///
+/// Fails if the order at `order_sector_index` isn't one of the user's orders on the given side of
+/// the book.
+///
+/// Returns the cancelled order's client order id.
+pub fn cancel_user_order(
+    market: &mut MarketRefMut<'_>,
+    user: &Address,
+    order_sector_index: SectorIndex,
+    is_bid: bool,
+    user_sector_index_hint: SectorIndex,
+) -> Result<u64, DropsetError> {
+    // Remove the order from the user's orders. This fails if the order doesn't belong to the user.
+    {
+        Sector::check_in_bounds(market.sectors, user_sector_index_hint)?;
+        // Safety: The user sector index hint was just verified in-bounds.
+        unsafe { find_seat_with_hint(market, user_sector_index_hint, user) }?;
+        // Safety: The user's seat was just verified at the in-bounds index hint.
+        unsafe { UserOrders::new(market.sectors, user_sector_index_hint, is_bid) }
+            .remove(order_sector_index)?;
+    }
+
+    // The safety comment below explains why this isn't explicitly necessary.
+    debug_assert!(Sector::check_in_bounds(market.sectors, order_sector_index).is_ok());
+
+    // Load the order given the order sector index.
+    let order = {
+        // Safety: The order sector index was just removed from the user's orders, so it still
+        // points to a sector with a valid order. All order sector indices in a user's
+        // orders are thus in-bounds and don't need to be explicitly verified as in-bounds.
+        unsafe { load_order_from_sector_index(market, order_sector_index) }
+    };
+
+    let client_order_id = order.client_order_id();
+
+    // If the user placed a bid, they provided quote as collateral. If they placed an ask, they
+    // provided base as collateral.
+    let order_size_remaining = if is_bid {
+        order.quote_remaining()
+    } else {
+        order.base_remaining()
+    };
+
+    // Increment the user's collateral in their market seat by the amount remaining in the order.
+    {
+        // Safety: The seat hint was already validated as in-bounds. It could only possibly be out
+        // of bounds now if the account data size was just reduced, which it was not.
+        let sector =
+            unsafe { Sector::from_sector_index_mut(market.sectors, user_sector_index_hint) };
+        let user_seat = sector.load_payload_mut::<MarketSeat>();
+        if is_bid {
+            user_seat.try_increment_quote_available(order_size_remaining)?;
+        } else {
+            user_seat.try_increment_base_available(order_size_remaining)?;
+        }
+    }
+
+    // Remove the order at the order sector index from the appropriate orders collection.
+    unsafe {
+        // Safety: The order sector index removed from the user's orders is still in-bounds.
+        if is_bid {
+            market.bids().remove_at(order_sector_index);
+        } else {
+            market.asks().remove_at(order_sector_index);
+        }
+    }
+
+    Ok(client_order_id)
+}
+
+/// The result of amending a user's order with [`amend_user_order`].
+#[derive(Clone, Copy, Debug)]
+pub struct AmendedOrder {
+    /// The amended order's sector index, which differs from its previous sector index if the order
+    /// was requeued.
+    pub order_sector_index: SectorIndex,
+    /// Whether or not the order was moved to the back of its price level.
+    pub requeued: bool,
+    /// The amended order's client order id.
+    pub client_order_id: u64,
+}
+
+/// Amends the size of a user's bid or ask order on the market's order book and adjusts the
+/// collateral in the user's seat to match.
+///
+/// Decreasing (or keeping) an order's base size amends the order in place, so it keeps its queue
+/// position. Increasing it moves the order to the back of its price level, since it would otherwise
+/// jump ahead of the size posted at the same price before it.
+///
+/// Fails if the order at `order_sector_index` isn't one of the user's orders on the given side of
+/// the book, if the order is already expired at the current `unix_timestamp`, if the new order
+/// info's price doesn't match the order's price, or if the market has an oracle and the price is
+/// outside the market's oracle band.
+pub fn amend_user_order(
+    market: &mut MarketRefMut<'_>,
+    user: &Address,
+    order_sector_index: SectorIndex,
+    is_bid: bool,
+    user_sector_index_hint: SectorIndex,
+    order_info: OrderInfo,
+    unix_timestamp: u64,
+) -> Result<AmendedOrder, DropsetError> {
+    let (new_base, new_quote) = (order_info.base_atoms, order_info.quote_atoms);
+
+    // Verify that the order belongs to the user.
+    {
+        Sector::check_in_bounds(market.sectors, user_sector_index_hint)?;
+        // Safety: The user sector index hint was just verified in-bounds.
+        let user_seat = unsafe { find_seat_with_hint(market, user_sector_index_hint, user) }?;
+        let order_sectors = if is_bid {
+            &user_seat.user_order_sectors.bids
+        } else {
+            &user_seat.user_order_sectors.asks
+        };
+        if !order_sectors.contains(market.sectors, order_sector_index) {
+            return Err(DropsetError::OrderNotFound);
+        }
+    }
+
+    // Safety: The order sector index is in the user's orders, so it points to a valid order.
+    let order = unsafe { load_order_from_sector_index(market, order_sector_index) }.clone();
+    // Expired orders can only be cancelled or pruned.
+    if order.is_expired(unix_timestamp) {
+        return Err(DropsetError::OrderAlreadyExpired);
+    }
+    if order.encoded_price() != order_info.encoded_price.as_u32() {
+        return Err(DropsetError::AmendPriceMismatch);
+    }
+    market
+        .header
+        .check_oracle_band(order_info.encoded_price.as_u32(), unix_timestamp)?;
+
+    // Update the user's collateral by the difference between the order's old and new sizes. If the
+    // user placed a bid, they provided quote as collateral. If they placed an ask, they provided
+    // base as collateral.
+    {
+        // Safety: The user sector index hint was already verified in-bounds.
+        let sector =
+            unsafe { Sector::from_sector_index_mut(market.sectors, user_sector_index_hint) };
+        let user_seat = sector.load_payload_mut::<MarketSeat>();
+        if is_bid {
+            let old_quote = order.quote_remaining();
+            if new_quote > old_quote {
+                user_seat.try_decrement_quote_available(new_quote - old_quote)?;
+            } else {
+                user_seat.try_increment_quote_available(old_quote - new_quote)?;
+            }
+        } else {
+            let old_base = order.base_remaining();
+            if new_base > old_base {
+                user_seat.try_decrement_base_available(new_base - old_base)?;
+            } else {
+                user_seat.try_increment_base_available(old_base - new_base)?;
+            }
+        }
+    }
+
+    let requeued = new_base > order.base_remaining();
+
+    let amended_sector_index = if requeued {
+        let mut amended_order = order.clone();
+        amended_order.set_base_remaining(new_base);
+        amended_order.set_quote_remaining(new_quote);
+
+        // Remove the order and insert the amended order at the back of its price level. The removed
+        // sector is pushed onto the free stack, so the insertion can't run out of sectors. The
+        // amended order is copied from the order before it was removed, so it keeps its links in
+        // the user's orders.
+        // Safety: The order sector index points to a valid order on the given side of the book.
+        let new_index = if is_bid {
+            unsafe { market.bids().remove_at(order_sector_index) };
+            insert_order(&mut market.bids(), amended_order)
+        } else {
+            unsafe { market.asks().remove_at(order_sector_index) };
+            insert_order(&mut market.asks(), amended_order)
+        }?;
+
+        // Re-link the order's neighbors in the user's orders to the order's new sector.
+        // Safety: The user's seat was already verified at the in-bounds index hint, and the order
+        // at the new sector index is a copy of the user's order with its links unchanged.
+        unsafe {
+            UserOrders::new(market.sectors, user_sector_index_hint, is_bid).replace(new_index)
+        };
+
+        new_index
+    } else {
+        // Safety: The order sector index points to a valid order.
+        let order = unsafe { load_mut_order_from_sector_index(market, order_sector_index) };
+        order.set_base_remaining(new_base);
+        order.set_quote_remaining(new_quote);
+
+        order_sector_index
+    };
+
+    Ok(AmendedOrder {
+        order_sector_index: amended_sector_index,
+        requeued,
+        client_order_id: order.client_order_id(),
+    })
+}
+
+/// Finds the sector index of a user's bid or ask order with the passed client order id.
+///
+/// The client order id should not be [`dropset_interface::state::order::NO_CLIENT_ORDER_ID`]. If
+/// multiple orders share the same client order id, the first one found is returned.
+///
+/// # Safety
+///
+/// Caller guarantees `user_sector_index_hint` is in-bounds of `market.sectors` bytes.
+pub unsafe fn find_user_order_by_client_order_id<H, S>(
+    market: &Market<H, S>,
+    user: &Address,
+    is_bid: bool,
+    user_sector_index_hint: SectorIndex,
+    client_order_id: u64,
+) -> Result<SectorIndex, DropsetError>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    // Safety: Caller guarantees the index hint is in-bounds.
+    let user_seat = unsafe { find_seat_with_hint(market, user_sector_index_hint, user) }?;
+    let order_sectors = if is_bid {
+        &user_seat.user_order_sectors.bids
+    } else {
+        &user_seat.user_order_sectors.asks
+    };
+
+    order_sectors
+        .iter(market.sectors.as_ref())
+        .find(|(_, order)| order.client_order_id() == client_order_id)
+        .map(|(order_sector_index, _)| order_sector_index)
+        .ok_or(DropsetError::OrderNotFound)
+}
+
+/// Removes an order from the market's order book on behalf of its maker, returning the order's
+/// remaining collateral to the maker's seat. This is used to remove expired orders and orders
+/// outside the market's oracle band, so the caller is expected to have already checked that the
+/// order is one of them.
+///
+/// Returns a copy of the removed order.
+///
+/// # Safety
+///
+/// Caller guarantees `validated_sector_index` is in-bounds of `market.sectors` bytes and points to
+/// a valid bid order if `is_bid` is true or a valid ask order if it's false.
+pub unsafe fn expire_order(
+    market: &mut MarketRefMut<'_>,
+    validated_sector_index: SectorIndex,
+    is_bid: bool,
+) -> Result<Order, DropsetError> {
+    // Safety: Caller guarantees `validated_sector_index` is in-bounds.
+    let order = unsafe { load_order_from_sector_index(market, validated_sector_index) }.clone();
+
+    let maker = {
+        // Safety: The order's user seat sector index is in-bounds, as it came from a valid order.
+        let sector = unsafe { Sector::from_sector_index(market.sectors, order.user_seat()) };
+        sector.load_payload::<MarketSeat>().user
+    };
+
+    cancel_user_order(
+        market,
+        &maker,
+        validated_sector_index,
+        is_bid,
+        order.user_seat(),
+    )?;
+
+    Ok(order)
+}
+
 #[cfg(test)]
 mod tests {
     extern crate std;
@@ -95,31 +475,34 @@ mod tests {
         vec::*,
     };
 
-    use dropset_interface::state::{
-        asks_dll::{
-            AskOrders,
-            AskOrdersLinkedList,
-        },
-        bids_dll::{
-            BidOrders,
-            BidOrdersLinkedList,
-        },
-        linked_list::{
-            LinkedList,
-            LinkedListHeaderOperations,
-        },
-        market::MarketRefMut,
-        market_header::MarketHeader,
-        order::{
-            Order,
-            OrdersCollection,
-        },
-        sector::{
-            SectorIndex,
-            NIL,
-            SECTOR_SIZE,
+    use dropset_interface::{
+        instructions::MarketParams,
+        state::{
+            asks_dll::{
+                AskOrders,
+                AskOrdersLinkedList,
+            },
+            bids_dll::{
+                BidOrders,
+                BidOrdersLinkedList,
+            },
+            linked_list::{
+                LinkedList,
+                LinkedListHeaderOperations,
+            },
+            market::MarketRefMut,
+            market_header::MarketHeader,
+            order::{
+                Order,
+                OrdersCollection,
+            },
+            sector::{
+                SectorIndex,
+                NIL,
+                SECTOR_SIZE,
+            },
+            transmutable::Transmutable,
         },
-        transmutable::Transmutable,
     };
     use price::{
         biased_exponent,
@@ -152,6 +535,8 @@ mod tests {
             &Address::from_str_const("11111111111111111111111111111111111111111111"),
             &Address::from_str_const("22222222222222222222222222222222222222222222"),
             254,
+            &Address::from_str_const("33333333333333333333333333333333333333333333"),
+            &MarketParams::default(),
         )
         .expect("Should initialize market data")
     }
@@ -8,13 +8,18 @@ use dropset_interface::{
             MarketRefMut,
         },
         market_header::MarketHeader,
-        market_seat::MarketSeat,
+        market_seat::{
+            MarketSeat,
+            NO_SEAT_DELEGATE,
+        },
+        seat_delegate::SeatDelegate,
         seats_dll::SeatsLinkedList,
         sector::{
             Sector,
             SectorIndex,
             NIL,
         },
+        sector_kind::SectorKind,
     },
 };
 use solana_address::{
@@ -22,10 +27,16 @@ use solana_address::{
     Address,
 };
 
+/// Inserts a new seat into the seats list, keeping it sorted by user address.
+///
+/// Fails if the user already has a seat, or if the market is permissioned and the seat isn't
+/// allowlisted, so that only the market authority can register seats on permissioned markets.
 pub fn try_insert_market_seat(
     list: &mut SeatsLinkedList,
     seat: MarketSeat,
 ) -> Result<SectorIndex, DropsetError> {
+    list.header.check_seat_allowlisted(&seat)?;
+
     let (prev_index, next_index) = find_new_seat_prev_and_next(list, &seat.user);
     let seat_bytes = seat.as_bytes();
 
@@ -75,7 +86,97 @@ fn find_new_seat_prev_and_next(
     (list.header.seats_dll_tail(), NIL)
 }
 
-/// Tries to find a market seat given an index hint.
+/// Returns the sector index of a user's market seat.
+///
+/// A non-NIL `hint` must point directly to the user's seat. A NIL `hint` falls back to searching
+/// the seats list by address with [`find_seat_by_address`], so clients can omit the hint instead of
+/// first fetching the market account.
+pub fn find_seat_sector_index<H, S>(
+    market: &Market<H, S>,
+    hint: SectorIndex,
+    user: &Address,
+) -> Result<SectorIndex, DropsetError>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    if hint == NIL {
+        return find_seat_by_address(market, user).ok_or(DropsetError::SeatNotFound);
+    }
+
+    Sector::check_in_bounds(market.sectors.as_ref(), hint)?;
+    // Safety: The index hint was just verified as in-bounds.
+    unsafe { find_seat_with_hint(market, hint, user) }?;
+    Ok(hint)
+}
+
+/// Returns the sector index and user of the market seat the `signer` may mutate orders for.
+///
+/// The signer may act for its own seat or for a seat it's the delegate of. A NIL `hint` only finds
+/// the signer's own seat, so a delegate must always pass the seat's sector index. A delegate can
+/// only take liquidity (`is_taking`) if the seat's user allowed it, and on permissioned markets
+/// only allowlisted seats can take liquidity.
+pub fn find_authorized_seat<H, S>(
+    market: &Market<H, S>,
+    hint: SectorIndex,
+    signer: &Address,
+    is_taking: bool,
+) -> Result<(SectorIndex, Address), DropsetError>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    let index = if hint == NIL {
+        find_seat_by_address(market, signer).ok_or(DropsetError::SeatNotFound)?
+    } else {
+        Sector::check_in_bounds(market.sectors.as_ref(), hint)?;
+        hint
+    };
+
+    // Safety: The index was either found by iterating the seats list or verified as in-bounds.
+    let sector = unsafe { Sector::from_sector_index(market.sectors.as_ref(), index) };
+    if !sector.is_kind(SectorKind::Seat) {
+        return Err(DropsetError::InvalidIndexHint);
+    }
+    let seat = sector.load_payload::<MarketSeat>();
+    if !address_eq(signer, &seat.user) {
+        let delegate = match market.seat_delegate(seat) {
+            Some(delegate) if address_eq(signer, &delegate.delegate) => delegate,
+            _ => return Err(DropsetError::InvalidIndexHint),
+        };
+        if is_taking && !delegate.can_take() {
+            return Err(DropsetError::DelegateCannotTake);
+        }
+    }
+    if is_taking {
+        market.header.as_ref().check_seat_allowlisted(seat)?;
+    }
+
+    Ok((index, seat.user))
+}
+
+/// Linearly scans the seats list for a user's market seat and returns its sector index.
+///
+/// Seats are sorted by user address, so the scan stops at the first seat that sorts after `user`.
+pub fn find_seat_by_address<H, S>(market: &Market<H, S>, user: &Address) -> Option<SectorIndex>
+where
+    H: AsRef<MarketHeader>,
+    S: AsRef<[u8]>,
+{
+    for (index, sector) in market.iter_seats() {
+        let seat = sector.load_payload::<MarketSeat>();
+        if address_eq(user, &seat.user) {
+            return Some(index);
+        }
+        if user < &seat.user {
+            break;
+        }
+    }
+    None
+}
+
+/// Tries to find a market seat given an index hint. The hint must point to a live seat sector, not
+/// just any in-bounds sector whose payload happens to start with `user`.
 ///
 /// # Safety
 ///
@@ -91,6 +192,9 @@ where
 {
     // Safety: Caller guarantees `hint` is in-bounds.
     let sector = unsafe { Sector::from_sector_index(market.sectors.as_ref(), hint) };
+    if !sector.is_kind(SectorKind::Seat) {
+        return Err(DropsetError::InvalidIndexHint);
+    }
     let seat = sector.load_payload::<MarketSeat>();
     if address_eq(user, &seat.user) {
         Ok(seat)
@@ -99,7 +203,26 @@ where
     }
 }
 
-/// Tries to find a mutable market seat given an index hint.
+/// Settles a taker's fill with their market seat, debiting the amount paid in and crediting the
+/// amount received out, as returned by [`dropset_interface::matching::swap_amounts`].
+///
+/// A buy pays quote and receives base, and a sell pays base and receives quote.
+pub fn settle_taker_fill_with_seat(
+    user_seat: &mut MarketSeat,
+    is_buy: bool,
+    amount_in: u64,
+    amount_out: u64,
+) -> Result<(), DropsetError> {
+    if is_buy {
+        user_seat.try_decrement_quote_available(amount_in)?;
+        user_seat.try_increment_base_available(amount_out)
+    } else {
+        user_seat.try_decrement_base_available(amount_in)?;
+        user_seat.try_increment_quote_available(amount_out)
+    }
+}
+
+/// Tries to find a mutable market seat given an index hint. See [`find_seat_with_hint`].
 ///
 /// # Safety
 ///
@@ -111,6 +234,9 @@ pub unsafe fn find_mut_seat_with_hint<'m>(
 ) -> Result<&'m mut MarketSeat, DropsetError> {
     // Safety: Caller guarantees `hint` is in-bounds.
     let sector = unsafe { Sector::from_sector_index_mut(market.sectors, hint) };
+    if !sector.is_kind(SectorKind::Seat) {
+        return Err(DropsetError::InvalidIndexHint);
+    }
     let seat = sector.load_payload_mut::<MarketSeat>();
     if address_eq(user, &seat.user) {
         Ok(seat)
@@ -118,3 +244,66 @@ pub unsafe fn find_mut_seat_with_hint<'m>(
         Err(DropsetError::InvalidIndexHint)
     }
 }
+
+/// Sets the delegate of the seat at `seat_index`, overwriting its delegate sector or writing to a
+/// sector popped from the free stack if the seat doesn't have a delegate yet.
+///
+/// # Safety
+///
+/// Caller guarantees `seat_index` is in-bounds of `market.sectors` bytes and points to a seat.
+pub unsafe fn set_seat_delegate(
+    market: &mut MarketRefMut<'_>,
+    seat_index: SectorIndex,
+    delegate: SeatDelegate,
+) -> Result<(), DropsetError> {
+    // Safety: Caller guarantees `seat_index` points to a seat.
+    let delegate_sector = unsafe { Sector::from_sector_index(market.sectors, seat_index) }
+        .load_payload::<MarketSeat>()
+        .delegate_sector();
+    let delegate_sector = match delegate_sector {
+        NIL => market.free_stack().pop_free_sector()?,
+        index => index,
+    };
+
+    // Safety: The delegate sector is either the seat's existing delegate sector or was just popped
+    // from the free stack, and `seat_index` points to a seat.
+    unsafe {
+        let sector = Sector::from_sector_index_mut(market.sectors, delegate_sector);
+        sector.set_payload(delegate.as_bytes());
+        sector.set_kind(SectorKind::SeatDelegate);
+        Sector::from_sector_index_mut(market.sectors, seat_index)
+            .load_payload_mut::<MarketSeat>()
+            .set_delegate_sector(delegate_sector);
+    }
+
+    Ok(())
+}
+
+/// Removes the delegate of the seat at `seat_index` and pushes its delegate sector to the free
+/// stack. Returns the removed delegate, or [`NO_SEAT_DELEGATE`] if the seat didn't have one.
+///
+/// # Safety
+///
+/// Caller guarantees `seat_index` is in-bounds of `market.sectors` bytes and points to a seat.
+pub unsafe fn remove_seat_delegate(
+    market: &mut MarketRefMut<'_>,
+    seat_index: SectorIndex,
+) -> Address {
+    // Safety: Caller guarantees `seat_index` points to a seat.
+    let seat = unsafe { Sector::from_sector_index_mut(market.sectors, seat_index) }
+        .load_payload_mut::<MarketSeat>();
+    let delegate_sector = seat.delegate_sector();
+    if delegate_sector == NIL {
+        return NO_SEAT_DELEGATE;
+    }
+    seat.set_delegate_sector(NIL);
+
+    // Safety: A seat's delegate sector always points to a valid delegate.
+    let delegate = unsafe { Sector::from_sector_index(market.sectors, delegate_sector) }
+        .load_payload::<SeatDelegate>()
+        .delegate;
+    // Safety: The delegate sector is in-bounds and no longer referenced by the seat.
+    unsafe { market.free_stack().push_free_sector(delegate_sector) };
+
+    delegate
+}
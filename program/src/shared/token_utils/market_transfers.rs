@@ -12,6 +12,7 @@ use pinocchio::{
 };
 
 use crate::{
+    context::market_order_context::MarketOrderContext,
     market_signer,
     validation::{
         market_account_view::MarketAccountView,
@@ -150,3 +151,49 @@ pub unsafe fn withdraw_non_zero_from_market<'t, 'a>(
         .invoke_signed(&[market_signer!(base_mint, quote_mint, market_bump)])
     }
 }
+
+/// Settles a taker's fill with their token accounts, depositing the amount paid in to the market
+/// and withdrawing the amount received out, as returned by
+/// [`dropset_interface::matching::swap_amounts`]. See
+/// [`crate::shared::seat_operations::settle_taker_fill_with_seat`] for the seat counterpart.
+///
+/// A buy pays quote and receives base, and a sell pays base and receives quote. Fails if the
+/// amount deposited doesn't match the amount paid in exactly.
+///
+/// # Safety
+///
+/// Caller guarantees no account data in `ctx` is currently borrowed.
+pub unsafe fn settle_taker_fill_with_token_accounts(
+    ctx: &MarketOrderContext<'_>,
+    is_buy: bool,
+    amount_in: u64,
+    amount_out: u64,
+) -> ProgramResult {
+    #[rustfmt::skip]
+    let (in_user_ata, in_market_ata, in_mint, out_user_ata, out_market_ata, out_mint) = if is_buy {
+        (&ctx.quote_user_ata, &ctx.quote_market_ata, &ctx.quote_mint, &ctx.base_user_ata, &ctx.base_market_ata, &ctx.base_mint)
+    } else {
+        (&ctx.base_user_ata, &ctx.base_market_ata, &ctx.base_mint, &ctx.quote_user_ata, &ctx.quote_market_ata, &ctx.quote_mint)
+    };
+
+    // Safety: No account data is currently borrowed.
+    let amount_deposited = unsafe {
+        deposit_non_zero_to_market(in_user_ata, in_market_ata, ctx.user, in_mint, amount_in)
+    }?;
+
+    // Ensure that the amount paid matches the exact amount transferred.
+    if amount_deposited != amount_in {
+        return Err(DropsetError::AmountFilledVsTransferredMismatch.into());
+    }
+
+    // Safety: No account data is currently borrowed.
+    unsafe {
+        withdraw_non_zero_from_market(
+            out_user_ata,
+            out_market_ata,
+            &ctx.market_account,
+            out_mint,
+            amount_out,
+        )
+    }
+}
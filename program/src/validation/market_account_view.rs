@@ -20,11 +20,15 @@ use dropset_interface::{
 };
 use pinocchio::{
     account::AccountView,
+    error::ProgramError,
     hint::unlikely,
     ProgramResult,
 };
 
-use crate::shared::account_resize::fund_then_resize_unchecked;
+use crate::shared::account_resize::{
+    fund_then_resize_unchecked,
+    resize_then_refund_unchecked,
+};
 
 /// A validated wrapper around a raw market [`AccountView`], providing safe access
 /// to the market header and sector data after verifying ownership and layout.
@@ -162,4 +166,35 @@ impl<'a> MarketAccountView<'a> {
 
         Ok(())
     }
+
+    /// Truncates the market account's trailing `num_sectors` sectors and refunds the lamports no
+    /// longer required for rent to `recipient`. Returns the number of lamports refunded.
+    ///
+    /// # Safety
+    ///
+    /// Caller guarantees:
+    /// - WRITE accounts are not currently borrowed in *any* capacity.
+    /// - The trailing `num_sectors` sectors are neither in use nor on the free stack, e.g. after
+    ///   [`crate::shared::market_operations::compact_market_sectors`].
+    ///
+    /// ### Accounts
+    ///   0. `[WRITE]` Market account
+    ///   1. `[WRITE]` Recipient
+    #[inline(always)]
+    pub unsafe fn truncate(
+        &mut self,
+        recipient: &AccountView,
+        num_sectors: u32,
+    ) -> Result<u64, ProgramError> {
+        let removed_space = (num_sectors as usize) * SECTOR_SIZE;
+        let new_size = self
+            .account
+            .data_len()
+            .checked_sub(removed_space)
+            .filter(|new_size| *new_size >= MarketHeader::LEN)
+            .ok_or(DropsetError::IndexOutOfBounds)?;
+
+        // Safety: Scoped writes to the market account and recipient to truncate the market account.
+        unsafe { resize_then_refund_unchecked(self.account, recipient, new_size) }
+    }
 }
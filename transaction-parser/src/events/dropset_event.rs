@@ -2,14 +2,36 @@
 //! events or contiguous instruction data.
 
 use dropset_interface::events::{
+    AddAllowlistedTraderEventInstructionData,
+    AmendOrderEventInstructionData,
     CancelOrderEventInstructionData,
+    CloseMarketEventInstructionData,
     CloseSeatEventInstructionData,
+    CompactMarketEventInstructionData,
     DepositEventInstructionData,
     DropsetEventTag,
+    ExpandMarketEventInstructionData,
+    ExpireOrderEventInstructionData,
+    FillEventInstructionData,
     HeaderEventInstructionData,
+    LimitOrderEventInstructionData,
     MarketOrderEventInstructionData,
     PostOrderEventInstructionData,
     RegisterMarketEventInstructionData,
+    RemoveAllowlistedTraderEventInstructionData,
+    RemoveOutOfBandOrderEventInstructionData,
+    RenounceMarketAuthorityEventInstructionData,
+    RevokeSeatDelegateEventInstructionData,
+    SelfTradeCancelRestingEventInstructionData,
+    SelfTradeCancelTakingEventInstructionData,
+    SelfTradeDecrementBothEventInstructionData,
+    SetMarketOracleEventInstructionData,
+    SetSeatDelegateEventInstructionData,
+    SweepFeesEventInstructionData,
+    TransferMarketAuthorityEventInstructionData,
+    UpdateMarketParamsEventInstructionData,
+    UpdateMarketStatusEventInstructionData,
+    UpdateOraclePriceEventInstructionData,
     WithdrawEventInstructionData,
 };
 use instruction_macros_traits::Tagged;
@@ -26,6 +48,28 @@ pub enum DropsetEvent {
     PostOrder(PostOrderEventInstructionData),
     CancelOrder(CancelOrderEventInstructionData),
     MarketOrder(MarketOrderEventInstructionData),
+    LimitOrder(LimitOrderEventInstructionData),
+    Fill(FillEventInstructionData),
+    SweepFees(SweepFeesEventInstructionData),
+    UpdateMarketParams(UpdateMarketParamsEventInstructionData),
+    TransferMarketAuthority(TransferMarketAuthorityEventInstructionData),
+    RenounceMarketAuthority(RenounceMarketAuthorityEventInstructionData),
+    UpdateMarketStatus(UpdateMarketStatusEventInstructionData),
+    SelfTradeCancelResting(SelfTradeCancelRestingEventInstructionData),
+    SelfTradeCancelTaking(SelfTradeCancelTakingEventInstructionData),
+    SelfTradeDecrementBoth(SelfTradeDecrementBothEventInstructionData),
+    ExpireOrder(ExpireOrderEventInstructionData),
+    AmendOrder(AmendOrderEventInstructionData),
+    CompactMarket(CompactMarketEventInstructionData),
+    ExpandMarket(ExpandMarketEventInstructionData),
+    CloseMarket(CloseMarketEventInstructionData),
+    SetSeatDelegate(SetSeatDelegateEventInstructionData),
+    RevokeSeatDelegate(RevokeSeatDelegateEventInstructionData),
+    AddAllowlistedTrader(AddAllowlistedTraderEventInstructionData),
+    RemoveAllowlistedTrader(RemoveAllowlistedTraderEventInstructionData),
+    SetMarketOracle(SetMarketOracleEventInstructionData),
+    UpdateOraclePrice(UpdateOraclePriceEventInstructionData),
+    RemoveOutOfBandOrder(RemoveOutOfBandOrderEventInstructionData),
 }
 
 impl DropsetEvent {
@@ -39,6 +83,40 @@ impl DropsetEvent {
             Self::PostOrder(_) => PostOrderEventInstructionData::LEN_WITH_TAG,
             Self::CancelOrder(_) => CancelOrderEventInstructionData::LEN_WITH_TAG,
             Self::MarketOrder(_) => MarketOrderEventInstructionData::LEN_WITH_TAG,
+            Self::LimitOrder(_) => LimitOrderEventInstructionData::LEN_WITH_TAG,
+            Self::Fill(_) => FillEventInstructionData::LEN_WITH_TAG,
+            Self::SweepFees(_) => SweepFeesEventInstructionData::LEN_WITH_TAG,
+            Self::UpdateMarketParams(_) => UpdateMarketParamsEventInstructionData::LEN_WITH_TAG,
+            Self::TransferMarketAuthority(_) => {
+                TransferMarketAuthorityEventInstructionData::LEN_WITH_TAG
+            }
+            Self::RenounceMarketAuthority(_) => {
+                RenounceMarketAuthorityEventInstructionData::LEN_WITH_TAG
+            }
+            Self::UpdateMarketStatus(_) => UpdateMarketStatusEventInstructionData::LEN_WITH_TAG,
+            Self::SelfTradeCancelResting(_) => {
+                SelfTradeCancelRestingEventInstructionData::LEN_WITH_TAG
+            }
+            Self::SelfTradeCancelTaking(_) => {
+                SelfTradeCancelTakingEventInstructionData::LEN_WITH_TAG
+            }
+            Self::SelfTradeDecrementBoth(_) => {
+                SelfTradeDecrementBothEventInstructionData::LEN_WITH_TAG
+            }
+            Self::ExpireOrder(_) => ExpireOrderEventInstructionData::LEN_WITH_TAG,
+            Self::AmendOrder(_) => AmendOrderEventInstructionData::LEN_WITH_TAG,
+            Self::CompactMarket(_) => CompactMarketEventInstructionData::LEN_WITH_TAG,
+            Self::ExpandMarket(_) => ExpandMarketEventInstructionData::LEN_WITH_TAG,
+            Self::CloseMarket(_) => CloseMarketEventInstructionData::LEN_WITH_TAG,
+            Self::SetSeatDelegate(_) => SetSeatDelegateEventInstructionData::LEN_WITH_TAG,
+            Self::RevokeSeatDelegate(_) => RevokeSeatDelegateEventInstructionData::LEN_WITH_TAG,
+            Self::AddAllowlistedTrader(_) => AddAllowlistedTraderEventInstructionData::LEN_WITH_TAG,
+            Self::RemoveAllowlistedTrader(_) => {
+                RemoveAllowlistedTraderEventInstructionData::LEN_WITH_TAG
+            }
+            Self::SetMarketOracle(_) => SetMarketOracleEventInstructionData::LEN_WITH_TAG,
+            Self::UpdateOraclePrice(_) => UpdateOraclePriceEventInstructionData::LEN_WITH_TAG,
+            Self::RemoveOutOfBandOrder(_) => RemoveOutOfBandOrderEventInstructionData::LEN_WITH_TAG,
         }
     }
 }
@@ -122,6 +200,90 @@ impl DropsetEvent {
             DropsetEventTag::MarketOrderEvent => Ok(DropsetEvent::MarketOrder(
                 MarketOrderEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
             )),
+            DropsetEventTag::LimitOrderEvent => Ok(DropsetEvent::LimitOrder(
+                LimitOrderEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::FillEvent => Ok(DropsetEvent::Fill(
+                FillEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::SweepFeesEvent => Ok(DropsetEvent::SweepFees(
+                SweepFeesEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::UpdateMarketParamsEvent => Ok(DropsetEvent::UpdateMarketParams(
+                UpdateMarketParamsEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::TransferMarketAuthorityEvent => {
+                Ok(DropsetEvent::TransferMarketAuthority(
+                    TransferMarketAuthorityEventInstructionData::unpack_untagged(data)
+                        .map_err(|_| err())?,
+                ))
+            }
+            DropsetEventTag::RenounceMarketAuthorityEvent => {
+                Ok(DropsetEvent::RenounceMarketAuthority(
+                    RenounceMarketAuthorityEventInstructionData::unpack_untagged(data)
+                        .map_err(|_| err())?,
+                ))
+            }
+            DropsetEventTag::UpdateMarketStatusEvent => Ok(DropsetEvent::UpdateMarketStatus(
+                UpdateMarketStatusEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::SelfTradeCancelRestingEvent => {
+                Ok(DropsetEvent::SelfTradeCancelResting(
+                    SelfTradeCancelRestingEventInstructionData::unpack_untagged(data)
+                        .map_err(|_| err())?,
+                ))
+            }
+            DropsetEventTag::SelfTradeCancelTakingEvent => Ok(DropsetEvent::SelfTradeCancelTaking(
+                SelfTradeCancelTakingEventInstructionData::unpack_untagged(data)
+                    .map_err(|_| err())?,
+            )),
+            DropsetEventTag::SelfTradeDecrementBothEvent => {
+                Ok(DropsetEvent::SelfTradeDecrementBoth(
+                    SelfTradeDecrementBothEventInstructionData::unpack_untagged(data)
+                        .map_err(|_| err())?,
+                ))
+            }
+            DropsetEventTag::ExpireOrderEvent => Ok(DropsetEvent::ExpireOrder(
+                ExpireOrderEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::AmendOrderEvent => Ok(DropsetEvent::AmendOrder(
+                AmendOrderEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::CompactMarketEvent => Ok(DropsetEvent::CompactMarket(
+                CompactMarketEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::ExpandMarketEvent => Ok(DropsetEvent::ExpandMarket(
+                ExpandMarketEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::CloseMarketEvent => Ok(DropsetEvent::CloseMarket(
+                CloseMarketEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::SetSeatDelegateEvent => Ok(DropsetEvent::SetSeatDelegate(
+                SetSeatDelegateEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::RevokeSeatDelegateEvent => Ok(DropsetEvent::RevokeSeatDelegate(
+                RevokeSeatDelegateEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::AddAllowlistedTraderEvent => Ok(DropsetEvent::AddAllowlistedTrader(
+                AddAllowlistedTraderEventInstructionData::unpack_untagged(data)
+                    .map_err(|_| err())?,
+            )),
+            DropsetEventTag::RemoveAllowlistedTraderEvent => {
+                Ok(DropsetEvent::RemoveAllowlistedTrader(
+                    RemoveAllowlistedTraderEventInstructionData::unpack_untagged(data)
+                        .map_err(|_| err())?,
+                ))
+            }
+            DropsetEventTag::SetMarketOracleEvent => Ok(DropsetEvent::SetMarketOracle(
+                SetMarketOracleEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::UpdateOraclePriceEvent => Ok(DropsetEvent::UpdateOraclePrice(
+                UpdateOraclePriceEventInstructionData::unpack_untagged(data).map_err(|_| err())?,
+            )),
+            DropsetEventTag::RemoveOutOfBandOrderEvent => Ok(DropsetEvent::RemoveOutOfBandOrder(
+                RemoveOutOfBandOrderEventInstructionData::unpack_untagged(data)
+                    .map_err(|_| err())?,
+            )),
         }
     }
 }
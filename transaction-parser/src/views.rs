@@ -4,9 +4,14 @@
 use std::collections::HashMap;
 
 use dropset_interface::state::{
+    compaction_phase::CompactionPhase,
     market::MarketRef,
     market_header::MarketHeader,
-    market_seat::MarketSeat,
+    market_seat::{
+        MarketSeat,
+        NO_SEAT_DELEGATE,
+    },
+    market_status::MarketStatus,
     sector::Sector,
     order::Order,
     sector::SectorIndex,
@@ -34,7 +39,24 @@ pub struct MarketHeaderView {
     pub quote_mint: Address,
     pub market_bump: u8,
     pub nonce: u64,
-    pub _padding: [u8; 3],
+    pub authority: Address,
+    pub fee_authority: Address,
+    pub taker_fee_bps: u16,
+    pub maker_rebate_bps: u16,
+    pub protocol_fees: u64,
+    pub status: MarketStatus,
+    pub tick_size: u32,
+    pub base_lot_size: u64,
+    pub min_base_order_size: u64,
+    pub min_quote_order_size: u64,
+    pub max_orders: u8,
+    pub is_permissioned: bool,
+    pub oracle: Address,
+    pub oracle_band_bps: u16,
+    pub oracle_max_staleness: u32,
+    pub oracle_price: u32,
+    pub oracle_publish_time: u64,
+    pub compaction_phase: CompactionPhase,
 }
 
 /// A view on a market account's data with the collection of type T sectors.
@@ -98,6 +120,9 @@ pub struct MarketSeatView {
     pub index: SectorIndex,
     pub next_index: SectorIndex,
     pub user: Address,
+    pub delegate: Address,
+    pub delegate_can_take: bool,
+    pub is_allowlisted: bool,
     pub base_available: u64,
     pub quote_available: u64,
     pub user_order_sectors: UserOrderSectors,
@@ -112,17 +137,24 @@ pub struct OrderView {
     pub user_seat: SectorIndex,
     pub base_remaining: u64,
     pub quote_remaining: u64,
+    pub expiry: u64,
+    pub client_order_id: u64,
 }
 
-impl From<(SectorIndex, &Sector)> for MarketSeatView {
-    fn from(index_and_seat: (SectorIndex, &Sector)) -> Self {
-        let (sector_index, sector) = index_and_seat;
+/// Seats store their delegate in a separate sector, so the market is needed to view a seat.
+impl From<(&MarketRef<'_>, (SectorIndex, &Sector))> for MarketSeatView {
+    fn from(market_and_seat: (&MarketRef<'_>, (SectorIndex, &Sector))) -> Self {
+        let (market, (sector_index, sector)) = market_and_seat;
         let seat = sector.load_payload::<MarketSeat>();
+        let delegate = market.seat_delegate(seat);
         Self {
             prev_index: sector.prev(),
             index: sector_index,
             next_index: sector.next(),
             user: seat.user,
+            delegate: delegate.map_or(NO_SEAT_DELEGATE, |delegate| delegate.delegate),
+            delegate_can_take: delegate.is_some_and(|delegate| delegate.can_take()),
+            is_allowlisted: seat.is_allowlisted(),
             base_available: seat.base_available(),
             quote_available: seat.quote_available(),
             user_order_sectors: seat.user_order_sectors.clone(),
@@ -142,6 +174,8 @@ impl From<(SectorIndex, &Sector)> for OrderView {
             user_seat: order.user_seat(),
             base_remaining: order.base_remaining(),
             quote_remaining: order.quote_remaining(),
+            expiry: order.expiry(),
+            client_order_id: order.client_order_id(),
         }
     }
 }
@@ -165,14 +199,34 @@ impl From<&MarketHeader> for MarketHeaderView {
             quote_mint: header.quote_mint,
             market_bump: header.market_bump,
             nonce: header.num_events(),
-            _padding: [0; 3],
+            authority: header.authority,
+            fee_authority: header.fee_authority,
+            taker_fee_bps: header.taker_fee_bps(),
+            maker_rebate_bps: header.maker_rebate_bps(),
+            protocol_fees: header.protocol_fees(),
+            status: header.status(),
+            tick_size: header.tick_size(),
+            base_lot_size: header.base_lot_size(),
+            min_base_order_size: header.min_base_order_size(),
+            min_quote_order_size: header.min_quote_order_size(),
+            max_orders: header.max_orders(),
+            is_permissioned: header.is_permissioned(),
+            oracle: header.oracle,
+            oracle_band_bps: header.oracle_band_bps(),
+            oracle_max_staleness: header.oracle_max_staleness(),
+            oracle_price: header.oracle_price(),
+            oracle_publish_time: header.oracle_publish_time(),
+            compaction_phase: header.compaction_phase(),
         }
     }
 }
 
 impl From<MarketRef<'_>> for MarketViewAll {
     fn from(market: MarketRef<'_>) -> Self {
-        let seats = market.iter_seats().map(MarketSeatView::from).collect_vec();
+        let seats = market
+            .iter_seats()
+            .map(|index_and_seat| MarketSeatView::from((&market, index_and_seat)))
+            .collect_vec();
         let bids = market.iter_bids().map(OrderView::from).collect_vec();
         let asks = market.iter_asks().map(OrderView::from).collect_vec();
 